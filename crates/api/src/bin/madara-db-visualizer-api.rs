@@ -44,6 +44,7 @@ async fn main() {
     let state = Arc::new(api::AppState {
         db,
         indexer: Mutex::new(indexer),
+        audits: Mutex::new(Vec::new()),
     });
 
     // Initial sync
@@ -30,13 +30,44 @@ struct Args {
     /// Disable the initial index sync (useful for very large DBs)
     #[arg(long)]
     no_initial_sync: bool,
+
+    /// Follow a running node: open as a RocksDB secondary instance and keep catching up.
+    /// New blocks are indexed as they appear unless --no-initial-sync is set.
+    #[arg(long)]
+    follow: bool,
+
+    /// How often to catch up with the node in follow mode, in milliseconds
+    #[arg(long, default_value_t = 2000)]
+    follow_interval_ms: u64,
+
+    /// Directory for the secondary instance's RocksDB logs in follow mode
+    /// (defaults to MAKIMONO_HOME/state/secondary/<hash>)
+    #[arg(long)]
+    secondary_path: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
-    let db = match DbReader::open(&args.db_path) {
+    let opened = if args.follow {
+        let secondary_path = args
+            .secondary_path
+            .clone()
+            .unwrap_or_else(|| default_secondary_path(&args.db_path));
+        if let Err(e) = std::fs::create_dir_all(&secondary_path) {
+            eprintln!(
+                "Failed to create secondary directory {}: {e}",
+                secondary_path.display()
+            );
+            std::process::exit(1);
+        }
+        DbReader::open_secondary(&args.db_path, &secondary_path)
+    } else {
+        DbReader::open(&args.db_path)
+    };
+
+    let db = match opened {
         Ok(db) => db,
         Err(e) => {
             eprintln!(
@@ -69,6 +100,7 @@ async fn main() {
     let state = Arc::new(api::AppState {
         db,
         indexer: Mutex::new(indexer),
+        audits: Mutex::new(Vec::new()),
     });
 
     if !args.no_initial_sync {
@@ -79,6 +111,14 @@ async fn main() {
         }
     }
 
+    if args.follow {
+        api::spawn_follow(
+            state.clone(),
+            std::time::Duration::from_millis(args.follow_interval_ms),
+            !args.no_initial_sync,
+        );
+    }
+
     // Build API router (no CORS needed for same-origin).
     let api_router = api::build_router(state.clone(), None);
 
@@ -106,6 +146,9 @@ async fn main() {
     eprintln!("makimono-viz: http://{}", addr);
     eprintln!("db: {}", args.db_path.display());
     eprintln!("index: {}", index_path.display());
+    if args.follow {
+        eprintln!("follow: catching up every {}ms", args.follow_interval_ms);
+    }
     if let Some(v) = detected.version {
         let src = detected
             .source_path
@@ -126,6 +169,15 @@ async fn main() {
 }
 
 fn default_index_path(db_path: &Path) -> PathBuf {
+    default_state_path(db_path, "index").with_extension("db")
+}
+
+fn default_secondary_path(db_path: &Path) -> PathBuf {
+    default_state_path(db_path, "secondary")
+}
+
+/// MAKIMONO_HOME/state/<kind>/<sha256 of db path>
+fn default_state_path(db_path: &Path, kind: &str) -> PathBuf {
     let home = std::env::var_os("MAKIMONO_HOME")
         .map(PathBuf::from)
         .or_else(|| {
@@ -139,5 +191,5 @@ fn default_index_path(db_path: &Path) -> PathBuf {
     let digest = hasher.finalize();
     let hex = hex::encode(digest);
 
-    home.join("state").join("index").join(format!("{hex}.db"))
+    home.join("state").join(kind).join(hex)
 }
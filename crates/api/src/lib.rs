@@ -10,7 +10,9 @@ pub fn build_router(state: Arc<AppState>, cors: Option<CorsLayer>) -> Router {
     let app = Router::new()
         .route("/api/health", axum::routing::get(health))
         .route("/api/stats", axum::routing::get(stats))
+        .route("/api/meta", axum::routing::get(node_meta))
         .route("/api/blocks", axum::routing::get(blocks))
+        .route("/api/blocks/pending", axum::routing::get(pending_block))
         .route(
             "/api/blocks/{block_number}",
             axum::routing::get(block_detail),
@@ -19,6 +21,10 @@ pub fn build_router(state: Arc<AppState>, cors: Option<CorsLayer>) -> Router {
             "/api/blocks/{block_number}/transactions",
             axum::routing::get(block_transactions),
         )
+        .route(
+            "/api/blocks/{block_number}/verify",
+            axum::routing::get(block_verify),
+        )
         .route(
             "/api/blocks/{block_number}/transactions/{tx_index}",
             axum::routing::get(transaction_detail_by_index),
@@ -32,20 +38,72 @@ pub fn build_router(state: Arc<AppState>, cors: Option<CorsLayer>) -> Router {
             "/api/contracts/{address}",
             axum::routing::get(contract_detail),
         )
+        .route(
+            "/api/contracts/{address}/class-history",
+            axum::routing::get(contract_class_history),
+        )
         .route(
             "/api/contracts/{address}/storage",
             axum::routing::get(contract_storage),
         )
+        .route(
+            "/api/contracts/{address}/storage/{key}/history",
+            axum::routing::get(contract_storage_history),
+        )
         .route("/api/classes", axum::routing::get(classes))
         .route(
             "/api/classes/{class_hash}",
             axum::routing::get(class_detail),
         )
+        .route(
+            "/api/classes/{class_hash}/program",
+            axum::routing::get(class_program),
+        )
+        .route(
+            "/api/classes/{class_hash}/compiled",
+            axum::routing::get(class_compiled),
+        )
         .route(
             "/api/blocks/{block_number}/state-diff",
             axum::routing::get(block_state_diff),
         )
+        .route(
+            "/api/blocks/{block_number}/trie-changes",
+            axum::routing::get(block_trie_changes),
+        )
+        .route(
+            "/api/tries/{trie}/path/{key}",
+            axum::routing::get(trie_path),
+        )
+        .route("/api/proofs/storage", axum::routing::post(storage_proof))
+        .route(
+            "/api/proofs/verify",
+            axum::routing::post(verify_storage_proof),
+        )
+        .route(
+            "/api/audit/state-root",
+            axum::routing::post(start_state_root_audit),
+        )
+        .route(
+            "/api/audit/state-root/{id}",
+            axum::routing::get(state_root_audit_status),
+        )
+        .route("/api/events/search", axum::routing::post(search_events))
         .route("/api/search", axum::routing::get(search))
+        .route("/api/mempool", axum::routing::get(mempool))
+        .route(
+            "/api/messages/l1-to-l2",
+            axum::routing::get(l1_to_l2_messages),
+        )
+        .route(
+            "/api/messages/l1-to-l2/{nonce}",
+            axum::routing::get(l1_to_l2_message),
+        )
+        .route(
+            "/api/messages/l2-to-l1",
+            axum::routing::get(l2_to_l1_messages),
+        )
+        .route("/api/bouncer-weights", axum::routing::get(bouncer_weights))
         // Index endpoints
         .route("/api/index/status", axum::routing::get(index_status))
         .route("/api/index/sync", axum::routing::post(index_sync))
@@ -100,7 +158,8 @@ pub fn build_router(state: Arc<AppState>, cors: Option<CorsLayer>) -> Router {
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use db_reader::DbReader;
@@ -110,26 +169,113 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 use visualizer_types::{
     BatchKeyValueResponse, BatchKeysRequest, BlockDetail, BlockListResponse, BlockSummary,
-    ClassListResponse, ClassResponse, ColumnFamilyInfo, ColumnFamilyListResponse,
-    ColumnFamilySchemaInfo, ColumnFamilyStats, ColumnInfo, ContractListResponse, ContractResponse,
-    ContractStorageDiffInfo, ContractStorageResponse, DeclaredClassInfo, DeployedContractInfo,
-    EventInfo, FilteredContractsResponse, FilteredTransactionsResponse, HealthResponse,
-    IndexStatusResponse, IndexedTransactionInfo, KeyInfo, KeyListResponse, MadaraDbVersionInfo,
-    MessageInfo, NonceUpdateResponse, QueryRequest, QueryResult, RawKeyValue, RawKeyValueResponse,
-    ReplacedClassInfo, SchemaCategoriesResponse, SchemaCategoryInfo, SchemaColumnFamiliesResponse,
-    SchemaFieldInfo, SchemaKeyInfo, SchemaRelationshipInfo, SchemaValueInfo, SearchResponse,
-    StateDiffResponse, StatsResponse, StorageDiffEntryInfo, StorageEntryResponse, TableInfo,
-    TableListResponse, TableSchemaResponse, TransactionDetail, TransactionListResponse,
-    TransactionSummary,
+    BlockVerificationResponse, BouncerCapacityInfo, BouncerWeightsInfo, BouncerWeightsResponse,
+    CasmEntryPointInfo, CasmEntryPointsByTypeInfo, CasmHintInfo, ChainTipInfo, ClassDefinitionInfo,
+    ClassHistoryEntry, ClassListResponse, ClassResponse, ColumnFamilyInfo,
+    ColumnFamilyListResponse, ColumnFamilySchemaInfo, ColumnFamilyStats, ColumnInfo,
+    CompiledClassHashCheckInfo, CompiledClassResponse, ContractClassHistoryResponse,
+    ContractLeafData, ContractListResponse, ContractResponse, ContractStorageDiffInfo,
+    ContractStorageResponse, ContractsProof, DeclaredClassInfo, DecodeErrorResponse,
+    DecodedArgInfo, DecodedCallInfo, DecodedEventInfo, DecodedTransactionInfo,
+    DeployedContractInfo, DevnetAccountInfo, EntryPointInfo, EntryPointsByTypeInfo, EventInfo,
+    EventMatchInfo, EventSearchRequest, EventSearchResponse, EventSearchStats, ExecutionResources,
+    FilteredContractsResponse, FilteredTransactionsResponse, GasPrices, GasVector, GlobalRoots,
+    HashCheckInfo, HealthResponse, IndexStatusResponse, IndexedTransactionInfo, KeyInfo,
+    KeyListResponse, L1ToL2MessageInfo, L1ToL2MessageListResponse, L2ToL1MessageInfo,
+    L2ToL1MessageListResponse, LegacyProgramInfo, MadaraDbVersionInfo, MempoolAccountInfo,
+    MempoolResponse, MempoolTransactionInfo, MerkleNode, MessageInfo, MetaDecodeErrorInfo,
+    NodeHashToNode, NodeMetaResponse, NonceUpdateResponse, PendingBlockResponse,
+    PendingTransactionInfo, PreconfirmedHeaderInfo, ProofCheckResponse, ProofVerificationResponse,
+    QueryRequest, QueryResult, RawKeyValue, RawKeyValueResponse, ReplacedClassInfo, ResourceBounds,
+    ResourceBoundsMapping, RuntimeExecConfigInfo, SchemaCategoriesResponse, SchemaCategoryInfo,
+    SchemaColumnFamiliesResponse, SchemaFieldInfo, SchemaKeyInfo, SchemaRelationshipInfo,
+    SchemaValueInfo, SearchResponse, StateDiffResponse, StateRootAuditRequest,
+    StateRootAuditStatus, StateRootMismatchInfo, StatsResponse, StorageDiffEntryInfo,
+    StorageEntryResponse, StorageHistoryEntry, StorageHistoryResponse, StorageProofRequest,
+    StorageProofResponse, TableInfo, TableListResponse, TableSchemaResponse, TransactionBody,
+    TransactionDetail, TransactionListResponse, TransactionSummary, TrieChangesResponse,
+    TrieLeafChangeInfo, TrieLogMismatchInfo, TrieNodeInfo, TriePathResponse, V3TransactionFields,
+    VerifyStorageProofRequest,
 };
 
 pub struct AppState {
     pub db: DbReader,
     pub indexer: Mutex<Indexer>,
+    /// State-root audit jobs, indexed by job id
+    pub audits: Mutex<Vec<StateRootAuditStatus>>,
 }
 
-// Keep in sync with upstream Madara `.db-versions.yml` as we validate more versions.
-pub const SUPPORTED_MADARA_DB_VERSIONS: &[u32] = &[8, 9];
+/// Keep a secondary `DbReader` in step with the running node.
+///
+/// Every `interval` the reader catches up with the primary; when `sync_index` is set, blocks
+/// that appeared since the last tick are indexed too. Runs on its own thread because both
+/// RocksDB catch-up and SQLite indexing block.
+pub fn spawn_follow(state: Arc<AppState>, interval: std::time::Duration, sync_index: bool) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(interval);
+
+        if let Err(e) = state.db.catch_up_with_primary() {
+            eprintln!("Warning: catching up with primary failed: {e}");
+            continue;
+        }
+
+        if sync_index {
+            let mut idx = state.indexer.lock().unwrap();
+            match idx.sync_from_db(&state.db) {
+                Ok(0) => {}
+                Ok(count) => eprintln!("Follow: {count} new blocks indexed"),
+                Err(e) => eprintln!("Warning: follow index sync failed: {e}"),
+            }
+        }
+    });
+}
+
+/// Handler error: a plain status with a message, or a value that exists but failed to decode
+/// (reported as 422 with a structured body)
+enum ApiError {
+    Status(StatusCode, String),
+    Decode(db_reader::DecodeError),
+}
+
+impl From<(StatusCode, String)> for ApiError {
+    fn from((status, message): (StatusCode, String)) -> Self {
+        ApiError::Status(status, message)
+    }
+}
+
+impl From<db_reader::DbError> for ApiError {
+    fn from(e: db_reader::DbError) -> Self {
+        match e {
+            db_reader::DbError::Decode(e) => ApiError::Decode(e),
+            db_reader::DbError::InvalidInput(message) => {
+                ApiError::Status(StatusCode::BAD_REQUEST, message)
+            }
+            other => ApiError::Status(StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        match self {
+            ApiError::Status(status, message) => (status, message).into_response(),
+            ApiError::Decode(e) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(DecodeErrorResponse {
+                    error: "decode_error".to_string(),
+                    message: e.to_string(),
+                    cf: e.cf,
+                    key: e.key,
+                    offset: e.offset,
+                    expected_type: e.expected_type,
+                }),
+            )
+                .into_response(),
+        }
+    }
+}
+
+pub use db_reader::SUPPORTED_MADARA_DB_VERSIONS;
 
 async fn health() -> Json<HealthResponse> {
     Json(HealthResponse {
@@ -159,6 +305,65 @@ async fn stats(State(state): State<Arc<AppState>>) -> Json<StatsResponse> {
     })
 }
 
+async fn node_meta(State(state): State<Arc<AppState>>) -> Result<Json<NodeMetaResponse>, ApiError> {
+    let meta = state.db.get_node_meta()?;
+    let l1_confirmation_lag = meta.l1_confirmation_lag();
+    let trie_lag = meta.trie_lag();
+    let chain_tip = meta.chain_tip.map(|tip| match tip {
+        db_reader::ChainTip::Confirmed(block_number) => ChainTipInfo {
+            status: "CONFIRMED".to_string(),
+            block_number,
+            preconfirmed: None,
+        },
+        db_reader::ChainTip::Preconfirmed(header) => ChainTipInfo {
+            status: "PRECONFIRMED".to_string(),
+            block_number: header.block_number,
+            preconfirmed: Some(preconfirmed_header_response(header)),
+        },
+    });
+    let (chain_id, chain_name) = match meta.chain_info {
+        Some(info) => (Some(info.chain_id), Some(info.chain_name)),
+        None => (None, None),
+    };
+
+    Ok(Json(NodeMetaResponse {
+        chain_id,
+        chain_name,
+        genesis_block_hash: meta.genesis_block_hash,
+        chain_tip,
+        latest_block: meta.latest_block,
+        confirmed_on_l1_tip: meta.confirmed_on_l1_tip,
+        l1_confirmation_lag,
+        last_synced_l1_event_block: meta.last_synced_l1_event_block,
+        latest_applied_trie_update: meta.latest_applied_trie_update,
+        trie_lag,
+        snap_sync_latest_block: meta.snap_sync_latest_block,
+        devnet_accounts: meta
+            .devnet_accounts
+            .into_iter()
+            .map(|a| DevnetAccountInfo {
+                address: a.address,
+                public_key: a.public_key,
+                class_hash: a.class_hash,
+            })
+            .collect(),
+        runtime_exec_config: meta.runtime_exec_config.map(|c| RuntimeExecConfigInfo {
+            protocol_version: c.protocol_version,
+            no_charge_fee: c.no_charge_fee,
+            chain_config: c.chain_config,
+        }),
+        unknown_keys: meta.unknown_keys,
+        decode_errors: meta
+            .failures
+            .into_iter()
+            .map(|f| MetaDecodeErrorInfo {
+                key: f.key,
+                message: f.error.to_string(),
+            })
+            .collect(),
+    }))
+}
+
 #[derive(Deserialize)]
 struct BlocksQuery {
     #[serde(default = "default_limit")]
@@ -205,8 +410,8 @@ async fn blocks(
 async fn block_detail(
     State(state): State<Arc<AppState>>,
     Path(block_number): Path<u64>,
-) -> Result<Json<BlockDetail>, (StatusCode, String)> {
-    let block = state.db.get_block_detail(block_number).ok_or((
+) -> Result<Json<BlockDetail>, ApiError> {
+    let block = state.db.get_block_detail(block_number)?.ok_or((
         StatusCode::NOT_FOUND,
         format!("Block {} not found", block_number),
     ))?;
@@ -222,6 +427,41 @@ async fn block_detail(
         event_count: block.event_count,
         l2_gas_used: block.l2_gas_used,
         tx_hashes: block.tx_hashes,
+        transaction_commitment: block.transaction_commitment,
+        event_commitment: block.event_commitment,
+        state_diff_length: block.state_diff_length,
+        state_diff_commitment: block.state_diff_commitment,
+        receipt_commitment: block.receipt_commitment,
+        protocol_version: block.protocol_version,
+        gas_prices: gas_prices_response(block.gas_prices),
+        l1_da_mode: block.l1_da_mode,
+    }))
+}
+
+async fn block_verify(
+    State(state): State<Arc<AppState>>,
+    Path(block_number): Path<u64>,
+) -> Result<Json<BlockVerificationResponse>, ApiError> {
+    let verification = state.db.verify_block(block_number)?.ok_or((
+        StatusCode::NOT_FOUND,
+        format!("Block {} not found", block_number),
+    ))?;
+
+    Ok(Json(BlockVerificationResponse {
+        block_number: verification.block_number,
+        protocol_version: verification.protocol_version.clone(),
+        hash_rules: verification.rules.to_string(),
+        valid: verification.is_valid(),
+        checks: verification
+            .checks()
+            .into_iter()
+            .map(|(name, check)| HashCheckInfo {
+                name: name.to_string(),
+                expected: check.expected.clone(),
+                computed: check.computed.clone(),
+                matches: check.matches(),
+            })
+            .collect(),
     }))
 }
 
@@ -236,10 +476,11 @@ async fn block_transactions(
         .into_iter()
         .map(|tx| {
             let (status, revert_reason) = match tx.status {
-                db_reader::ExecutionStatus::Succeeded => ("SUCCEEDED".to_string(), None),
-                db_reader::ExecutionStatus::Reverted(reason) => {
+                Some(db_reader::ExecutionStatus::Succeeded) => ("SUCCEEDED".to_string(), None),
+                Some(db_reader::ExecutionStatus::Reverted(reason)) => {
                     ("REVERTED".to_string(), Some(reason))
                 }
+                None => ("UNKNOWN".to_string(), None),
             };
             TransactionSummary {
                 tx_hash: tx.tx_hash,
@@ -259,28 +500,48 @@ async fn block_transactions(
     }))
 }
 
-/// Get transaction detail by block number and tx index
-async fn transaction_detail_by_index(
+async fn pending_block(
     State(state): State<Arc<AppState>>,
-    Path((block_number, tx_index)): Path<(u64, u64)>,
-) -> Result<Json<TransactionDetail>, (StatusCode, String)> {
-    let tx = state
-        .db
-        .get_transaction_detail(block_number, tx_index)
-        .ok_or((
-            StatusCode::NOT_FOUND,
-            format!(
-                "Transaction at block {} index {} not found",
-                block_number, tx_index
-            ),
-        ))?;
+) -> Result<Json<PendingBlockResponse>, ApiError> {
+    let block = state.db.get_preconfirmed_block()?.ok_or((
+        StatusCode::NOT_FOUND,
+        "No preconfirmed block: the chain tip is a closed block".to_string(),
+    ))?;
+    let header = block.header;
+    let mut abis = db_reader::AbiCache::default();
 
+    Ok(Json(PendingBlockResponse {
+        transactions: block
+            .transactions
+            .into_iter()
+            .map(|tx| {
+                let decoded = state
+                    .db
+                    .decode_transaction_abi_with_cache(&tx.detail, &mut abis);
+                PendingTransactionInfo {
+                    transaction: transaction_detail_response(tx.detail, decoded),
+                    state_diff: state_diff_response(header.block_number, tx.state_diff),
+                    declared_class_hash: tx.declared_class_hash,
+                    arrived_at: tx.arrived_at.try_into().unwrap_or(u64::MAX),
+                    paid_fee_on_l1: tx.paid_fee_on_l1,
+                }
+            })
+            .collect(),
+        header: preconfirmed_header_response(header),
+        undecodable_transactions: block.undecodable_transactions,
+    }))
+}
+
+fn transaction_detail_response(
+    tx: db_reader::TransactionDetail,
+    decoded: db_reader::DecodedTransaction,
+) -> TransactionDetail {
     let (status, revert_reason) = match tx.status {
         db_reader::ExecutionStatus::Succeeded => ("SUCCEEDED".to_string(), None),
         db_reader::ExecutionStatus::Reverted(reason) => ("REVERTED".to_string(), Some(reason)),
     };
 
-    Ok(Json(TransactionDetail {
+    TransactionDetail {
         tx_hash: tx.tx_hash,
         tx_type: tx.tx_type.to_string(),
         status,
@@ -312,62 +573,213 @@ async fn transaction_detail_by_index(
         signature: tx.signature,
         nonce: tx.nonce,
         version: tx.version,
-    }))
+        body: tx.body.map(transaction_body_response),
+        execution_resources: tx.execution_resources.map(execution_resources_response),
+        decoded: Some(decoded_transaction_response(decoded)),
+    }
+}
+
+fn decoded_transaction_response(decoded: db_reader::DecodedTransaction) -> DecodedTransactionInfo {
+    let args = |args: Vec<db_reader::DecodedArg>| {
+        args.into_iter()
+            .map(|a| DecodedArgInfo {
+                name: a.name,
+                type_name: a.type_name,
+                value: a.value,
+            })
+            .collect()
+    };
+
+    DecodedTransactionInfo {
+        calls: decoded
+            .calls
+            .into_iter()
+            .map(|c| DecodedCallInfo {
+                to: c.to,
+                selector: c.selector,
+                function: c.function,
+                args: c.args.map(args),
+                calldata: c.calldata,
+            })
+            .collect(),
+        events: decoded
+            .events
+            .into_iter()
+            .map(|e| DecodedEventInfo {
+                index: e.index,
+                from_address: e.from_address,
+                name: e.name,
+                fields: args(e.fields),
+            })
+            .collect(),
+    }
+}
+
+fn execution_resources_response(r: db_reader::ExecutionResources) -> ExecutionResources {
+    let gas = |g: db_reader::GasVector| GasVector {
+        l1_gas: g.l1_gas,
+        l1_data_gas: g.l1_data_gas,
+        l2_gas: g.l2_gas,
+    };
+
+    ExecutionResources {
+        steps: r.steps,
+        memory_holes: r.memory_holes,
+        range_check_builtin_applications: r.range_check_builtin_applications,
+        pedersen_builtin_applications: r.pedersen_builtin_applications,
+        poseidon_builtin_applications: r.poseidon_builtin_applications,
+        ec_op_builtin_applications: r.ec_op_builtin_applications,
+        ecdsa_builtin_applications: r.ecdsa_builtin_applications,
+        bitwise_builtin_applications: r.bitwise_builtin_applications,
+        keccak_builtin_applications: r.keccak_builtin_applications,
+        segment_arena_builtin: r.segment_arena_builtin,
+        data_availability: gas(r.data_availability),
+        total_gas_consumed: gas(r.total_gas_consumed),
+    }
+}
+
+fn transaction_body_response(body: db_reader::TransactionBody) -> TransactionBody {
+    use db_reader::TransactionBody as Body;
+
+    match body {
+        Body::Invoke {
+            max_fee,
+            entry_point_selector,
+            v3,
+            account_deployment_data,
+        } => TransactionBody::Invoke {
+            max_fee,
+            entry_point_selector,
+            v3: v3.map(v3_fields_response),
+            account_deployment_data,
+        },
+        Body::L1Handler {
+            contract_address,
+            entry_point_selector,
+        } => TransactionBody::L1Handler {
+            contract_address,
+            entry_point_selector,
+        },
+        Body::Declare {
+            class_hash,
+            compiled_class_hash,
+            max_fee,
+            v3,
+            account_deployment_data,
+        } => TransactionBody::Declare {
+            class_hash,
+            compiled_class_hash,
+            max_fee,
+            v3: v3.map(v3_fields_response),
+            account_deployment_data,
+        },
+        Body::Deploy {
+            class_hash,
+            contract_address_salt,
+            constructor_calldata,
+        } => TransactionBody::Deploy {
+            class_hash,
+            contract_address_salt,
+            constructor_calldata,
+        },
+        Body::DeployAccount {
+            class_hash,
+            contract_address_salt,
+            constructor_calldata,
+            max_fee,
+            v3,
+        } => TransactionBody::DeployAccount {
+            class_hash,
+            contract_address_salt,
+            constructor_calldata,
+            max_fee,
+            v3: v3.map(v3_fields_response),
+        },
+    }
+}
+
+fn v3_fields_response(v3: db_reader::V3Fields) -> V3TransactionFields {
+    V3TransactionFields {
+        resource_bounds: resource_bounds_response(v3.resource_bounds),
+        tip: v3.tip,
+        paymaster_data: v3.paymaster_data,
+        nonce_data_availability_mode: v3.nonce_data_availability_mode,
+        fee_data_availability_mode: v3.fee_data_availability_mode,
+    }
+}
+
+fn preconfirmed_header_response(header: db_reader::PreconfirmedHeader) -> PreconfirmedHeaderInfo {
+    PreconfirmedHeaderInfo {
+        block_number: header.block_number,
+        sequencer_address: header.sequencer_address,
+        timestamp: header.timestamp,
+        protocol_version: header.protocol_version,
+        gas_prices: gas_prices_response(header.gas_prices),
+        l1_da_mode: header.l1_da_mode,
+    }
+}
+
+fn gas_prices_response(prices: db_reader::GasPrices) -> GasPrices {
+    GasPrices {
+        eth_l1_gas_price: prices.eth_l1_gas_price,
+        strk_l1_gas_price: prices.strk_l1_gas_price,
+        eth_l1_data_gas_price: prices.eth_l1_data_gas_price,
+        strk_l1_data_gas_price: prices.strk_l1_data_gas_price,
+        eth_l2_gas_price: prices.eth_l2_gas_price,
+        strk_l2_gas_price: prices.strk_l2_gas_price,
+    }
+}
+
+fn resource_bounds_response(bounds: db_reader::ResourceBoundsMapping) -> ResourceBoundsMapping {
+    let convert = |b: db_reader::ResourceBounds| ResourceBounds {
+        max_amount: b.max_amount,
+        max_price_per_unit: b.max_price_per_unit,
+    };
+
+    ResourceBoundsMapping {
+        l1_gas: convert(bounds.l1_gas),
+        l2_gas: convert(bounds.l2_gas),
+        l1_data_gas: bounds.l1_data_gas.map(convert),
+    }
+}
+
+/// Get transaction detail by block number and tx index
+async fn transaction_detail_by_index(
+    State(state): State<Arc<AppState>>,
+    Path((block_number, tx_index)): Path<(u64, u64)>,
+) -> Result<Json<TransactionDetail>, ApiError> {
+    let tx = state
+        .db
+        .get_transaction_detail(block_number, tx_index)?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            format!(
+                "Transaction at block {} index {} not found",
+                block_number, tx_index
+            ),
+        ))?;
+
+    let decoded = state.db.decode_transaction_abi(&tx);
+    Ok(Json(transaction_detail_response(tx, decoded)))
 }
 
 async fn transaction_detail(
     State(state): State<Arc<AppState>>,
     Path(tx_hash): Path<String>,
-) -> Result<Json<TransactionDetail>, (StatusCode, String)> {
+) -> Result<Json<TransactionDetail>, ApiError> {
     // Find the transaction by hash
     let (block_n, tx_index) = state.db.find_transaction_by_hash(&tx_hash).ok_or((
         StatusCode::NOT_FOUND,
         format!("Transaction {} not found (hash lookup failed)", tx_hash),
     ))?;
 
-    let tx = state.db.get_transaction_detail(block_n, tx_index).ok_or((
+    let tx = state.db.get_transaction_detail(block_n, tx_index)?.ok_or((
         StatusCode::NOT_FOUND,
         format!("Transaction {} not found (detail lookup failed)", tx_hash),
     ))?;
 
-    let (status, revert_reason) = match tx.status {
-        db_reader::ExecutionStatus::Succeeded => ("SUCCEEDED".to_string(), None),
-        db_reader::ExecutionStatus::Reverted(reason) => ("REVERTED".to_string(), Some(reason)),
-    };
-
-    Ok(Json(TransactionDetail {
-        tx_hash: tx.tx_hash,
-        tx_type: tx.tx_type.to_string(),
-        status,
-        revert_reason,
-        block_number: tx.block_number,
-        tx_index: tx.tx_index,
-        actual_fee: tx.actual_fee,
-        fee_unit: tx.fee_unit,
-        events: tx
-            .events
-            .into_iter()
-            .map(|e| EventInfo {
-                from_address: e.from_address,
-                keys: e.keys,
-                data: e.data,
-            })
-            .collect(),
-        messages_sent: tx
-            .messages_sent
-            .into_iter()
-            .map(|m| MessageInfo {
-                from_address: m.from_address,
-                to_address: m.to_address,
-                payload: m.payload,
-            })
-            .collect(),
-        sender_address: tx.sender_address,
-        calldata: tx.calldata,
-        signature: tx.signature,
-        nonce: tx.nonce,
-        version: tx.version,
-    }))
+    let decoded = state.db.decode_transaction_abi(&tx);
+    Ok(Json(transaction_detail_response(tx, decoded)))
 }
 
 // Contract endpoints
@@ -402,11 +814,23 @@ async fn contracts(
     Json(ContractListResponse { contracts, total })
 }
 
+#[derive(Deserialize)]
+struct ContractQuery {
+    /// Read the contract as of this block instead of the latest state
+    #[serde(default)]
+    block: Option<u64>,
+}
+
 async fn contract_detail(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
+    Query(query): Query<ContractQuery>,
 ) -> Result<Json<ContractResponse>, (StatusCode, String)> {
-    let contract = state.db.get_contract(&address).ok_or((
+    let contract = match query.block {
+        Some(block) => state.db.get_contract_at(&address, block),
+        None => state.db.get_contract(&address),
+    }
+    .ok_or((
         StatusCode::NOT_FOUND,
         format!("Contract {} not found", address),
     ))?;
@@ -418,18 +842,64 @@ async fn contract_detail(
     }))
 }
 
+async fn contract_class_history(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Json<ContractClassHistoryResponse> {
+    let entries: Vec<ClassHistoryEntry> = state
+        .db
+        .get_contract_class_history(&address)
+        .into_iter()
+        .map(|v| ClassHistoryEntry {
+            block_number: v.block_number,
+            class_hash: v.class_hash,
+        })
+        .collect();
+
+    let total = entries.len();
+
+    Json(ContractClassHistoryResponse {
+        address,
+        entries,
+        total,
+    })
+}
+
+#[derive(Deserialize)]
+struct StorageQuery {
+    #[serde(default = "default_limit_usize")]
+    limit: usize,
+    /// Read storage as of this block instead of the latest state
+    #[serde(default)]
+    block: Option<u64>,
+    /// Restrict the result to a single storage key
+    #[serde(default)]
+    key: Option<String>,
+}
+
 async fn contract_storage(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
-    Query(query): Query<LimitQuery>,
+    Query(query): Query<StorageQuery>,
 ) -> Json<ContractStorageResponse> {
-    let entries: Vec<StorageEntryResponse> = state
-        .db
-        .get_contract_storage(&address, query.limit)
+    let entries = match (&query.key, query.block) {
+        (Some(key), block) => state
+            .db
+            .get_storage_at(&address, key, block.unwrap_or(u64::from(u32::MAX)))
+            .into_iter()
+            .collect(),
+        (None, Some(block)) => state
+            .db
+            .get_contract_storage_at(&address, block, query.limit),
+        (None, None) => state.db.get_contract_storage(&address, query.limit),
+    };
+
+    let entries: Vec<StorageEntryResponse> = entries
         .into_iter()
         .map(|e| StorageEntryResponse {
             key: e.key,
             value: e.value,
+            block_number: e.block_number,
         })
         .collect();
 
@@ -437,6 +907,32 @@ async fn contract_storage(
 
     Json(ContractStorageResponse {
         address,
+        block_number: query.block,
+        entries,
+        total,
+    })
+}
+
+async fn contract_storage_history(
+    State(state): State<Arc<AppState>>,
+    Path((address, key)): Path<(String, String)>,
+    Query(query): Query<LimitQuery>,
+) -> Json<StorageHistoryResponse> {
+    let entries: Vec<StorageHistoryEntry> = state
+        .db
+        .get_storage_history(&address, &key, query.limit)
+        .into_iter()
+        .map(|v| StorageHistoryEntry {
+            block_number: v.block_number,
+            value: v.value,
+        })
+        .collect();
+
+    let total = entries.len();
+
+    Json(StorageHistoryResponse {
+        address,
+        key,
         entries,
         total,
     })
@@ -454,6 +950,7 @@ async fn classes(
             class_hash: c.class_hash,
             class_type: c.class_type.to_string(),
             compiled_class_hash: c.compiled_class_hash,
+            definition: None,
         })
         .collect();
 
@@ -465,31 +962,157 @@ async fn classes(
 async fn class_detail(
     State(state): State<Arc<AppState>>,
     Path(class_hash): Path<String>,
-) -> Result<Json<ClassResponse>, (StatusCode, String)> {
-    let class = state.db.get_class(&class_hash).ok_or((
+) -> Result<Json<ClassResponse>, ApiError> {
+    let class = state.db.get_class_definition(&class_hash)?.ok_or((
         StatusCode::NOT_FOUND,
         format!("Class {} not found", class_hash),
     ))?;
 
+    let compiled_class_hash_check =
+        state
+            .db
+            .check_compiled_class_hash(&class)?
+            .map(|check| CompiledClassHashCheckInfo {
+                declared_at: check.declared_at,
+                state_diff_compiled_class_hash: check.state_diff_compiled_class_hash,
+                matches: check.matches,
+            });
+    let entry_points = |eps: Vec<db_reader::EntryPoint>| {
+        eps.into_iter()
+            .map(|ep| EntryPointInfo {
+                selector: ep.selector,
+                function_idx: ep.function_idx,
+                offset: ep.offset,
+            })
+            .collect()
+    };
+    // A Sierra ABI that is not valid JSON is still shown, as a string
+    let abi = class
+        .abi
+        .map(|abi| serde_json::from_str(&abi).unwrap_or(serde_json::Value::String(abi)));
+
     Ok(Json(ClassResponse {
         class_hash: class.class_hash,
         class_type: class.class_type.to_string(),
-        compiled_class_hash: class.compiled_class_hash,
+        compiled_class_hash: class
+            .compiled_class_hash_v2
+            .clone()
+            .or_else(|| class.compiled_class_hash.clone()),
+        definition: Some(ClassDefinitionInfo {
+            declared_at: class.declared_at,
+            contract_class_version: class.contract_class_version,
+            sierra_version: class.sierra_version,
+            compiler_version: class.compiler_version,
+            program_length: class.program_length,
+            entry_points: EntryPointsByTypeInfo {
+                constructor: entry_points(class.entry_points.constructor),
+                external: entry_points(class.entry_points.external),
+                l1_handler: entry_points(class.entry_points.l1_handler),
+            },
+            abi,
+            compiled_class_hash: class.compiled_class_hash,
+            compiled_class_hash_v2: class.compiled_class_hash_v2,
+            legacy_program: class.legacy_program.map(|p| LegacyProgramInfo {
+                builtins: p.builtins,
+                prime: p.prime,
+                compiler_version: p.compiler_version,
+                main_scope: p.main_scope,
+                data_length: p.data_length,
+                hints_count: p.hints_count,
+                identifiers_count: p.identifiers_count,
+                decompressed_size: p.decompressed_size,
+            }),
+            legacy_program_error: class.legacy_program_error,
+            compiled_class_hash_check,
+        }),
+    }))
+}
+
+async fn class_compiled(
+    State(state): State<Arc<AppState>>,
+    Path(class_hash): Path<String>,
+) -> Result<Json<CompiledClassResponse>, ApiError> {
+    let class = state.db.get_class_definition(&class_hash)?.ok_or((
+        StatusCode::NOT_FOUND,
+        format!("Class {} not found", class_hash),
+    ))?;
+    let compiled = state.db.get_class_compiled(&class)?.ok_or((
+        StatusCode::NOT_FOUND,
+        format!("No compiled class stored for class {}", class_hash),
+    ))?;
+
+    let entry_points = |eps: Vec<db_reader::CasmEntryPoint>| {
+        eps.into_iter()
+            .map(|ep| CasmEntryPointInfo {
+                selector: ep.selector,
+                offset: ep.offset,
+                builtins: ep.builtins,
+            })
+            .collect()
+    };
+    let hints_count = compiled.hints_count();
+
+    Ok(Json(CompiledClassResponse {
+        class_hash: class.class_hash,
+        compiled_class_hash: compiled.compiled_class_hash,
+        block_number: compiled.block_number,
+        compiler_version: compiled.compiler_version,
+        prime: compiled.prime,
+        bytecode_length: compiled.bytecode_length,
+        entry_points: CasmEntryPointsByTypeInfo {
+            constructor: entry_points(compiled.entry_points.constructor),
+            external: entry_points(compiled.entry_points.external),
+            l1_handler: entry_points(compiled.entry_points.l1_handler),
+        },
+        hints_count,
+        hints: compiled
+            .hints
+            .into_iter()
+            .map(|h| CasmHintInfo {
+                pc: h.pc,
+                hints: h.hints,
+            })
+            .collect(),
     }))
 }
 
+async fn class_program(
+    State(state): State<Arc<AppState>>,
+    Path(class_hash): Path<String>,
+) -> Result<Response, ApiError> {
+    let (class_hash, program) = state.db.get_class_program(&class_hash)?.ok_or((
+        StatusCode::NOT_FOUND,
+        format!("Class {} not found", class_hash),
+    ))?;
+
+    // Only the hex hash from the database key goes into the header
+    let filename = format!("attachment; filename=\"{class_hash}-program.json\"");
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::CONTENT_DISPOSITION, filename),
+        ],
+        program,
+    )
+        .into_response())
+}
+
 // State diff endpoint
 
 async fn block_state_diff(
     State(state): State<Arc<AppState>>,
     Path(block_number): Path<u64>,
-) -> Result<Json<StateDiffResponse>, (StatusCode, String)> {
-    let diff = state.db.get_state_diff(block_number).ok_or((
+) -> Result<Json<StateDiffResponse>, ApiError> {
+    let diff = state.db.get_state_diff(block_number)?.ok_or((
         StatusCode::NOT_FOUND,
         format!("State diff for block {} not found", block_number),
     ))?;
 
-    Ok(Json(StateDiffResponse {
+    Ok(Json(state_diff_response(block_number, diff)))
+}
+
+fn state_diff_response(block_number: u64, diff: db_reader::StateDiffInfo) -> StateDiffResponse {
+    StateDiffResponse {
         block_number,
         deployed_contracts: diff
             .deployed_contracts
@@ -538,16 +1161,701 @@ async fn block_state_diff(
                 class_hash: r.class_hash,
             })
             .collect(),
+    }
+}
+
+// Mempool endpoint
+
+#[derive(Deserialize)]
+struct MempoolQuery {
+    /// `sender` (default), `nonce` or `arrival`
+    #[serde(default)]
+    sort: Option<String>,
+    #[serde(default = "default_mempool_limit")]
+    limit: usize,
+}
+
+fn default_mempool_limit() -> usize {
+    200
+}
+
+async fn mempool(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<MempoolQuery>,
+) -> Result<Json<MempoolResponse>, ApiError> {
+    let sort = query
+        .sort
+        .as_deref()
+        .map(db_reader::MempoolSort::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let snapshot = state.db.get_mempool(sort);
+    let total = snapshot.transactions.len();
+
+    Ok(Json(MempoolResponse {
+        transactions: snapshot
+            .transactions
+            .into_iter()
+            .take(query.limit)
+            .map(|tx| MempoolTransactionInfo {
+                tx_hash: tx.tx_hash,
+                tx_type: tx.tx_type.to_string(),
+                contract_address: tx.contract_address,
+                nonce: tx.nonce,
+                version: tx.version,
+                arrived_at: tx.arrived_at.try_into().unwrap_or(u64::MAX),
+                max_fee: tx.max_fee,
+                tip: tx.tip,
+                resource_bounds: tx.resource_bounds.map(resource_bounds_response),
+                paid_fee_on_l1: tx.paid_fee_on_l1,
+                charge_fee: tx.charge_fee,
+                declared_class_hash: tx.declared_class_hash,
+                blocked_by_nonce_gap: tx.blocked_by_nonce_gap,
+            })
+            .collect(),
+        accounts: snapshot
+            .accounts
+            .into_iter()
+            .map(|a| MempoolAccountInfo {
+                address: a.address,
+                current_nonce: a.current_nonce,
+                nonces: a.nonces,
+                missing_nonces: a.missing_nonces,
+                missing_count: a.missing_count,
+            })
+            .collect(),
+        total,
+        sort: query.sort.unwrap_or_else(|| "sender".to_string()),
+    }))
+}
+
+// Messaging endpoints
+
+#[derive(Deserialize)]
+struct L1ToL2MessagesQuery {
+    #[serde(default)]
+    from_nonce: u64,
+    #[serde(default = "default_message_limit")]
+    limit: usize,
+}
+
+#[derive(Deserialize)]
+struct L2ToL1MessagesQuery {
+    #[serde(default)]
+    from_block: Option<u64>,
+    /// Defaults to the latest block
+    #[serde(default)]
+    to_block: Option<u64>,
+    #[serde(default = "default_message_limit")]
+    limit: usize,
+}
+
+fn default_message_limit() -> usize {
+    100
+}
+
+fn l1_to_l2_message_response(m: db_reader::L1ToL2Message) -> L1ToL2MessageInfo {
+    L1ToL2MessageInfo {
+        nonce: m.nonce,
+        status: m.status.to_string(),
+        message_hash: m.message_hash,
+        from_address: m.from_address,
+        to_address: m.to_address,
+        entry_point_selector: m.entry_point_selector,
+        payload: m.payload,
+        paid_fee_on_l1: m.paid_fee_on_l1,
+        tx_hash: m.tx_hash,
+        block_number: m.block_number,
+        tx_index: m.tx_index,
+    }
+}
+
+async fn l1_to_l2_messages(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<L1ToL2MessagesQuery>,
+) -> Result<Json<L1ToL2MessageListResponse>, ApiError> {
+    let list = state
+        .db
+        .list_l1_to_l2_messages(query.from_nonce, query.limit)?;
+    let pending = list
+        .messages
+        .iter()
+        .filter(|m| m.status == db_reader::L1ToL2MessageStatus::Pending)
+        .count();
+
+    Ok(Json(L1ToL2MessageListResponse {
+        pending,
+        consumed: list.messages.len() - pending,
+        messages: list
+            .messages
+            .into_iter()
+            .map(l1_to_l2_message_response)
+            .collect(),
+        undecodable_messages: list.undecodable_messages,
+    }))
+}
+
+async fn l1_to_l2_message(
+    State(state): State<Arc<AppState>>,
+    Path(nonce): Path<u64>,
+) -> Result<Json<L1ToL2MessageInfo>, ApiError> {
+    let message = state.db.get_l1_to_l2_message(nonce)?.ok_or((
+        StatusCode::NOT_FOUND,
+        format!("No L1 to L2 message with nonce {nonce}"),
+    ))?;
+    Ok(Json(l1_to_l2_message_response(message)))
+}
+
+async fn l2_to_l1_messages(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<L2ToL1MessagesQuery>,
+) -> Result<Json<L2ToL1MessageListResponse>, ApiError> {
+    let from_block = query.from_block.unwrap_or(0);
+    let to_block = query
+        .to_block
+        .or_else(|| state.db.get_latest_block_number())
+        .unwrap_or(0);
+    let list = state
+        .db
+        .list_l2_to_l1_messages(from_block, to_block, query.limit)?;
+
+    Ok(Json(L2ToL1MessageListResponse {
+        messages: list
+            .messages
+            .into_iter()
+            .map(|m| L2ToL1MessageInfo {
+                block_number: m.block_number,
+                tx_index: m.tx_index,
+                message_index: m.message_index,
+                tx_hash: m.tx_hash,
+                from_address: m.from_address,
+                to_address: m.to_address,
+                payload: m.payload,
+                message_hash: m.message_hash,
+            })
+            .collect(),
+        from_block,
+        to_block: list.to_block,
+        last_block_scanned: list.last_block_scanned,
+        undecodable_transactions: list.undecodable_transactions,
+    }))
+}
+
+// Block utilization endpoint
+
+#[derive(Deserialize)]
+struct BouncerWeightsQuery {
+    /// Defaults to 100 blocks before `to`
+    #[serde(default)]
+    from: Option<u64>,
+    /// Defaults to the latest block
+    #[serde(default)]
+    to: Option<u64>,
+}
+
+fn bouncer_weights_response(w: db_reader::BouncerWeights) -> BouncerWeightsInfo {
+    let builtins = w
+        .builtins
+        .map(|b| {
+            vec![
+                ("add_mod".to_string(), b.add_mod),
+                ("bitwise".to_string(), b.bitwise),
+                ("ecdsa".to_string(), b.ecdsa),
+                ("ec_op".to_string(), b.ec_op),
+                ("keccak".to_string(), b.keccak),
+                ("mul_mod".to_string(), b.mul_mod),
+                ("pedersen".to_string(), b.pedersen),
+                ("poseidon".to_string(), b.poseidon),
+                ("range_check".to_string(), b.range_check),
+                ("range_check96".to_string(), b.range_check96),
+            ]
+        })
+        .unwrap_or_default();
+
+    BouncerWeightsInfo {
+        block_number: w.block_number,
+        l1_gas: w.l1_gas,
+        message_segment_length: w.message_segment_length,
+        n_events: w.n_events,
+        state_diff_size: w.state_diff_size,
+        sierra_gas: w.sierra_gas,
+        n_txs: w.n_txs,
+        proving_gas: w.proving_gas,
+        n_steps: w.n_steps,
+        builtin_count: w.builtins.map(|b| b.total()),
+        builtins,
+    }
+}
+
+async fn bouncer_weights(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BouncerWeightsQuery>,
+) -> Result<Json<BouncerWeightsResponse>, ApiError> {
+    let to_block = query
+        .to
+        .or_else(|| state.db.get_latest_block_number())
+        .unwrap_or(0);
+    let from_block = query.from.unwrap_or(to_block.saturating_sub(99));
+    let range = state.db.list_bouncer_weights(from_block, to_block)?;
+    // The reader stops after MAX_BOUNCER_WEIGHTS_RANGE blocks
+    let to_block =
+        to_block.min(from_block.saturating_add(db_reader::MAX_BOUNCER_WEIGHTS_RANGE - 1));
+
+    Ok(Json(BouncerWeightsResponse {
+        blocks: range
+            .blocks
+            .into_iter()
+            .map(bouncer_weights_response)
+            .collect(),
+        from_block,
+        to_block,
+        undecodable_blocks: range.undecodable_blocks,
+        block_max_capacity: range.block_max_capacity.map(|c| BouncerCapacityInfo {
+            l1_gas: c.l1_gas,
+            message_segment_length: c.message_segment_length,
+            n_events: c.n_events,
+            state_diff_size: c.state_diff_size,
+            sierra_gas: c.sierra_gas,
+            n_txs: c.n_txs,
+            proving_gas: c.proving_gas,
+        }),
+        block_max_capacity_error: range.block_max_capacity_error,
     }))
 }
 
 // Search endpoint
 
+#[derive(Deserialize)]
+struct TrieQuery {
+    /// Contract whose storage trie to walk (storage tries only)
+    #[serde(default)]
+    contract: Option<String>,
+}
+
+async fn block_trie_changes(
+    State(state): State<Arc<AppState>>,
+    Path(block_number): Path<u64>,
+) -> Result<Json<TrieChangesResponse>, ApiError> {
+    state.db.get_block_summary(block_number)?.ok_or((
+        StatusCode::NOT_FOUND,
+        format!("Block {} not found", block_number),
+    ))?;
+    let changes = state.db.get_trie_changes(block_number)?;
+
+    Ok(Json(TrieChangesResponse {
+        block_number,
+        has_log: changes.has_log,
+        nodes_changed: changes.nodes_changed,
+        leaves: changes
+            .leaves
+            .into_iter()
+            .map(|leaf| TrieLeafChangeInfo {
+                trie: leaf.trie.name().to_string(),
+                contract_address: leaf.contract_address,
+                key: leaf.key,
+                old_value: leaf.old_value,
+                new_value: leaf.new_value,
+            })
+            .collect(),
+        mismatches: changes
+            .mismatches
+            .into_iter()
+            .map(|m| TrieLogMismatchInfo {
+                trie: m.trie.name().to_string(),
+                contract_address: m.contract_address,
+                key: m.key,
+                reason: m.reason,
+            })
+            .collect(),
+    }))
+}
+
+async fn trie_path(
+    State(state): State<Arc<AppState>>,
+    Path((trie_name, key)): Path<(String, String)>,
+    Query(query): Query<TrieQuery>,
+) -> Result<Json<TriePathResponse>, ApiError> {
+    let trie = db_reader::Trie::from_name(&trie_name).ok_or((
+        StatusCode::BAD_REQUEST,
+        format!("Unknown trie {trie_name}, expected contract, storage or class"),
+    ))?;
+
+    let path = state
+        .db
+        .get_trie_path(trie, query.contract.as_deref(), &key)?;
+
+    let nodes = path
+        .nodes
+        .iter()
+        .map(|visited| {
+            let path = db_reader::bits_to_string(&visited.path);
+            match &visited.node {
+                db_reader::TrieNode::Binary(node) => TrieNodeInfo {
+                    path,
+                    kind: "binary".to_string(),
+                    hash: node.hash.clone(),
+                    height: node.height,
+                    left: Some(node.left.to_string()),
+                    right: Some(node.right.to_string()),
+                    taken: visited
+                        .took_right
+                        .map(|right| if right { "right" } else { "left" }.to_string()),
+                    edge_path: None,
+                    child: None,
+                },
+                db_reader::TrieNode::Edge(node) => TrieNodeInfo {
+                    path,
+                    kind: "edge".to_string(),
+                    hash: node.hash.clone(),
+                    height: node.height,
+                    left: None,
+                    right: None,
+                    taken: None,
+                    edge_path: Some(db_reader::bits_to_string(&node.path)),
+                    child: Some(node.child.to_string()),
+                },
+            }
+        })
+        .collect();
+
+    let (end, leaf, diverged_at, missing_path) = match &path.end {
+        db_reader::TriePathEnd::Leaf(handle) => ("leaf", Some(handle.to_string()), None, None),
+        db_reader::TriePathEnd::Diverged { depth } => ("diverged", None, Some(*depth as u64), None),
+        db_reader::TriePathEnd::MissingNode { path } => (
+            "missing_node",
+            None,
+            None,
+            Some(db_reader::bits_to_string(path)),
+        ),
+        db_reader::TriePathEnd::EmptyTrie => ("empty_trie", None, None, None),
+    };
+
+    Ok(Json(TriePathResponse {
+        trie: path.trie.name().to_string(),
+        identifier: path.identifier.clone(),
+        key: path.key.clone(),
+        root_hash: path.root_hash().map(str::to_string),
+        nodes,
+        end: end.to_string(),
+        leaf,
+        diverged_at,
+        missing_path,
+        flat_value: path.flat_value.clone(),
+    }))
+}
+
 #[derive(Deserialize)]
 struct SearchQuery {
     q: String,
 }
 
+fn proof_nodes_response(nodes: Vec<db_reader::ProofNode>) -> Vec<NodeHashToNode> {
+    nodes
+        .into_iter()
+        .map(|n| NodeHashToNode {
+            node_hash: n.node_hash,
+            node: match n.node {
+                db_reader::MerkleNode::Binary { left, right } => MerkleNode::Binary { left, right },
+                db_reader::MerkleNode::Edge {
+                    path,
+                    length,
+                    child,
+                } => MerkleNode::Edge {
+                    path,
+                    length,
+                    child,
+                },
+            },
+        })
+        .collect()
+}
+
+fn proof_nodes_request(nodes: Vec<NodeHashToNode>) -> Vec<db_reader::ProofNode> {
+    nodes
+        .into_iter()
+        .map(|n| db_reader::ProofNode {
+            node_hash: n.node_hash,
+            node: match n.node {
+                MerkleNode::Binary { left, right } => db_reader::MerkleNode::Binary { left, right },
+                MerkleNode::Edge {
+                    path,
+                    length,
+                    child,
+                } => db_reader::MerkleNode::Edge {
+                    path,
+                    length,
+                    child,
+                },
+            },
+        })
+        .collect()
+}
+
+fn storage_proof_request(request: StorageProofRequest) -> db_reader::StorageProofRequest {
+    db_reader::StorageProofRequest {
+        class_hashes: request.class_hashes,
+        contract_addresses: request.contract_addresses,
+        contracts_storage_keys: request
+            .contracts_storage_keys
+            .into_iter()
+            .map(|c| db_reader::ContractStorageKeys {
+                contract_address: c.contract_address,
+                storage_keys: c.storage_keys,
+            })
+            .collect(),
+    }
+}
+
+/// Search events by block range, emitter and keys, pruning blocks with their bloom filters
+async fn search_events(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<EventSearchRequest>,
+) -> Result<Json<EventSearchResponse>, ApiError> {
+    let to_block = request
+        .to_block
+        .or_else(|| state.db.get_latest_block_number())
+        .unwrap_or(0);
+    let filter = db_reader::EventFilter::parse(
+        request.from_block.unwrap_or(0),
+        to_block,
+        request.address.as_deref(),
+        &request.keys,
+        request.limit.unwrap_or(100),
+    )?;
+    let result = state.db.search_events(&filter)?;
+    let stats = result.stats;
+
+    Ok(Json(EventSearchResponse {
+        events: result
+            .events
+            .into_iter()
+            .map(|e| EventMatchInfo {
+                block_number: e.block_number,
+                tx_index: e.tx_index,
+                event_index: e.event_index,
+                tx_hash: e.tx_hash,
+                from_address: e.from_address,
+                keys: e.keys,
+                data: e.data,
+            })
+            .collect(),
+        stats: EventSearchStats {
+            blocks_in_range: stats.blocks_in_range,
+            blocks_with_bloom: stats.blocks_with_bloom,
+            blocks_scanned: stats.blocks_scanned,
+            candidate_blocks: stats.candidate_blocks,
+            false_positive_blocks: stats.false_positive_blocks,
+            false_positive_rate: stats.false_positive_rate,
+            expected_item_false_positive_rate: stats.expected_item_false_positive_rate,
+            bloom_pruning: stats.bloom_pruning,
+            bloom_consistent: stats.bloom_consistent,
+            undecodable_transactions: stats.undecodable_transactions,
+        },
+        to_block: result.to_block,
+        last_block_scanned: result.last_block_scanned,
+    }))
+}
+
+/// Generate a `starknet_getStorageProof`-shaped proof from the bonsai tries
+async fn storage_proof(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<StorageProofRequest>,
+) -> Result<Json<StorageProofResponse>, ApiError> {
+    let proof = state
+        .db
+        .get_storage_proof(&storage_proof_request(request))?;
+
+    Ok(Json(StorageProofResponse {
+        classes_proof: proof_nodes_response(proof.classes_proof),
+        contracts_proof: ContractsProof {
+            nodes: proof_nodes_response(proof.contracts_proof),
+            contract_leaves_data: proof
+                .contract_leaves_data
+                .into_iter()
+                .map(|leaf| ContractLeafData {
+                    nonce: leaf.nonce,
+                    class_hash: leaf.class_hash,
+                    storage_root: leaf.storage_root,
+                })
+                .collect(),
+        },
+        contracts_storage_proofs: proof
+            .contracts_storage_proofs
+            .into_iter()
+            .map(proof_nodes_response)
+            .collect(),
+        global_roots: GlobalRoots {
+            contracts_tree_root: proof.global_roots.contracts_tree_root,
+            classes_tree_root: proof.global_roots.classes_tree_root,
+            block_hash: proof.global_roots.block_hash,
+        },
+    }))
+}
+
+/// Check a storage proof against the state root of a block in this DB
+async fn verify_storage_proof(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<VerifyStorageProofRequest>,
+) -> Result<Json<ProofVerificationResponse>, ApiError> {
+    let block_number = body
+        .block_number
+        .or_else(|| state.db.get_latest_block_number());
+    let proof = body.proof;
+    let proof = db_reader::StorageProof {
+        classes_proof: proof_nodes_request(proof.classes_proof),
+        contracts_proof: proof_nodes_request(proof.contracts_proof.nodes),
+        contract_leaves_data: proof
+            .contracts_proof
+            .contract_leaves_data
+            .into_iter()
+            .map(|leaf| db_reader::ContractLeafData {
+                nonce: leaf.nonce,
+                class_hash: leaf.class_hash,
+                storage_root: leaf.storage_root,
+            })
+            .collect(),
+        contracts_storage_proofs: proof
+            .contracts_storage_proofs
+            .into_iter()
+            .map(proof_nodes_request)
+            .collect(),
+        global_roots: db_reader::GlobalRoots {
+            contracts_tree_root: proof.global_roots.contracts_tree_root,
+            classes_tree_root: proof.global_roots.classes_tree_root,
+            block_hash: proof.global_roots.block_hash,
+        },
+    };
+
+    let verification = state.db.verify_storage_proof_at(
+        &proof,
+        &storage_proof_request(body.request),
+        block_number,
+    )?;
+    let valid = verification.is_valid();
+
+    Ok(Json(ProofVerificationResponse {
+        block_number,
+        expected_state_root: verification.expected_state_root,
+        computed_state_root: verification.computed_state_root,
+        state_root_matches: verification.state_root_matches,
+        checks: verification
+            .checks
+            .into_iter()
+            .map(|check| ProofCheckResponse {
+                trie: check.trie.name().to_string(),
+                contract_address: check.contract_address,
+                key: check.key,
+                value: check.value,
+                error: check.error,
+            })
+            .collect(),
+        valid,
+    }))
+}
+
+/// Start a state-root audit on its own thread; poll its progress by id
+async fn start_state_root_audit(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<StateRootAuditRequest>,
+) -> Result<Json<StateRootAuditStatus>, ApiError> {
+    let to = request
+        .to
+        .or_else(|| state.db.get_latest_block_number())
+        .ok_or((StatusCode::NOT_FOUND, "Database has no blocks".to_string()))?;
+    if request.from > to {
+        return Err(ApiError::Status(
+            StatusCode::BAD_REQUEST,
+            format!("Empty block range {}..={}", request.from, to),
+        ));
+    }
+
+    let status = {
+        let mut audits = state.audits.lock().unwrap();
+        let status = StateRootAuditStatus {
+            id: audits.len() as u64,
+            from: request.from,
+            to,
+            status: "running".to_string(),
+            current_block: None,
+            blocks_checked: 0,
+            mismatches: 0,
+            first_mismatch: None,
+            error: None,
+        };
+        audits.push(status.clone());
+        status
+    };
+
+    let job = Arc::clone(&state);
+    let id = status.id as usize;
+    std::thread::spawn(move || {
+        let update = |f: &mut dyn FnMut(&mut StateRootAuditStatus)| {
+            f(&mut job.audits.lock().unwrap()[id]);
+        };
+
+        let result = job
+            .db
+            .audit_state_root(request.from, to, |progress| match progress {
+                db_reader::AuditProgress::Replaying { block_number } => {
+                    update(&mut |s| s.current_block = Some(block_number))
+                }
+                db_reader::AuditProgress::Checked(check) => update(&mut |s| {
+                    s.current_block = Some(check.block_number);
+                    s.blocks_checked += 1;
+                    if !check.matches() {
+                        s.mismatches += 1;
+                    }
+                }),
+            });
+
+        update(&mut |s| match &result {
+            Ok(report) => {
+                s.status = "done".to_string();
+                s.first_mismatch = report.first_mismatch.as_ref().map(mismatch_info);
+            }
+            Err(e) => {
+                s.status = "failed".to_string();
+                s.error = Some(e.to_string());
+            }
+        });
+    });
+
+    Ok(Json(status))
+}
+
+fn mismatch_info(mismatch: &db_reader::StateRootMismatch) -> StateRootMismatchInfo {
+    StateRootMismatchInfo {
+        block_number: mismatch.check.block_number,
+        expected_state_root: mismatch.check.expected_state_root.clone(),
+        computed_state_root: mismatch.check.computed_state_root.clone(),
+        contracts_tree_root: mismatch.check.contracts_tree_root.clone(),
+        classes_tree_root: mismatch.check.classes_tree_root.clone(),
+        divergent_tries: mismatch
+            .divergent_tries
+            .as_ref()
+            .map(|tries| trie_names(tries)),
+        changed_tries: trie_names(&mismatch.changed_tries),
+    }
+}
+
+fn trie_names(tries: &[db_reader::Trie]) -> Vec<String> {
+    tries.iter().map(|trie| trie.name().to_string()).collect()
+}
+
+async fn state_root_audit_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<usize>,
+) -> Result<Json<StateRootAuditStatus>, (StatusCode, String)> {
+    state
+        .audits
+        .lock()
+        .unwrap()
+        .get(id)
+        .cloned()
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, format!("Audit job {id} not found")))
+}
+
 async fn search(
     State(state): State<Arc<AppState>>,
     Query(query): Query<SearchQuery>,
@@ -20,6 +20,7 @@ async fn health_endpoint_works() {
     let state = Arc::new(api::AppState {
         db,
         indexer: Mutex::new(idx),
+        audits: Mutex::new(Vec::new()),
     });
 
     let app = api::build_router(state, None);
@@ -0,0 +1,1090 @@
+//! ABI-driven decoding of calldata and events
+//!
+//! Classes store their ABI in `class_info`: Sierra classes as the compiler's JSON, legacy
+//! classes re-encoded in the same JSON shape (see [`ClassDefinition::abi`]). This module reads
+//! both and decodes `__execute__` multicalls, direct calls and events into named values.
+
+use crate::blocks::Felt;
+use crate::contracts::parse_felt_hex;
+use crate::transactions::{TransactionBody, TransactionDetail, TransactionType};
+use crate::DbReader;
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// One decoded argument or event field
+#[derive(Debug, Clone)]
+pub struct DecodedArg {
+    pub name: String,
+    /// Type as written in the ABI, e.g. `core::integer::u256` or `felt*`
+    pub type_name: String,
+    /// Felts as hex strings, integers as decimal strings, structs as objects, enums as
+    /// `{ "Variant": value }`
+    pub value: Value,
+}
+
+/// One call of a transaction, with its arguments decoded when the target's ABI allows
+#[derive(Debug, Clone)]
+pub struct DecodedCall {
+    pub to: String,
+    pub selector: String,
+    /// Function name, when the selector resolves in the target's ABI
+    pub function: Option<String>,
+    /// Named arguments, when the calldata matches the function's inputs exactly
+    pub args: Option<Vec<DecodedArg>>,
+    pub calldata: Vec<String>,
+}
+
+/// An event decoded with its emitter's ABI
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+    /// Index of the event in the receipt
+    pub index: usize,
+    pub from_address: String,
+    /// Full ABI name of the event, e.g. `openzeppelin::token::erc20::ERC20Component::Transfer`
+    pub name: String,
+    pub fields: Vec<DecodedArg>,
+}
+
+/// Calls and events of a transaction decoded with the ABIs stored in `class_info`
+#[derive(Debug, Clone, Default)]
+pub struct DecodedTransaction {
+    pub calls: Vec<DecodedCall>,
+    /// Only events whose emitter ABI matched; raw events stay in [`TransactionDetail::events`]
+    pub events: Vec<DecodedEvent>,
+}
+
+type RawFelt = [u8; 32];
+
+/// Deepest nesting of ABI types or `flat` event enums followed while decoding. ABIs come from
+/// arbitrary classes, and a type that contains itself would otherwise recurse forever.
+const MAX_TYPE_DEPTH: usize = 64;
+
+/// Most types expanded while decoding one felt slice. Types that read no felts, like empty
+/// structs, can otherwise fan out exponentially within [`MAX_TYPE_DEPTH`]; calldata and
+/// event data are a few thousand felts at most.
+const MAX_TYPE_EXPANSIONS: usize = 100_000;
+
+/// Starknet field prime, big-endian
+const PRIME: RawFelt = {
+    let mut p = [0u8; 32];
+    p[0] = 0x08;
+    p[7] = 0x11;
+    p[31] = 0x01;
+    p
+};
+
+/// `starknet_keccak`: Keccak-256 truncated to 250 bits, used for selectors
+pub fn starknet_keccak(data: &[u8]) -> RawFelt {
+    let hash = Keccak256::digest(data);
+    let mut selector = [0u8; 32];
+    selector.copy_from_slice(&hash);
+    selector[0] &= 0x03;
+    selector
+}
+
+/// Selector of a function or event name
+pub fn selector_from_name(name: &str) -> String {
+    Felt(starknet_keccak(name.as_bytes())).to_hex()
+}
+
+#[derive(Debug, Clone)]
+struct AbiParam {
+    name: String,
+    ty: String,
+}
+
+#[derive(Debug, Clone)]
+struct AbiFunction {
+    name: String,
+    selector: RawFelt,
+    inputs: Vec<AbiParam>,
+}
+
+#[derive(Debug, Clone)]
+struct EventMember {
+    name: String,
+    ty: String,
+    /// `key`, `data`, `nested` or `flat`
+    kind: String,
+}
+
+#[derive(Debug, Clone)]
+enum AbiEvent {
+    Struct(Vec<EventMember>),
+    Enum(Vec<EventMember>),
+    /// Cairo 0 event, selected by the selector of its name
+    Legacy {
+        keys: Vec<AbiParam>,
+        data: Vec<AbiParam>,
+    },
+}
+
+/// Functions, types and events of one class ABI
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ContractAbi {
+    functions: Vec<AbiFunction>,
+    structs: HashMap<String, Vec<AbiParam>>,
+    enums: HashMap<String, Vec<AbiParam>>,
+    /// In ABI order
+    events: Vec<(String, AbiEvent)>,
+}
+
+impl ContractAbi {
+    /// Parse a Sierra or (re-encoded) legacy JSON ABI
+    pub(crate) fn parse(abi: &str) -> Option<Self> {
+        let entries: Vec<Value> = serde_json::from_str(abi).ok()?;
+        let mut parsed = ContractAbi::default();
+        for entry in &entries {
+            parsed.add_entry(entry);
+        }
+        Some(parsed)
+    }
+
+    fn add_entry(&mut self, entry: &Value) {
+        let name = entry["name"].as_str().unwrap_or_default().to_string();
+        match entry["type"].as_str() {
+            Some("function" | "l1_handler" | "constructor") => {
+                self.functions.push(AbiFunction {
+                    selector: starknet_keccak(name.as_bytes()),
+                    name,
+                    inputs: params(&entry["inputs"]),
+                });
+            }
+            Some("interface") => {
+                for item in entry["items"].as_array().into_iter().flatten() {
+                    self.add_entry(item);
+                }
+            }
+            Some("struct") => {
+                self.structs.insert(name, params(&entry["members"]));
+            }
+            Some("enum") => {
+                self.enums.insert(name, params(&entry["variants"]));
+            }
+            Some("event") => {
+                let event = match entry["kind"].as_str() {
+                    Some("struct") => AbiEvent::Struct(event_members(&entry["members"])),
+                    Some("enum") => AbiEvent::Enum(event_members(&entry["variants"])),
+                    _ => AbiEvent::Legacy {
+                        keys: params(&entry["keys"]),
+                        data: params(&entry["data"]),
+                    },
+                };
+                self.events.push((name, event));
+            }
+            _ => {}
+        }
+    }
+
+    fn function(&self, selector: &RawFelt) -> Option<&AbiFunction> {
+        self.functions.iter().find(|f| &f.selector == selector)
+    }
+
+    fn function_by_name(&self, name: &str) -> Option<&AbiFunction> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+
+    fn event(&self, name: &str) -> Option<&AbiEvent> {
+        self.events.iter().find(|(n, _)| n == name).map(|(_, e)| e)
+    }
+
+    /// Decode calldata as the inputs of `function`; `None` unless it matches exactly
+    fn decode_args(&self, function: &AbiFunction, calldata: &[RawFelt]) -> Option<Vec<DecodedArg>> {
+        let mut reader = FeltReader::new(self, calldata);
+        let args = reader.params(&function.inputs)?;
+        reader.is_done().then_some(args)
+    }
+
+    /// Decode an event; `None` unless one event definition consumes every key and data felt
+    fn decode_event(
+        &self,
+        keys: &[RawFelt],
+        data: &[RawFelt],
+    ) -> Option<(String, Vec<DecodedArg>)> {
+        let (selector, rest) = keys.split_first()?;
+
+        // Cairo 1: the contract's event enum, variants selected by name
+        let mut visited = HashSet::new();
+        for (name, event) in &self.events {
+            if let AbiEvent::Enum(_) = event {
+                if let Some(decoded) = self.match_enum(name, selector, rest, data, 0, &mut visited)
+                {
+                    return Some(decoded);
+                }
+            }
+        }
+        // Cairo 0, or Cairo 1 struct events emitted without an enclosing enum
+        self.events.iter().find_map(|(name, event)| {
+            let short_name = name.rsplit("::").next().unwrap_or(name);
+            if &starknet_keccak(short_name.as_bytes()) != selector {
+                return None;
+            }
+            match event {
+                AbiEvent::Legacy {
+                    keys,
+                    data: data_params,
+                } => {
+                    let mut key_reader = FeltReader::new(self, rest);
+                    let mut data_reader = FeltReader::new(self, data);
+                    let mut fields = key_reader.params(keys)?;
+                    fields.extend(data_reader.params(data_params)?);
+                    (key_reader.is_done() && data_reader.is_done()).then(|| (name.clone(), fields))
+                }
+                AbiEvent::Struct(_) => self.decode_event_type(name, rest, data, 0, &mut visited),
+                AbiEvent::Enum(_) => None,
+            }
+        })
+    }
+
+    /// Match `selector` against the variants of the event enum `name`.
+    ///
+    /// The outcome only depends on the enum and on how many keys are left, so `visited`
+    /// skips enums already tried with those keys: flat variants pointing at each other would
+    /// otherwise be explored along every path between them.
+    fn match_enum<'a>(
+        &'a self,
+        name: &'a str,
+        selector: &RawFelt,
+        keys: &[RawFelt],
+        data: &[RawFelt],
+        depth: usize,
+        visited: &mut HashSet<(&'a str, usize)>,
+    ) -> Option<(String, Vec<DecodedArg>)> {
+        if depth == MAX_TYPE_DEPTH || !visited.insert((name, keys.len())) {
+            return None;
+        }
+        let AbiEvent::Enum(variants) = self.event(name)? else {
+            return None;
+        };
+        variants
+            .iter()
+            .find_map(|variant| match variant.kind.as_str() {
+                "nested" if &starknet_keccak(variant.name.as_bytes()) == selector => {
+                    self.decode_event_type(&variant.ty, keys, data, depth + 1, visited)
+                }
+                // A flat variant's own variants are selected directly by the outer selector
+                "flat" => self.match_enum(&variant.ty, selector, keys, data, depth + 1, visited),
+                _ => None,
+            })
+    }
+
+    fn decode_event_type<'a>(
+        &'a self,
+        name: &'a str,
+        keys: &[RawFelt],
+        data: &[RawFelt],
+        depth: usize,
+        visited: &mut HashSet<(&'a str, usize)>,
+    ) -> Option<(String, Vec<DecodedArg>)> {
+        match self.event(name)? {
+            AbiEvent::Struct(members) => {
+                let mut key_reader = FeltReader::new(self, keys);
+                let mut data_reader = FeltReader::new(self, data);
+                let mut fields = Vec::with_capacity(members.len());
+                for member in members {
+                    let reader = if member.kind == "key" {
+                        &mut key_reader
+                    } else {
+                        &mut data_reader
+                    };
+                    fields.push(DecodedArg {
+                        name: member.name.clone(),
+                        type_name: member.ty.clone(),
+                        value: reader.value(&member.ty)?,
+                    });
+                }
+                (key_reader.is_done() && data_reader.is_done()).then(|| (name.to_string(), fields))
+            }
+            // A nested enum consumes one more key to select its variant
+            AbiEvent::Enum(_) => {
+                let (selector, rest) = keys.split_first()?;
+                self.match_enum(name, selector, rest, data, depth, visited)
+            }
+            AbiEvent::Legacy { .. } => None,
+        }
+    }
+}
+
+fn params(value: &Value) -> Vec<AbiParam> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|p| AbiParam {
+            name: p["name"].as_str().unwrap_or_default().to_string(),
+            ty: p["type"].as_str().unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+fn event_members(value: &Value) -> Vec<EventMember> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|m| EventMember {
+            name: m["name"].as_str().unwrap_or_default().to_string(),
+            ty: m["type"].as_str().unwrap_or_default().to_string(),
+            kind: m["kind"].as_str().unwrap_or("data").to_string(),
+        })
+        .collect()
+}
+
+/// Reads typed values off a felt slice
+struct FeltReader<'a> {
+    abi: &'a ContractAbi,
+    felts: &'a [RawFelt],
+    pos: usize,
+    /// Types being expanded by [`FeltReader::value`]
+    depth: usize,
+    /// Types expanded so far
+    expansions: usize,
+}
+
+impl<'a> FeltReader<'a> {
+    fn new(abi: &'a ContractAbi, felts: &'a [RawFelt]) -> Self {
+        FeltReader {
+            abi,
+            felts,
+            pos: 0,
+            depth: 0,
+            expansions: 0,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos == self.felts.len()
+    }
+
+    fn next(&mut self) -> Option<RawFelt> {
+        let felt = *self.felts.get(self.pos)?;
+        self.pos += 1;
+        Some(felt)
+    }
+
+    /// Read a length prefix, rejecting lengths the remaining felts cannot hold
+    fn len(&mut self) -> Option<usize> {
+        let len = usize::try_from(felt_to_u64(&self.next()?)?).ok()?;
+        (len <= self.felts.len() - self.pos).then_some(len)
+    }
+
+    /// Decode an argument list. Cairo 0 pointer arguments (`felt*`) take their length
+    /// from the preceding `<name>_len` argument.
+    fn params(&mut self, params: &[AbiParam]) -> Option<Vec<DecodedArg>> {
+        let mut lengths: HashMap<&str, usize> = HashMap::new();
+        let mut args = Vec::with_capacity(params.len());
+        for param in params {
+            let start = self.pos;
+            let value = match param.ty.strip_suffix('*') {
+                Some(element) => {
+                    let len = *lengths.get(format!("{}_len", param.name).as_str())?;
+                    if len > self.felts.len() - self.pos {
+                        return None;
+                    }
+                    self.array(element, len)?
+                }
+                None => self.value(&param.ty)?,
+            };
+            if self.pos == start + 1 {
+                if let Some(len) = felt_to_u64(&self.felts[start]) {
+                    lengths.insert(&param.name, len as usize);
+                }
+            }
+            args.push(DecodedArg {
+                name: param.name.clone(),
+                type_name: param.ty.clone(),
+                value,
+            });
+        }
+        Some(args)
+    }
+
+    fn array(&mut self, element: &str, len: usize) -> Option<Value> {
+        (0..len)
+            .map(|_| self.value(element))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array)
+    }
+
+    fn value(&mut self, ty: &str) -> Option<Value> {
+        if self.depth == MAX_TYPE_DEPTH || self.expansions == MAX_TYPE_EXPANSIONS {
+            return None;
+        }
+        self.depth += 1;
+        self.expansions += 1;
+        let value = self.read_value(ty.trim());
+        self.depth -= 1;
+        value
+    }
+
+    fn read_value(&mut self, ty: &str) -> Option<Value> {
+        match ty {
+            "()" => return Some(Value::Null),
+            "felt"
+            | "core::felt252"
+            | "core::starknet::contract_address::ContractAddress"
+            | "core::starknet::class_hash::ClassHash"
+            | "core::starknet::eth_address::EthAddress"
+            | "core::starknet::storage_access::StorageAddress"
+            | "core::bytes_31::bytes31" => return Some(Value::String(Felt(self.next()?).to_hex())),
+            "core::bool" => {
+                return match felt_to_u64(&self.next()?)? {
+                    0 => Some(Value::Bool(false)),
+                    1 => Some(Value::Bool(true)),
+                    _ => None,
+                }
+            }
+            "core::integer::u256" | "Uint256" => {
+                let (low, high) = (self.next()?, self.next()?);
+                if !fits_bits(&low, 128) || !fits_bits(&high, 128) {
+                    return None;
+                }
+                let mut value = [0u8; 32];
+                value[..16].copy_from_slice(&high[16..]);
+                value[16..].copy_from_slice(&low[16..]);
+                return Some(Value::String(to_decimal(&value)));
+            }
+            "core::byte_array::ByteArray" => return self.byte_array().map(Value::String),
+            _ => {}
+        }
+
+        if let Some(bits) = unsigned_bits(ty) {
+            let felt = self.next()?;
+            return fits_bits(&felt, bits).then(|| Value::String(to_decimal(&felt)));
+        }
+        if let Some(bits) = signed_bits(ty) {
+            return signed_decimal(&self.next()?, bits).map(Value::String);
+        }
+        if ty.starts_with('(') && ty.ends_with(')') {
+            return split_tuple(&ty[1..ty.len() - 1])
+                .into_iter()
+                .map(|element| self.value(element))
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array);
+        }
+        if let Some(element) = generic_arg(ty, "core::array::Array::")
+            .or_else(|| generic_arg(ty, "core::array::Span::"))
+        {
+            let len = self.len()?;
+            return self.array(element, len);
+        }
+
+        let abi = self.abi;
+        if let Some(members) = abi.structs.get(ty) {
+            let mut object = serde_json::Map::new();
+            for member in members {
+                object.insert(member.name.clone(), self.value(&member.ty)?);
+            }
+            return Some(Value::Object(object));
+        }
+        if let Some(variants) = abi.enums.get(ty) {
+            let index = usize::try_from(felt_to_u64(&self.next()?)?).ok()?;
+            let variant = variants.get(index)?;
+            let value = self.value(&variant.ty)?;
+            return Some(Value::Object(
+                [(variant.name.clone(), value)].into_iter().collect(),
+            ));
+        }
+        if let Some(inner) = generic_arg(ty, "core::option::Option::") {
+            return match felt_to_u64(&self.next()?)? {
+                0 => self.value(inner),
+                1 => Some(Value::Null),
+                _ => None,
+            };
+        }
+        None
+    }
+
+    /// `ByteArray { data: Array<bytes31>, pending_word: felt252, pending_word_len: u32 }`
+    fn byte_array(&mut self) -> Option<String> {
+        let words = self.len()?;
+        let mut bytes = Vec::with_capacity(words * 31 + 31);
+        for _ in 0..words {
+            bytes.extend_from_slice(&self.next()?[1..]);
+        }
+        let pending_word = self.next()?;
+        let pending_len = usize::try_from(felt_to_u64(&self.next()?)?).ok()?;
+        if pending_len > 31 {
+            return None;
+        }
+        bytes.extend_from_slice(&pending_word[32 - pending_len..]);
+        Some(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+/// `core::integer::u8`..`u128` and `usize`
+fn unsigned_bits(ty: &str) -> Option<usize> {
+    match ty {
+        "core::integer::usize" => Some(64),
+        _ => match ty.strip_prefix("core::integer::u")?.parse().ok()? {
+            bits @ (8 | 16 | 32 | 64 | 128) => Some(bits),
+            _ => None,
+        },
+    }
+}
+
+/// `core::integer::i8`..`i128`
+fn signed_bits(ty: &str) -> Option<usize> {
+    match ty.strip_prefix("core::integer::i")?.parse().ok()? {
+        bits @ (8 | 16 | 32 | 64 | 128) => Some(bits),
+        _ => None,
+    }
+}
+
+/// `T` of `prefix<T>`, e.g. `core::felt252` of `core::array::Array::<core::felt252>`
+fn generic_arg<'t>(ty: &'t str, prefix: &str) -> Option<&'t str> {
+    ty.strip_prefix(prefix)?
+        .strip_prefix('<')?
+        .strip_suffix('>')
+}
+
+/// Split tuple contents on top-level commas, dropping Cairo 0 member names (`x: felt`)
+fn split_tuple(contents: &str) -> Vec<&str> {
+    let mut elements = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, c) in contents.char_indices() {
+        match c {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                elements.push(&contents[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !contents.trim().is_empty() {
+        elements.push(&contents[start..]);
+    }
+    elements
+        .into_iter()
+        .map(|element| match element.split_once(": ") {
+            Some((_, ty)) => ty.trim(),
+            None => element.trim(),
+        })
+        .collect()
+}
+
+fn felt_to_u64(felt: &RawFelt) -> Option<u64> {
+    fits_bits(felt, 64).then(|| u64::from_be_bytes(felt[24..].try_into().unwrap()))
+}
+
+fn fits_bits(felt: &RawFelt, bits: usize) -> bool {
+    let zero_bytes = 32 - bits / 8;
+    felt[..zero_bytes].iter().all(|&b| b == 0)
+}
+
+fn to_decimal(value: &RawFelt) -> String {
+    let mut n = *value;
+    let mut digits = Vec::new();
+    loop {
+        let mut rem = 0u32;
+        for byte in n.iter_mut() {
+            let cur = (rem << 8) | u32::from(*byte);
+            *byte = (cur / 10) as u8;
+            rem = cur % 10;
+        }
+        digits.push(b'0' + rem as u8);
+        if n.iter().all(|&b| b == 0) {
+            break;
+        }
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("decimal digits are ASCII")
+}
+
+/// Signed integers are field elements; negative values are stored as `P - |x|`
+fn signed_decimal(felt: &RawFelt, bits: usize) -> Option<String> {
+    if fits_bits(felt, bits) {
+        // Non-negative values must leave the sign bit clear
+        let sign_bit = felt[32 - bits / 8] & 0x80 != 0;
+        return (!sign_bit).then(|| to_decimal(felt));
+    }
+    let magnitude = sub(&PRIME, felt)?;
+    fits_bits(&magnitude, bits).then(|| format!("-{}", to_decimal(&magnitude)))
+}
+
+/// `a - b`, or `None` if it would underflow
+fn sub(a: &RawFelt, b: &RawFelt) -> Option<RawFelt> {
+    let mut out = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut d = i16::from(a[i]) - i16::from(b[i]) - borrow;
+        borrow = i16::from(d < 0);
+        if d < 0 {
+            d += 256;
+        }
+        out[i] = d as u8;
+    }
+    (borrow == 0).then_some(out)
+}
+
+/// A call before its arguments are decoded
+struct RawCall {
+    to: RawFelt,
+    selector: RawFelt,
+    calldata: Vec<RawFelt>,
+}
+
+/// Split `__execute__` calldata into calls. Cairo 1 accounts (and recent Cairo 0 ones) pass
+/// `calls: Array<Call>`; older Cairo 0 accounts pass a call array followed by the
+/// concatenated calldata.
+fn split_multicall(
+    account_abi: Option<&ContractAbi>,
+    calldata: &[RawFelt],
+) -> Option<Vec<RawCall>> {
+    let legacy = account_abi
+        .and_then(|abi| abi.function_by_name("__execute__"))
+        .and_then(|f| f.inputs.first())
+        .map(|input| input.name == "call_array_len");
+    match legacy {
+        Some(true) => split_call_array(calldata),
+        Some(false) => split_calls(calldata),
+        None => split_calls(calldata).or_else(|| split_call_array(calldata)),
+    }
+}
+
+fn split_calls(calldata: &[RawFelt]) -> Option<Vec<RawCall>> {
+    let empty = ContractAbi::default();
+    let mut reader = FeltReader::new(&empty, calldata);
+    let count = reader.len()?;
+    let mut calls = Vec::with_capacity(count);
+    for _ in 0..count {
+        let to = reader.next()?;
+        let selector = reader.next()?;
+        let len = reader.len()?;
+        let start = reader.pos;
+        reader.pos += len;
+        calls.push(RawCall {
+            to,
+            selector,
+            calldata: calldata[start..reader.pos].to_vec(),
+        });
+    }
+    reader.is_done().then_some(calls)
+}
+
+fn split_call_array(calldata: &[RawFelt]) -> Option<Vec<RawCall>> {
+    let empty = ContractAbi::default();
+    let mut reader = FeltReader::new(&empty, calldata);
+    let count = reader.len()?;
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        let to = reader.next()?;
+        let selector = reader.next()?;
+        let offset = usize::try_from(felt_to_u64(&reader.next()?)?).ok()?;
+        let len = usize::try_from(felt_to_u64(&reader.next()?)?).ok()?;
+        entries.push((to, selector, offset, len));
+    }
+    let data_len = reader.len()?;
+    let data = &calldata[reader.pos..reader.pos + data_len];
+    reader.pos += data_len;
+    if !reader.is_done() {
+        return None;
+    }
+    entries
+        .into_iter()
+        .map(|(to, selector, offset, len)| {
+            Some(RawCall {
+                to,
+                selector,
+                calldata: data.get(offset..offset.checked_add(len)?)?.to_vec(),
+            })
+        })
+        .collect()
+}
+
+fn parse_felts(values: &[String]) -> Option<Vec<RawFelt>> {
+    values.iter().map(|v| parse_felt_hex(v)).collect()
+}
+
+/// Parsed class ABIs, by class hash. Reuse one across the transactions decoded for a request
+/// so each class is read and parsed once.
+#[derive(Default)]
+pub struct AbiCache {
+    by_class: HashMap<String, Option<Rc<ContractAbi>>>,
+}
+
+/// Resolves ABIs as of one transaction's block
+struct AbiResolver<'a> {
+    db: &'a DbReader,
+    block_n: u64,
+    cache: &'a mut AbiCache,
+}
+
+impl<'a> AbiResolver<'a> {
+    fn class_abi(&mut self, class_hash: &str) -> Option<Rc<ContractAbi>> {
+        let db = self.db;
+        self.cache
+            .by_class
+            .entry(class_hash.to_string())
+            .or_insert_with(|| {
+                let abi = db.get_class_abi(class_hash).ok()??;
+                ContractAbi::parse(&abi).map(Rc::new)
+            })
+            .clone()
+    }
+
+    fn contract_abi(&mut self, address: &RawFelt) -> Option<Rc<ContractAbi>> {
+        let class_hash = self
+            .db
+            .get_contract_class_hash(address, Some(self.block_n))?;
+        self.class_abi(&class_hash)
+    }
+
+    fn decode_call(&mut self, call: RawCall, abi: Option<Rc<ContractAbi>>) -> DecodedCall {
+        let function = abi.as_deref().and_then(|abi| abi.function(&call.selector));
+        let args = function.and_then(|f| abi.as_deref()?.decode_args(f, &call.calldata));
+        DecodedCall {
+            to: Felt(call.to).to_hex(),
+            selector: Felt(call.selector).to_hex(),
+            function: function.map(|f| f.name.clone()),
+            args,
+            calldata: call.calldata.iter().map(|f| Felt(*f).to_hex()).collect(),
+        }
+    }
+}
+
+impl DbReader {
+    /// Decode a transaction's calls and events with the ABIs of the classes involved, as of
+    /// the transaction's block. Parts that do not match an ABI are left out.
+    pub fn decode_transaction_abi(&self, tx: &TransactionDetail) -> DecodedTransaction {
+        self.decode_transaction_abi_with_cache(tx, &mut AbiCache::default())
+    }
+
+    /// [`DbReader::decode_transaction_abi`], reusing the ABIs already in `cache`
+    pub fn decode_transaction_abi_with_cache(
+        &self,
+        tx: &TransactionDetail,
+        cache: &mut AbiCache,
+    ) -> DecodedTransaction {
+        let mut cache = AbiResolver {
+            db: self,
+            block_n: tx.block_number,
+            cache,
+        };
+        let sender = tx.sender_address.as_deref().and_then(parse_felt_hex);
+        let calldata = parse_felts(&tx.calldata).unwrap_or_default();
+
+        let calls = match (&tx.tx_type, &tx.body, sender) {
+            // Invoke V0 calls the target directly
+            (
+                TransactionType::Invoke,
+                Some(TransactionBody::Invoke {
+                    entry_point_selector: Some(selector),
+                    ..
+                }),
+                Some(to),
+            )
+            | (
+                TransactionType::L1Handler,
+                Some(TransactionBody::L1Handler {
+                    entry_point_selector: selector,
+                    ..
+                }),
+                Some(to),
+            ) => parse_felt_hex(selector)
+                .map(|selector| {
+                    let abi = cache.contract_abi(&to);
+                    vec![cache.decode_call(
+                        RawCall {
+                            to,
+                            selector,
+                            calldata,
+                        },
+                        abi,
+                    )]
+                })
+                .unwrap_or_default(),
+            (TransactionType::Invoke, _, Some(account)) => {
+                let account_abi = cache.contract_abi(&account);
+                split_multicall(account_abi.as_deref(), &calldata)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|call| {
+                        let abi = cache.contract_abi(&call.to);
+                        cache.decode_call(call, abi)
+                    })
+                    .collect()
+            }
+            (
+                TransactionType::Deploy | TransactionType::DeployAccount,
+                Some(
+                    TransactionBody::Deploy {
+                        class_hash,
+                        constructor_calldata,
+                        ..
+                    }
+                    | TransactionBody::DeployAccount {
+                        class_hash,
+                        constructor_calldata,
+                        ..
+                    },
+                ),
+                _,
+            ) => {
+                let abi = cache.class_abi(class_hash);
+                let call = RawCall {
+                    to: sender
+                        .or_else(|| parse_felt_hex(class_hash))
+                        .unwrap_or_default(),
+                    selector: starknet_keccak(b"constructor"),
+                    calldata: parse_felts(constructor_calldata).unwrap_or_default(),
+                };
+                vec![cache.decode_call(call, abi)]
+            }
+            _ => vec![],
+        };
+
+        let events = tx
+            .events
+            .iter()
+            .enumerate()
+            .filter_map(|(index, event)| {
+                let address = parse_felt_hex(&event.from_address)?;
+                let abi = cache.contract_abi(&address)?;
+                let (name, fields) =
+                    abi.decode_event(&parse_felts(&event.keys)?, &parse_felts(&event.data)?)?;
+                Some(DecodedEvent {
+                    index,
+                    from_address: event.from_address.clone(),
+                    name,
+                    fields,
+                })
+            })
+            .collect();
+
+        DecodedTransaction { calls, events }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felts(values: &[u64]) -> Vec<RawFelt> {
+        values
+            .iter()
+            .map(|&v| {
+                let mut felt = [0u8; 32];
+                felt[24..].copy_from_slice(&v.to_be_bytes());
+                felt
+            })
+            .collect()
+    }
+
+    fn selector(name: &str) -> RawFelt {
+        starknet_keccak(name.as_bytes())
+    }
+
+    #[test]
+    fn selectors_use_starknet_keccak() {
+        assert_eq!(
+            selector_from_name("transfer"),
+            "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+        );
+    }
+
+    #[test]
+    fn decodes_sierra_multicall_and_nested_events() {
+        let abi = ContractAbi::parse(
+            r#"[
+                {"type": "interface", "name": "IERC20", "items": [
+                    {"type": "function", "name": "transfer", "inputs": [
+                        {"name": "recipient", "type": "core::starknet::contract_address::ContractAddress"},
+                        {"name": "amount", "type": "core::integer::u256"}
+                    ]},
+                    {"type": "function", "name": "set_name", "inputs": [
+                        {"name": "name", "type": "core::byte_array::ByteArray"},
+                        {"name": "flags", "type": "core::array::Span::<core::bool>"},
+                        {"name": "delta", "type": "core::integer::i8"}
+                    ]}
+                ]},
+                {"type": "event", "name": "erc20::Transfer", "kind": "struct", "members": [
+                    {"name": "from", "type": "core::starknet::contract_address::ContractAddress", "kind": "key"},
+                    {"name": "to", "type": "core::starknet::contract_address::ContractAddress", "kind": "key"},
+                    {"name": "value", "type": "core::integer::u256", "kind": "data"}
+                ]},
+                {"type": "event", "name": "erc20::Event", "kind": "enum", "variants": [
+                    {"name": "Transfer", "type": "erc20::Transfer", "kind": "nested"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        // calls: [transfer(0xb, 2^128 + 5)]
+        let mut calldata = felts(&[1, 0xa, 0, 3, 0xb, 5, 1]);
+        calldata[2] = selector("transfer");
+        let calls = split_multicall(None, &calldata).unwrap();
+        assert_eq!(calls.len(), 1);
+        let function = abi.function(&calls[0].selector).unwrap();
+        let args = abi.decode_args(function, &calls[0].calldata).unwrap();
+        assert_eq!(args[0].value, "0x0b");
+        assert_eq!(args[1].value, "340282366920938463463374607431768211461");
+        // Leftover calldata means the inputs do not match
+        assert!(abi.decode_args(function, &felts(&[0xb, 5, 1, 0])).is_none());
+
+        // "hi" as a ByteArray with no full words, two flags, and -1
+        let mut calldata = felts(&[0, 0x6869, 2, 2, 1, 0, 0]);
+        calldata[6] = sub(&PRIME, &felts(&[1])[0]).unwrap();
+        let function = abi.function(&selector("set_name")).unwrap();
+        let args = abi.decode_args(function, &calldata).unwrap();
+        assert_eq!(args[0].value, "hi");
+        assert_eq!(args[1].value, serde_json::json!([true, false]));
+        assert_eq!(args[2].value, "-1");
+
+        let mut keys = felts(&[0, 0xa, 0xb]);
+        keys[0] = selector("Transfer");
+        let (name, fields) = abi.decode_event(&keys, &felts(&[7, 0])).unwrap();
+        assert_eq!(name, "erc20::Transfer");
+        assert_eq!(fields[1].name, "to");
+        assert_eq!(fields[2].value, "7");
+        assert!(abi.decode_event(&keys, &felts(&[7])).is_none());
+    }
+
+    #[test]
+    fn rejects_unsupported_integer_widths() {
+        let abi = ContractAbi::default();
+        for ty in [
+            "core::integer::i0",
+            "core::integer::i7",
+            "core::integer::i300",
+        ] {
+            assert!(
+                FeltReader::new(&abi, &felts(&[1])).value(ty).is_none(),
+                "{ty}"
+            );
+        }
+        let value = FeltReader::new(&abi, &felts(&[1])).value("core::integer::i16");
+        assert_eq!(value.unwrap(), "1");
+    }
+
+    #[test]
+    fn gives_up_on_recursive_types() {
+        let abi = ContractAbi::parse(
+            r#"[
+                {"type": "struct", "name": "Node", "members": [
+                    {"name": "next", "type": "Node"}
+                ]},
+                {"type": "function", "name": "walk", "inputs": [
+                    {"name": "node", "type": "Node"}
+                ]},
+                {"type": "event", "name": "A", "kind": "enum", "variants": [
+                    {"name": "B", "type": "B", "kind": "flat"}
+                ]},
+                {"type": "event", "name": "B", "kind": "enum", "variants": [
+                    {"name": "A", "type": "A", "kind": "flat"}
+                ]},
+                {"type": "event", "name": "C", "kind": "enum", "variants": [
+                    {"name": "D1", "type": "D", "kind": "flat"},
+                    {"name": "D2", "type": "D", "kind": "flat"}
+                ]},
+                {"type": "event", "name": "D", "kind": "enum", "variants": [
+                    {"name": "C1", "type": "C", "kind": "flat"},
+                    {"name": "C2", "type": "C", "kind": "flat"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        let function = abi.function(&selector("walk")).unwrap();
+        assert!(abi.decode_args(function, &felts(&[1, 2])).is_none());
+        assert!(abi.decode_event(&[selector("A")], &[]).is_none());
+
+        // Two paths out of every level of C and D: 2^64 of them without pruning
+        let start = std::time::Instant::now();
+        assert!(abi.decode_event(&[selector("C")], &[]).is_none());
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn gives_up_on_wide_zero_width_types() {
+        // S0 is empty and every S<k> holds two S<k-1>: 2^60 expansions for S60, all within
+        // the depth limit and none reading a felt
+        let mut items = vec![serde_json::json!({"type": "struct", "name": "S0", "members": []})];
+        for k in 1..=60 {
+            let member =
+                |name: &str| serde_json::json!({"name": name, "type": format!("S{}", k - 1)});
+            items.push(serde_json::json!({
+                "type": "struct",
+                "name": format!("S{k}"),
+                "members": [member("a"), member("b")],
+            }));
+        }
+        items.push(serde_json::json!({
+            "type": "function",
+            "name": "wide",
+            "inputs": [{"name": "s", "type": "S60"}],
+        }));
+        items.push(serde_json::json!({
+            "type": "function",
+            "name": "narrow",
+            "inputs": [{"name": "s", "type": "S8"}],
+        }));
+        let abi = ContractAbi::parse(&serde_json::Value::Array(items).to_string()).unwrap();
+
+        let start = std::time::Instant::now();
+        let function = abi.function(&selector("wide")).unwrap();
+        assert!(abi.decode_args(function, &[]).is_none());
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+
+        // 2^9 expansions stay well within the budget
+        let function = abi.function(&selector("narrow")).unwrap();
+        assert!(abi.decode_args(function, &[]).is_some());
+    }
+
+    #[test]
+    fn decodes_legacy_call_array_and_pointer_arguments() {
+        let abi = ContractAbi::parse(
+            r#"[
+                {"type": "struct", "name": "Uint256", "size": 2, "members": [
+                    {"name": "low", "type": "felt", "offset": 0},
+                    {"name": "high", "type": "felt", "offset": 1}
+                ]},
+                {"type": "function", "name": "__execute__", "inputs": [
+                    {"name": "call_array_len", "type": "felt"},
+                    {"name": "call_array", "type": "AccountCallArray*"},
+                    {"name": "calldata_len", "type": "felt"},
+                    {"name": "calldata", "type": "felt*"}
+                ]},
+                {"type": "function", "name": "store", "inputs": [
+                    {"name": "values_len", "type": "felt"},
+                    {"name": "values", "type": "felt*"},
+                    {"name": "amount", "type": "Uint256"}
+                ]},
+                {"type": "struct", "name": "Empty", "size": 0, "members": []},
+                {"type": "function", "name": "skip", "inputs": [
+                    {"name": "items_len", "type": "felt"},
+                    {"name": "items", "type": "Empty*"}
+                ]},
+                {"type": "event", "name": "stored", "keys": [], "data": [
+                    {"name": "count", "type": "felt"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        // One call to 0xa, data at offset 0 of length 5: store([1, 2], 3)
+        let mut calldata = felts(&[1, 0xa, 0, 0, 5, 5, 2, 1, 2, 3, 0]);
+        calldata[2] = selector("store");
+        let calls = split_multicall(Some(&abi), &calldata).unwrap();
+        assert_eq!(calls[0].calldata, felts(&[2, 1, 2, 3, 0]));
+        let function = abi.function(&calls[0].selector).unwrap();
+        let args = abi.decode_args(function, &calls[0].calldata).unwrap();
+        assert_eq!(args[1].value, serde_json::json!(["0x01", "0x02"]));
+        assert_eq!(args[2].value, "3");
+
+        let mut keys = felts(&[0]);
+        keys[0] = selector("stored");
+        let (name, fields) = abi.decode_event(&keys, &felts(&[2])).unwrap();
+        assert_eq!(name, "stored");
+        assert_eq!(fields[0].value, "0x02");
+
+        // A pointer length longer than the remaining calldata, even for elements of no felts
+        let function = abi.function(&selector("skip")).unwrap();
+        assert!(abi.decode_args(function, &felts(&[1 << 62])).is_none());
+        assert!(abi.decode_args(function, &felts(&[0])).is_some());
+    }
+}
@@ -0,0 +1,562 @@
+//! State-root audit.
+//!
+//! Replays the state diffs from genesis, rebuilds the contract and class trie commitments
+//! from the resulting leaves and compares the global state root with the one recorded in
+//! `block_info`. Nothing is read from the bonsai tries while replaying, so a trie that drifted
+//! from the state it should commit to shows up as a mismatch.
+//!
+//! The tries are kept in memory with their node hashes between blocks; a state diff only
+//! rehashes the paths to the leaves it changed.
+//!
+//! Class trie leaves are `poseidon("CONTRACT_CLASS_LEAF_V0", compiled_class_hash)`; legacy
+//! (Cairo 0) classes are not part of the class trie.
+
+use std::collections::{BTreeMap, HashSet};
+
+use starknet_types_core::felt::Felt;
+
+use crate::proofs::{
+    bits_to_felt, class_leaf_hash, contract_leaf_hash, felt_hex, state_root_hash, trie_hash,
+};
+use crate::state_diff::RawStateDiff;
+use crate::tries::{key_bits, CLASS_IDENTIFIER, CONTRACT_IDENTIFIER};
+use crate::{DbError, DbReader, Trie};
+
+/// Recomputed state root for one block
+#[derive(Debug, Clone)]
+pub struct BlockStateRootCheck {
+    pub block_number: u64,
+    /// `global_state_root` from `block_info`
+    pub expected_state_root: String,
+    pub computed_state_root: String,
+    pub contracts_tree_root: String,
+    pub classes_tree_root: String,
+}
+
+impl BlockStateRootCheck {
+    pub fn matches(&self) -> bool {
+        self.expected_state_root == self.computed_state_root
+    }
+}
+
+/// First block whose recomputed state root differs from the recorded one
+#[derive(Debug, Clone)]
+pub struct StateRootMismatch {
+    pub check: BlockStateRootCheck,
+    /// Sub-tries whose recomputed root differs from the on-disk bonsai root:
+    /// [`Trie::Contract`] and/or [`Trie::Class`]. The bonsai tries only describe the latest
+    /// block, so this is `None` for earlier blocks.
+    pub divergent_tries: Option<Vec<Trie>>,
+    /// Sub-tries that changed since the last matching block, the candidates for the
+    /// divergence. Empty when neither changed and only the recorded root moved.
+    pub changed_tries: Vec<Trie>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StateRootAuditReport {
+    pub from: u64,
+    pub to: u64,
+    pub blocks_checked: u64,
+    pub mismatches: u64,
+    pub first_mismatch: Option<StateRootMismatch>,
+}
+
+/// Reported once per block while an audit runs
+#[derive(Debug, Clone, Copy)]
+pub enum AuditProgress<'a> {
+    /// Applying a state diff from before the audited range
+    Replaying { block_number: u64 },
+    /// A block in the audited range was checked
+    Checked(&'a BlockStateRootCheck),
+}
+
+struct ContractState {
+    class_hash: Felt,
+    nonce: Felt,
+    storage: IncrementalTrie,
+}
+
+impl Default for ContractState {
+    fn default() -> Self {
+        Self {
+            class_hash: Felt::ZERO,
+            nonce: Felt::ZERO,
+            storage: IncrementalTrie::new(Trie::ContractStorage),
+        }
+    }
+}
+
+/// Which sub-tries a state diff touched
+#[derive(Debug, Clone, Copy, Default)]
+struct Touched {
+    contracts: bool,
+    classes: bool,
+}
+
+impl Touched {
+    fn merge(&mut self, other: Touched) {
+        self.contracts |= other.contracts;
+        self.classes |= other.classes;
+    }
+
+    fn tries(self) -> Vec<Trie> {
+        let mut tries = Vec::new();
+        if self.contracts {
+            tries.push(Trie::Contract);
+        }
+        if self.classes {
+            tries.push(Trie::Class);
+        }
+        tries
+    }
+}
+
+/// State accumulated from state diffs, as tries that keep their node hashes between blocks
+struct StateReplay {
+    contracts: BTreeMap<[u8; 32], ContractState>,
+    contracts_trie: IncrementalTrie,
+    /// Class hash to class leaf; legacy classes are left out
+    classes_trie: IncrementalTrie,
+    /// Contracts whose leaf in `contracts_trie` is out of date
+    dirty_contracts: HashSet<[u8; 32]>,
+}
+
+pub(crate) fn felt_key(bytes: &[u8]) -> [u8; 32] {
+    Felt::from_bytes_be_slice(bytes).to_bytes_be()
+}
+
+impl StateReplay {
+    fn new() -> Self {
+        Self {
+            contracts: BTreeMap::new(),
+            contracts_trie: IncrementalTrie::new(Trie::Contract),
+            classes_trie: IncrementalTrie::new(Trie::Class),
+            dirty_contracts: HashSet::new(),
+        }
+    }
+
+    fn contract(&mut self, address: &[u8]) -> &mut ContractState {
+        let address = felt_key(address);
+        self.dirty_contracts.insert(address);
+        self.contracts.entry(address).or_default()
+    }
+
+    fn apply(&mut self, diff: &RawStateDiff) -> Touched {
+        for item in &diff.storage_diffs {
+            let contract = self.contract(&item.address);
+            for entry in &item.storage_entries {
+                contract.storage.insert(
+                    &felt_key(&entry.key),
+                    Felt::from_bytes_be_slice(&entry.value),
+                );
+            }
+        }
+        for item in &diff.deployed_contracts {
+            self.contract(&item.address).class_hash = Felt::from_bytes_be_slice(&item.class_hash);
+        }
+        for item in &diff.replaced_classes {
+            self.contract(&item.contract_address).class_hash =
+                Felt::from_bytes_be_slice(&item.class_hash);
+        }
+        for item in &diff.nonces {
+            self.contract(&item.contract_address).nonce = Felt::from_bytes_be_slice(&item.nonce);
+        }
+        let declared = diff
+            .declared_classes
+            .iter()
+            .map(|item| (&item.class_hash, &item.compiled_class_hash));
+        let migrated = diff
+            .migrated_compiled_classes
+            .iter()
+            .map(|item| (&item.class_hash, &item.compiled_class_hash));
+        for (class_hash, compiled_class_hash) in declared.chain(migrated) {
+            let compiled = Felt::from_bytes_be_slice(compiled_class_hash);
+            let leaf = if compiled == Felt::ZERO {
+                Felt::ZERO
+            } else {
+                class_leaf_hash(&compiled)
+            };
+            self.classes_trie.insert(&felt_key(class_hash), leaf);
+        }
+
+        Touched {
+            contracts: !diff.storage_diffs.is_empty()
+                || !diff.deployed_contracts.is_empty()
+                || !diff.replaced_classes.is_empty()
+                || !diff.nonces.is_empty(),
+            classes: !diff.declared_classes.is_empty()
+                || !diff.migrated_compiled_classes.is_empty(),
+        }
+    }
+
+    /// Contract and class trie roots of the current state
+    fn roots(&mut self) -> (Felt, Felt) {
+        for address in self.dirty_contracts.drain() {
+            let contract = self.contracts.get_mut(&address).expect("dirty contract");
+            let storage_root = contract.storage.root_hash();
+            self.contracts_trie.insert(
+                &address,
+                contract_leaf_hash(&contract.class_hash, &storage_root, &contract.nonce),
+            );
+        }
+        (
+            self.contracts_trie.root_hash(),
+            self.classes_trie.root_hash(),
+        )
+    }
+}
+
+/// Binary Merkle-Patricia trie that caches node hashes, so after an update only the nodes on
+/// the updated path are rehashed
+struct IncrementalTrie {
+    trie: Trie,
+    root: Option<Box<TrieNode>>,
+}
+
+enum TrieNode {
+    Leaf(Felt),
+    Binary {
+        children: [Box<TrieNode>; 2],
+        /// `None` until hashed, and again after a change below
+        hash: Option<Felt>,
+    },
+    Edge {
+        path: Vec<bool>,
+        child: Box<TrieNode>,
+        hash: Option<Felt>,
+    },
+}
+
+impl IncrementalTrie {
+    fn new(trie: Trie) -> Self {
+        Self { trie, root: None }
+    }
+
+    /// Set the leaf at `key`; zero removes it
+    fn insert(&mut self, key: &[u8; 32], value: Felt) {
+        self.root = insert_leaf(self.root.take(), &key_bits(key), value);
+    }
+
+    fn root_hash(&mut self) -> Felt {
+        let trie = self.trie;
+        self.root
+            .as_mut()
+            .map_or(Felt::ZERO, |root| root.hash(trie))
+    }
+}
+
+impl TrieNode {
+    fn hash(&mut self, trie: Trie) -> Felt {
+        match self {
+            TrieNode::Leaf(value) => *value,
+            TrieNode::Binary { children, hash } => *hash.get_or_insert_with(|| {
+                let [left, right] = children;
+                trie_hash(trie, &left.hash(trie), &right.hash(trie))
+            }),
+            TrieNode::Edge { path, child, hash } => *hash.get_or_insert_with(|| {
+                trie_hash(trie, &child.hash(trie), &bits_to_felt(path))
+                    + Felt::from(path.len() as u64)
+            }),
+        }
+    }
+}
+
+/// `node` with the leaf at `bits` (relative to the node) set to `value`. Nodes off the path
+/// keep their cached hashes.
+fn insert_leaf(node: Option<Box<TrieNode>>, bits: &[bool], value: Felt) -> Option<Box<TrieNode>> {
+    let Some(node) = node else {
+        return (value != Felt::ZERO).then(|| edge(bits.to_vec(), Box::new(TrieNode::Leaf(value))));
+    };
+    match *node {
+        TrieNode::Leaf(_) => (value != Felt::ZERO).then(|| Box::new(TrieNode::Leaf(value))),
+        TrieNode::Binary {
+            children: [left, right],
+            ..
+        } => {
+            let (left, right) = if bits[0] {
+                (Some(left), insert_leaf(Some(right), &bits[1..], value))
+            } else {
+                (insert_leaf(Some(left), &bits[1..], value), Some(right))
+            };
+            match (left, right) {
+                (Some(left), Some(right)) => Some(Box::new(TrieNode::Binary {
+                    children: [left, right],
+                    hash: None,
+                })),
+                // A binary node left with one child becomes an edge
+                (Some(left), None) => Some(edge(vec![false], left)),
+                (None, Some(right)) => Some(edge(vec![true], right)),
+                (None, None) => None,
+            }
+        }
+        TrieNode::Edge { path, child, hash } => {
+            let common = path.iter().zip(bits).take_while(|(a, b)| a == b).count();
+            if common == path.len() {
+                return insert_leaf(Some(child), &bits[common..], value)
+                    .map(|child| edge(path, child));
+            }
+            if value == Felt::ZERO {
+                // The key is not in the trie
+                return Some(Box::new(TrieNode::Edge { path, child, hash }));
+            }
+            // Split the edge where the key leaves it
+            let existing = edge(path[common + 1..].to_vec(), child);
+            let added = edge(bits[common + 1..].to_vec(), Box::new(TrieNode::Leaf(value)));
+            let children = if bits[common] {
+                [existing, added]
+            } else {
+                [added, existing]
+            };
+            Some(edge(
+                path[..common].to_vec(),
+                Box::new(TrieNode::Binary {
+                    children,
+                    hash: None,
+                }),
+            ))
+        }
+    }
+}
+
+/// An edge along `path` to `child`, merged with `child` when it is an edge too
+fn edge(path: Vec<bool>, child: Box<TrieNode>) -> Box<TrieNode> {
+    if path.is_empty() {
+        return child;
+    }
+    match *child {
+        TrieNode::Edge {
+            path: rest, child, ..
+        } => Box::new(TrieNode::Edge {
+            path: [path, rest].concat(),
+            child,
+            hash: None,
+        }),
+        child => Box::new(TrieNode::Edge {
+            path,
+            child: Box::new(child),
+            hash: None,
+        }),
+    }
+}
+
+/// Hash of the node at `depth` holding `leaves`, all of which share their first `depth` bits.
+/// `hash` is the trie's node hash.
+pub(crate) fn subtree_hash(
+    hash: &impl Fn(&Felt, &Felt) -> Felt,
+    leaves: &[(Vec<bool>, Felt)],
+    depth: usize,
+) -> Felt {
+    let (first, last) = (&leaves[0].0, &leaves[leaves.len() - 1].0);
+    // Sorted keys: where the first and last differ is where the subtree branches
+    let split = (depth..first.len())
+        .find(|&i| first[i] != last[i])
+        .unwrap_or(first.len());
+
+    let child = if split == first.len() {
+        leaves[0].1
+    } else {
+        let right_start = leaves.partition_point(|(bits, _)| !bits[split]);
+        hash(
+            &subtree_hash(hash, &leaves[..right_start], split + 1),
+            &subtree_hash(hash, &leaves[right_start..], split + 1),
+        )
+    };
+
+    if split == depth {
+        child
+    } else {
+        let path = &first[depth..split];
+        hash(&child, &bits_to_felt(path)) + Felt::from(path.len() as u64)
+    }
+}
+
+impl DbReader {
+    pub(crate) fn get_raw_state_diff(&self, block_n: u64) -> Result<RawStateDiff, DbError> {
+        let missing = || DbError::InvalidInput(format!("block {block_n} has no state diff"));
+        let cf = self.db.cf_handle("block_state_diff").ok_or_else(missing)?;
+        let key = u32::try_from(block_n).map_err(|_| missing())?.to_be_bytes();
+        let value = self.db.get_cf(&cf, key)?.ok_or_else(missing)?;
+        Ok(self.decoder.decode_state_diff(&key, &value)?)
+    }
+
+    /// Recompute the global state root of every block in `from..=to` from the state diffs and
+    /// compare it with `block_info`.
+    ///
+    /// Diffs before `from` are replayed first, so auditing a late range still reads the whole
+    /// history. `progress` is called once per block.
+    pub fn audit_state_root(
+        &self,
+        from: u64,
+        to: u64,
+        mut progress: impl FnMut(AuditProgress<'_>),
+    ) -> Result<StateRootAuditReport, DbError> {
+        if from > to {
+            return Err(DbError::InvalidInput(format!(
+                "empty block range {from}..={to}"
+            )));
+        }
+
+        let mut state = StateReplay::new();
+        for block_number in 0..from {
+            state.apply(&self.get_raw_state_diff(block_number)?);
+            progress(AuditProgress::Replaying { block_number });
+        }
+
+        let latest = self.get_latest_block_number();
+        let mut report = StateRootAuditReport {
+            from,
+            to,
+            blocks_checked: 0,
+            mismatches: 0,
+            first_mismatch: None,
+        };
+        // Nothing before the range has been checked, so any sub-trie could be off
+        let mut changed_since_match = Touched {
+            contracts: from > 0,
+            classes: from > 0,
+        };
+
+        for block_number in from..=to {
+            changed_since_match.merge(state.apply(&self.get_raw_state_diff(block_number)?));
+            let (contracts_root, classes_root) = state.roots();
+
+            let block = self.get_raw_block(block_number)?.ok_or_else(|| {
+                DbError::InvalidInput(format!("block {block_number} has no block_info"))
+            })?;
+            let check = BlockStateRootCheck {
+                block_number,
+                expected_state_root: felt_hex(&Felt::from_bytes_be_slice(
+                    &block.header.global_state_root,
+                )),
+                computed_state_root: felt_hex(&state_root_hash(&contracts_root, &classes_root)),
+                contracts_tree_root: felt_hex(&contracts_root),
+                classes_tree_root: felt_hex(&classes_root),
+            };
+            report.blocks_checked += 1;
+            progress(AuditProgress::Checked(&check));
+
+            if check.matches() {
+                changed_since_match = Touched::default();
+                continue;
+            }
+            report.mismatches += 1;
+            if report.first_mismatch.is_none() {
+                let divergent_tries = if Some(block_number) == latest {
+                    Some(self.divergent_tries(&check)?)
+                } else {
+                    None
+                };
+                report.first_mismatch = Some(StateRootMismatch {
+                    check,
+                    divergent_tries,
+                    changed_tries: changed_since_match.tries(),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// At the latest block the bonsai tries hold the same state, so the sub-tries can be
+    /// compared one by one
+    fn divergent_tries(&self, check: &BlockStateRootCheck) -> Result<Vec<Trie>, DbError> {
+        let mut tries = Vec::new();
+        if self.trie_root(Trie::Contract, CONTRACT_IDENTIFIER)? != check.contracts_tree_root {
+            tries.push(Trie::Contract);
+        }
+        if self.trie_root(Trie::Class, CLASS_IDENTIFIER)? != check.classes_tree_root {
+            tries.push(Trie::Class);
+        }
+        Ok(tries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proofs::{merkle_node_hash, MerkleNode};
+
+    /// Root rebuilt from every leaf, the reference for [`IncrementalTrie`]
+    fn compute_trie_root(trie: Trie, leaves: impl Iterator<Item = ([u8; 32], Felt)>) -> Felt {
+        let leaves: Vec<(Vec<bool>, Felt)> = leaves
+            .filter(|(_, value)| *value != Felt::ZERO)
+            .map(|(key, value)| (key_bits(&key), value))
+            .collect();
+        if leaves.is_empty() {
+            return Felt::ZERO;
+        }
+        subtree_hash(&|a, b| trie_hash(trie, a, b), &leaves, 0)
+    }
+
+    fn key(last: u8) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        key[31] = last;
+        key
+    }
+
+    #[test]
+    fn single_leaf_root_is_full_edge() {
+        let value = Felt::from(42u64);
+        let root = compute_trie_root(Trie::ContractStorage, [(key(5), value)].into_iter());
+        let expected = merkle_node_hash(
+            Trie::ContractStorage,
+            &MerkleNode::Edge {
+                path: felt_hex(&bits_to_felt(&key_bits(&key(5)))),
+                length: 251,
+                child: felt_hex(&value),
+            },
+        )
+        .unwrap();
+        assert_eq!(felt_hex(&root), expected);
+        assert_eq!(
+            compute_trie_root(Trie::ContractStorage, std::iter::empty()),
+            Felt::ZERO
+        );
+    }
+
+    #[test]
+    fn leaves_branch_where_keys_differ() {
+        // 0b100 and 0b101 share all bits but the last
+        let (a, b) = (Felt::from(1u64), Felt::from(2u64));
+        let root = compute_trie_root(
+            Trie::Contract,
+            [(key(4), a), (key(5), b), (key(6), Felt::ZERO)].into_iter(),
+        );
+        let binary = trie_hash(Trie::Contract, &a, &b);
+        let shared = &key_bits(&key(4))[..250];
+        let expected =
+            trie_hash(Trie::Contract, &binary, &bits_to_felt(shared)) + Felt::from(250u64);
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn incremental_trie_matches_rebuilt_root() {
+        let mut trie = IncrementalTrie::new(Trie::ContractStorage);
+        let mut leaves = BTreeMap::new();
+        // Inserts, overwrites and removals, including ones that split and merge edges
+        let updates = [
+            (4, 1u64),
+            (5, 2),
+            (200, 3),
+            (4, 7),
+            (6, 0),
+            (5, 0),
+            (7, 8),
+            (4, 0),
+            (200, 0),
+            (7, 0),
+            (1, 9),
+        ];
+        for (last, value) in updates {
+            trie.insert(&key(last), Felt::from(value));
+            leaves.insert(key(last), Felt::from(value));
+            let expected =
+                compute_trie_root(Trie::ContractStorage, leaves.iter().map(|(k, v)| (*k, *v)));
+            assert_eq!(
+                trie.root_hash(),
+                expected,
+                "after setting {last} to {value}"
+            );
+        }
+    }
+}
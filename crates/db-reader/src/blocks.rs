@@ -1,6 +1,6 @@
 //! Block reading functionality
 
-use crate::DbReader;
+use crate::{DbError, DbReader};
 use serde::Deserialize;
 use serde_bytes::ByteBuf;
 
@@ -26,7 +26,7 @@ impl Felt {
 // Raw types for deserialization (bincode serializes Felt using serialize_bytes)
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawGasPrices {
+pub(crate) struct RawGasPrices {
     pub eth_l1_gas_price: u128,
     pub strk_l1_gas_price: u128,
     pub eth_l1_data_gas_price: u128,
@@ -36,18 +36,38 @@ struct RawGasPrices {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-enum RawL1DataAvailabilityMode {
+pub(crate) enum RawL1DataAvailabilityMode {
     Calldata,
     Blob,
 }
 
+impl std::fmt::Display for RawL1DataAvailabilityMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawL1DataAvailabilityMode::Calldata => write!(f, "CALLDATA"),
+            RawL1DataAvailabilityMode::Blob => write!(f, "BLOB"),
+        }
+    }
+}
+
 // StarknetVersion is a newtype around [u8; 4], so we match that
 #[derive(Debug, Clone, Deserialize)]
-struct RawStarknetVersion([u8; 4]);
+pub(crate) struct RawStarknetVersion(pub [u8; 4]);
+
+impl std::fmt::Display for RawStarknetVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [major, minor, patch, build] = self.0;
+        if build == 0 {
+            write!(f, "{major}.{minor}.{patch}")
+        } else {
+            write!(f, "{major}.{minor}.{patch}.{build}")
+        }
+    }
+}
 
 // Use ByteBuf for Felt fields since Felt uses serialize_bytes/deserialize_bytes
 #[derive(Debug, Clone, Deserialize)]
-struct RawHeader {
+pub(crate) struct RawHeader {
     pub parent_block_hash: ByteBuf,
     pub block_number: u64,
     pub global_state_root: ByteBuf,
@@ -66,41 +86,13 @@ struct RawHeader {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawMadaraBlockInfo {
+pub(crate) struct RawMadaraBlockInfo {
     pub header: RawHeader,
     pub block_hash: ByteBuf,
     pub total_l2_gas_used: u128,
     pub tx_hashes: Vec<ByteBuf>,
 }
 
-// Internal type for transactions module
-#[derive(Debug, Clone, Deserialize)]
-pub(crate) struct RawMadaraBlockInfoInternal {
-    pub header: RawHeaderInternal,
-    pub block_hash: ByteBuf,
-    pub total_l2_gas_used: u128,
-    pub tx_hashes: Vec<ByteBuf>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub(crate) struct RawHeaderInternal {
-    pub parent_block_hash: ByteBuf,
-    pub block_number: u64,
-    pub global_state_root: ByteBuf,
-    pub sequencer_address: ByteBuf,
-    pub block_timestamp: u64,
-    pub transaction_count: u64,
-    pub transaction_commitment: ByteBuf,
-    pub event_count: u64,
-    pub event_commitment: ByteBuf,
-    pub state_diff_length: Option<u64>,
-    pub state_diff_commitment: Option<ByteBuf>,
-    pub receipt_commitment: Option<ByteBuf>,
-    pub protocol_version: RawStarknetVersion,
-    pub gas_prices: RawGasPrices,
-    pub l1_da_mode: RawL1DataAvailabilityMode,
-}
-
 /// Simplified block summary for API responses
 #[derive(Debug, Clone)]
 pub struct BlockSummary {
@@ -111,6 +103,30 @@ pub struct BlockSummary {
     pub transaction_count: u64,
 }
 
+/// Gas prices of a block, in wei (ETH) and fri (STRK)
+#[derive(Debug, Clone)]
+pub struct GasPrices {
+    pub eth_l1_gas_price: u128,
+    pub strk_l1_gas_price: u128,
+    pub eth_l1_data_gas_price: u128,
+    pub strk_l1_data_gas_price: u128,
+    pub eth_l2_gas_price: u128,
+    pub strk_l2_gas_price: u128,
+}
+
+impl From<RawGasPrices> for GasPrices {
+    fn from(raw: RawGasPrices) -> Self {
+        Self {
+            eth_l1_gas_price: raw.eth_l1_gas_price,
+            strk_l1_gas_price: raw.strk_l1_gas_price,
+            eth_l1_data_gas_price: raw.eth_l1_data_gas_price,
+            strk_l1_data_gas_price: raw.strk_l1_data_gas_price,
+            eth_l2_gas_price: raw.eth_l2_gas_price,
+            strk_l2_gas_price: raw.strk_l2_gas_price,
+        }
+    }
+}
+
 /// Full block details
 #[derive(Debug, Clone)]
 pub struct BlockDetail {
@@ -124,6 +140,16 @@ pub struct BlockDetail {
     pub event_count: u64,
     pub l2_gas_used: u128,
     pub tx_hashes: Vec<String>,
+    pub transaction_commitment: String,
+    pub event_commitment: String,
+    pub state_diff_length: Option<u64>,
+    pub state_diff_commitment: Option<String>,
+    pub receipt_commitment: Option<String>,
+    /// Starknet protocol version, e.g. "0.13.2"
+    pub protocol_version: String,
+    pub gas_prices: GasPrices,
+    /// L1 data availability mode: "CALLDATA" or "BLOB"
+    pub l1_da_mode: String,
 }
 
 impl From<RawMadaraBlockInfo> for BlockSummary {
@@ -155,39 +181,52 @@ impl From<RawMadaraBlockInfo> for BlockDetail {
                 .into_iter()
                 .map(|f| Felt::from_bytes(&f).to_hex())
                 .collect(),
+            transaction_commitment: Felt::from_bytes(&info.header.transaction_commitment).to_hex(),
+            event_commitment: Felt::from_bytes(&info.header.event_commitment).to_hex(),
+            state_diff_length: info.header.state_diff_length,
+            state_diff_commitment: info
+                .header
+                .state_diff_commitment
+                .map(|f| Felt::from_bytes(&f).to_hex()),
+            receipt_commitment: info
+                .header
+                .receipt_commitment
+                .map(|f| Felt::from_bytes(&f).to_hex()),
+            protocol_version: info.header.protocol_version.to_string(),
+            gas_prices: info.header.gas_prices.into(),
+            l1_da_mode: info.header.l1_da_mode.to_string(),
         }
     }
 }
 
 impl DbReader {
     /// Get block info by block number with detailed error reporting
-    fn get_raw_block(&self, block_n: u64) -> Option<RawMadaraBlockInfo> {
-        use bincode::Options;
-
-        let cf = self.db.cf_handle("block_info")?;
-        let block_n_u32 = u32::try_from(block_n).ok()?;
-        let value = self.db.get_cf(&cf, block_n_u32.to_be_bytes()).ok()??;
-
-        // Use same options as madara: bincode::DefaultOptions::new()
-        let opts = bincode::DefaultOptions::new();
-        match opts.deserialize::<RawMadaraBlockInfo>(&value) {
-            Ok(info) => Some(info),
-            Err(e) => {
-                eprintln!("Failed to deserialize block {}: {}", block_n, e);
-                eprintln!("First 50 bytes: {:?}", &value[..50.min(value.len())]);
-                None
-            }
-        }
+    pub(crate) fn get_raw_block(
+        &self,
+        block_n: u64,
+    ) -> Result<Option<RawMadaraBlockInfo>, DbError> {
+        let Some(cf) = self.db.cf_handle("block_info") else {
+            return Ok(None);
+        };
+        let Ok(block_n_u32) = u32::try_from(block_n) else {
+            return Ok(None);
+        };
+        let key = block_n_u32.to_be_bytes();
+        let Some(value) = self.db.get_cf(&cf, key)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(self.decoder.decode_block_info(&key, &value)?))
     }
 
     /// Get block summary by block number
-    pub fn get_block_summary(&self, block_n: u64) -> Option<BlockSummary> {
-        self.get_raw_block(block_n).map(BlockSummary::from)
+    pub fn get_block_summary(&self, block_n: u64) -> Result<Option<BlockSummary>, DbError> {
+        Ok(self.get_raw_block(block_n)?.map(BlockSummary::from))
     }
 
     /// Get block detail by block number
-    pub fn get_block_detail(&self, block_n: u64) -> Option<BlockDetail> {
-        self.get_raw_block(block_n).map(BlockDetail::from)
+    pub fn get_block_detail(&self, block_n: u64) -> Result<Option<BlockDetail>, DbError> {
+        Ok(self.get_raw_block(block_n)?.map(BlockDetail::from))
     }
 
     /// Get paginated list of blocks (newest first)
@@ -208,7 +247,8 @@ impl DbReader {
                 None => break,
             };
 
-            if let Some(summary) = self.get_block_summary(block_n) {
+            // Undecodable blocks are skipped here; the detail view reports why
+            if let Ok(Some(summary)) = self.get_block_summary(block_n) {
                 blocks.push(summary);
             }
         }
@@ -216,3 +256,71 @@ impl DbReader {
         blocks
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::BLOCK_INFO;
+    use crate::test_utils::open_db;
+
+    #[test]
+    fn decodes_devnet_block_detail() {
+        let (_dir, reader) = open_db(&[
+            (
+                "block_info",
+                4u32.to_be_bytes().to_vec(),
+                hex::decode(BLOCK_INFO).unwrap(),
+            ),
+            ("block_info", 5u32.to_be_bytes().to_vec(), vec![0xff]),
+        ]);
+
+        let block = reader.get_block_detail(4).unwrap().unwrap();
+        assert_eq!(block.block_number, 4);
+        assert_eq!(
+            block.block_hash,
+            "0x0309d73fff79c7ac1255f39544b56de199bc7bff6299272212f23dc848e07743"
+        );
+        assert_eq!(block.sequencer_address, "0x0123");
+        assert_eq!(block.timestamp, 1769764822);
+        assert_eq!(block.l2_gas_used, 653327360);
+        assert_eq!(
+            block.transaction_commitment,
+            "0x03d965bb1887f963aed7cdeadc40b902a7a03258c4fbcca9f32c944c000a8a81"
+        );
+        assert_eq!(
+            block.event_commitment,
+            "0x030c71516137d499c50aabfe87e16c82705572aa32912c1f677b3501539a28a5"
+        );
+        assert_eq!(block.state_diff_length, Some(9));
+        assert_eq!(
+            block.state_diff_commitment.as_deref(),
+            Some("0x01d8d289f811fce0ffea2e8246306788b48d4028718dd8f821435a147afa1dd7")
+        );
+        assert_eq!(
+            block.receipt_commitment.as_deref(),
+            Some("0x019387f45d0fc79a0283534b7de1750d6c40bdae00dfb0131b8d28cfbbadf12f")
+        );
+        assert_eq!(block.protocol_version, "0.14.1");
+        assert_eq!(block.l1_da_mode, "BLOB");
+
+        let prices = &block.gas_prices;
+        assert_eq!(prices.eth_l1_gas_price, 128);
+        assert_eq!(prices.strk_l1_gas_price, 128);
+        assert_eq!(prices.eth_l1_data_gas_price, 128);
+        assert_eq!(prices.strk_l1_data_gas_price, 128);
+        assert_eq!(prices.eth_l2_gas_price, 99999);
+        assert_eq!(prices.strk_l2_gas_price, 100000);
+
+        assert!(reader.get_block_detail(6).unwrap().is_none());
+        match reader.get_block_detail(5) {
+            Err(DbError::Decode(err)) => assert_eq!(err.cf, "block_info"),
+            other => panic!("expected a decode error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn formats_protocol_version() {
+        assert_eq!(RawStarknetVersion([0, 13, 2, 0]).to_string(), "0.13.2");
+        assert_eq!(RawStarknetVersion([0, 13, 1, 1]).to_string(), "0.13.1.1");
+    }
+}
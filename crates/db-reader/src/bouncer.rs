@@ -0,0 +1,341 @@
+//! Block resource usage from `block_bouncer_weight`
+//!
+//! The bouncer caps what goes into a block; the weights it ended with are stored per
+//! block, keyed by the block number as a big-endian `u32`. Blockifier 0.14 replaced the
+//! builtin counts and steps with Sierra and proving gas, so both layouts are accepted.
+
+use rocksdb::{Direction, IteratorMode};
+use serde::Deserialize;
+
+use crate::meta::{RawMetaValue, META_RUNTIME_EXEC_CONFIG};
+use crate::{decode_value, DbError, DbReader, DecodeError};
+
+/// Most blocks returned by one range read
+pub const MAX_BOUNCER_WEIGHTS_RANGE: u64 = 1000;
+
+/// Resources used by one block
+#[derive(Debug, Clone)]
+pub struct BouncerWeights {
+    pub block_number: u64,
+    /// `gas` in the pre-0.14 layout
+    pub l1_gas: u64,
+    pub message_segment_length: u64,
+    pub n_events: u64,
+    pub state_diff_size: u64,
+    /// 0.14 layout only
+    pub sierra_gas: Option<u64>,
+    /// 0.14 layout only
+    pub n_txs: Option<u64>,
+    /// 0.14 layout only
+    pub proving_gas: Option<u64>,
+    /// Pre-0.14 layout only
+    pub n_steps: Option<u64>,
+    /// Pre-0.14 layout only
+    pub builtins: Option<BuiltinCount>,
+}
+
+/// Builtin applications counted by the pre-0.14 bouncer
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct BuiltinCount {
+    pub add_mod: u64,
+    pub bitwise: u64,
+    pub ecdsa: u64,
+    pub ec_op: u64,
+    pub keccak: u64,
+    pub mul_mod: u64,
+    pub pedersen: u64,
+    pub poseidon: u64,
+    pub range_check: u64,
+    pub range_check96: u64,
+}
+
+impl BuiltinCount {
+    pub fn total(&self) -> u64 {
+        [
+            self.add_mod,
+            self.bitwise,
+            self.ecdsa,
+            self.ec_op,
+            self.keccak,
+            self.mul_mod,
+            self.pedersen,
+            self.poseidon,
+            self.range_check,
+            self.range_check96,
+        ]
+        .iter()
+        .fold(0u64, |sum, &n| sum.saturating_add(n))
+    }
+}
+
+/// Per-block limits from `bouncer_config.block_max_capacity` in the node's chain config
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BouncerCapacity {
+    pub l1_gas: Option<u64>,
+    pub message_segment_length: Option<u64>,
+    pub n_events: Option<u64>,
+    pub state_diff_size: Option<u64>,
+    pub sierra_gas: Option<u64>,
+    pub n_txs: Option<u64>,
+    pub proving_gas: Option<u64>,
+}
+
+/// Weights of a block range
+#[derive(Debug, Clone, Default)]
+pub struct BouncerWeightsRange {
+    /// Ascending by block number; blocks without an entry are left out
+    pub blocks: Vec<BouncerWeights>,
+    pub undecodable_blocks: u64,
+    /// `None` when the chain config is not stored in `meta` or fails to decode
+    pub block_max_capacity: Option<BouncerCapacity>,
+    /// Why the stored chain config could not be read
+    pub block_max_capacity_error: Option<String>,
+}
+
+// Raw deserialization types for block_bouncer_weight
+
+/// Blockifier 0.14 `BouncerWeights`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawBouncerWeights {
+    pub l1_gas: u64,
+    pub message_segment_length: u64,
+    pub n_events: u64,
+    pub state_diff_size: u64,
+    pub sierra_gas: u64,
+    pub n_txs: u64,
+    pub proving_gas: u64,
+}
+
+/// Blockifier 0.13 `BouncerWeights`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawLegacyBouncerWeights {
+    pub builtin_count: BuiltinCount,
+    pub gas: u64,
+    pub message_segment_length: u64,
+    pub n_events: u64,
+    pub n_steps: u64,
+    pub state_diff_size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum RawBlockBouncerWeights {
+    Current(RawBouncerWeights),
+    Legacy(RawLegacyBouncerWeights),
+}
+
+/// Decode a `block_bouncer_weight` value. The 0.14 layout is 7 varints and the legacy one
+/// 15, so a value never decodes as both.
+pub(crate) fn decode_bouncer_weights(
+    key: &[u8],
+    value: &[u8],
+) -> Result<RawBlockBouncerWeights, DecodeError> {
+    decode_value("block_bouncer_weight", key, value)
+        .map(RawBlockBouncerWeights::Current)
+        .or_else(|e| {
+            decode_value("block_bouncer_weight", key, value)
+                .map(RawBlockBouncerWeights::Legacy)
+                .map_err(|_| e)
+        })
+}
+
+impl RawBlockBouncerWeights {
+    fn to_weights(&self, block_number: u64) -> BouncerWeights {
+        match self {
+            RawBlockBouncerWeights::Current(w) => BouncerWeights {
+                block_number,
+                l1_gas: w.l1_gas,
+                message_segment_length: w.message_segment_length,
+                n_events: w.n_events,
+                state_diff_size: w.state_diff_size,
+                sierra_gas: Some(w.sierra_gas),
+                n_txs: Some(w.n_txs),
+                proving_gas: Some(w.proving_gas),
+                n_steps: None,
+                builtins: None,
+            },
+            RawBlockBouncerWeights::Legacy(w) => BouncerWeights {
+                block_number,
+                l1_gas: w.gas,
+                message_segment_length: w.message_segment_length,
+                n_events: w.n_events,
+                state_diff_size: w.state_diff_size,
+                sierra_gas: None,
+                n_txs: None,
+                proving_gas: None,
+                n_steps: Some(w.n_steps),
+                builtins: Some(w.builtin_count),
+            },
+        }
+    }
+}
+
+/// Read `bouncer_config.block_max_capacity` out of the chain config YAML
+fn parse_block_max_capacity(chain_config: &str) -> Option<BouncerCapacity> {
+    #[derive(Deserialize)]
+    struct ChainConfig {
+        bouncer_config: BouncerConfig,
+    }
+    #[derive(Deserialize)]
+    struct BouncerConfig {
+        block_max_capacity: BouncerCapacity,
+    }
+
+    serde_yaml::from_str::<ChainConfig>(chain_config)
+        .ok()
+        .map(|c| c.bouncer_config.block_max_capacity)
+}
+
+impl DbReader {
+    /// Get the bouncer weights of one block
+    pub fn get_bouncer_weights(&self, block_n: u64) -> Result<Option<BouncerWeights>, DbError> {
+        let Some(cf) = self.db.cf_handle("block_bouncer_weight") else {
+            return Ok(None);
+        };
+        let Ok(block_n_u32) = u32::try_from(block_n) else {
+            return Ok(None);
+        };
+        let key = block_n_u32.to_be_bytes();
+        let Some(value) = self.db.get_cf(&cf, key)? else {
+            return Ok(None);
+        };
+
+        let raw = self.decoder.decode_bouncer_weights(&key, &value)?;
+        Ok(Some(raw.to_weights(block_n)))
+    }
+
+    /// Get the bouncer weights of blocks `from..=to`, at most
+    /// [`MAX_BOUNCER_WEIGHTS_RANGE`] of them, with the per-block limits when known
+    pub fn list_bouncer_weights(&self, from: u64, to: u64) -> Result<BouncerWeightsRange, DbError> {
+        if from > to {
+            return Err(DbError::InvalidInput(format!(
+                "Block range {from}..={to} is empty"
+            )));
+        }
+        let mut range = BouncerWeightsRange::default();
+        // The weights are still useful without the limits
+        match self.get_block_max_capacity() {
+            Ok(capacity) => range.block_max_capacity = capacity,
+            Err(e) => range.block_max_capacity_error = Some(e.to_string()),
+        }
+        let Some(cf) = self.db.cf_handle("block_bouncer_weight") else {
+            return Ok(range);
+        };
+        let (Ok(from), Ok(to)) = (u32::try_from(from), u32::try_from(to)) else {
+            return Ok(range);
+        };
+        let to = to.min(from.saturating_add(MAX_BOUNCER_WEIGHTS_RANGE as u32 - 1));
+
+        let start = from.to_be_bytes();
+        for item in self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&start, Direction::Forward))
+        {
+            let (key, value) = item?;
+            let Ok(key_bytes) = <[u8; 4]>::try_from(&key[..]) else {
+                continue;
+            };
+            let block_n = u32::from_be_bytes(key_bytes);
+            if block_n > to {
+                break;
+            }
+            match self.decoder.decode_bouncer_weights(&key, &value) {
+                Ok(raw) => range.blocks.push(raw.to_weights(u64::from(block_n))),
+                Err(_) => range.undecodable_blocks += 1,
+            }
+        }
+        Ok(range)
+    }
+
+    /// Per-block limits from the chain config saved in `RUNTIME_EXEC_CONFIG`
+    pub fn get_block_max_capacity(&self) -> Result<Option<BouncerCapacity>, DbError> {
+        let Some(cf) = self.db.cf_handle("meta") else {
+            return Ok(None);
+        };
+        let Some(value) = self.db.get_cf(&cf, META_RUNTIME_EXEC_CONFIG)? else {
+            return Ok(None);
+        };
+        let RawMetaValue::RuntimeExecConfig(config) = self
+            .decoder
+            .decode_meta(META_RUNTIME_EXEC_CONFIG.as_bytes(), &value)?
+        else {
+            return Ok(None);
+        };
+        Ok(config
+            .chain_config
+            .as_deref()
+            .and_then(parse_block_max_capacity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::open_db;
+    use bincode::Options;
+
+    #[test]
+    fn decodes_both_bouncer_weight_layouts() {
+        // 0.14: state_diff_size 12, sierra_gas 39669403, n_txs 3, proving_gas 48279350
+        let current = hex::decode("0000000cfc9b4e5d0203fc36afe002").unwrap();
+        let weights = decode_bouncer_weights(&[0, 0, 0, 184], &current)
+            .unwrap()
+            .to_weights(184);
+        assert_eq!(weights.state_diff_size, 12);
+        assert_eq!(weights.sierra_gas, Some(39_669_403));
+        assert_eq!(weights.n_txs, Some(3));
+        assert_eq!(weights.proving_gas, Some(48_279_350));
+        assert!(weights.n_steps.is_none());
+
+        let legacy = bincode::DefaultOptions::new()
+            .serialize(&(
+                [0u64, 2, 0, 0, 0, 0, 5, 1, 40, 0],
+                1000u64,
+                0u64,
+                3u64,
+                5000u64,
+                20u64,
+            ))
+            .unwrap();
+        let weights = decode_bouncer_weights(&[0, 0, 0, 1], &legacy)
+            .unwrap()
+            .to_weights(1);
+        assert_eq!(weights.l1_gas, 1000);
+        assert_eq!(weights.n_steps, Some(5000));
+        assert_eq!(weights.builtins.unwrap().total(), 48);
+        assert!(weights.sierra_gas.is_none());
+
+        assert!(decode_bouncer_weights(&[0, 0, 0, 2], &[0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn reads_block_max_capacity_from_chain_config() {
+        let yaml = "chain_name: Madara\nbouncer_config:\n  block_max_capacity:\n    l1_gas: 2500000\n    n_txs: 600\n  builtin_weights:\n    blake_weight: 3750\n";
+        let capacity = parse_block_max_capacity(yaml).unwrap();
+        assert_eq!(capacity.l1_gas, Some(2_500_000));
+        assert_eq!(capacity.n_txs, Some(600));
+        assert!(capacity.sierra_gas.is_none());
+        assert!(parse_block_max_capacity("chain_name: Madara\n").is_none());
+    }
+
+    #[test]
+    fn lists_weights_when_the_chain_config_does_not_decode() {
+        let (_dir, reader) = open_db(&[
+            (
+                "block_bouncer_weight",
+                1u32.to_be_bytes().to_vec(),
+                hex::decode("0000000cfc9b4e5d0203fc36afe002").unwrap(),
+            ),
+            ("meta", META_RUNTIME_EXEC_CONFIG.as_bytes().to_vec(), vec![]),
+        ]);
+
+        let range = reader.list_bouncer_weights(0, 5).unwrap();
+        assert_eq!(range.blocks.len(), 1);
+        assert_eq!(range.blocks[0].n_txs, Some(3));
+        assert!(range.block_max_capacity.is_none());
+        assert!(range
+            .block_max_capacity_error
+            .unwrap()
+            .starts_with("meta value"));
+    }
+}
@@ -0,0 +1,336 @@
+//! Compiled class (CASM) reading from `class_compiled`
+
+use crate::blocks::Felt;
+use crate::contracts::{parse_felt_hex, ClassDefinition, ClassType};
+use crate::{DbError, DbReader};
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+/// CASM entry point
+#[derive(Debug, Clone)]
+pub struct CasmEntryPoint {
+    pub selector: String,
+    /// Bytecode offset of the entry point
+    pub offset: u64,
+    /// Builtins the entry point expects, in order
+    pub builtins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CasmEntryPointsByType {
+    pub constructor: Vec<CasmEntryPoint>,
+    pub external: Vec<CasmEntryPoint>,
+    pub l1_handler: Vec<CasmEntryPoint>,
+}
+
+/// Hints attached to one program counter
+#[derive(Debug, Clone)]
+pub struct CasmHint {
+    pub pc: u64,
+    /// Each hint as stored by the Sierra compiler, e.g. `{"AllocSegment": {...}}`
+    pub hints: Vec<serde_json::Value>,
+}
+
+/// Decoded `class_compiled` value
+#[derive(Debug, Clone)]
+pub struct CompiledClass {
+    pub compiled_class_hash: String,
+    /// Block at which the compiled class was stored
+    pub block_number: u64,
+    pub compiler_version: String,
+    pub prime: String,
+    /// Number of bytecode felts
+    pub bytecode_length: usize,
+    pub entry_points: CasmEntryPointsByType,
+    pub hints: Vec<CasmHint>,
+}
+
+impl CompiledClass {
+    /// Total number of hints over every pc
+    pub fn hints_count(&self) -> usize {
+        self.hints.iter().map(|h| h.hints.len()).sum()
+    }
+}
+
+/// Stored compiled class hash of a Sierra class checked against its declaration
+#[derive(Debug, Clone)]
+pub struct CompiledClassHashCheck {
+    /// Block whose state diff declared the class
+    pub declared_at: u64,
+    /// Compiled class hash the declaring state diff recorded, if it lists the class
+    pub state_diff_compiled_class_hash: Option<String>,
+    /// Whether the state diff value equals either stored compiled class hash
+    pub matches: bool,
+}
+
+// Raw deserialization types for class_compiled
+// CompiledSierraWithBlockN { block_number, compiled_sierra: CompiledSierra(String) }, where the
+// string is the `CasmContractClass` JSON
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawCompiledSierraWithBlockN {
+    pub block_number: u64,
+    pub compiled_sierra: String,
+}
+
+#[derive(Deserialize)]
+struct RawCasmClass {
+    prime: String,
+    compiler_version: String,
+    bytecode: Vec<IgnoredAny>,
+    hints: Vec<(u64, Vec<serde_json::Value>)>,
+    entry_points_by_type: RawCasmEntryPointsByType,
+}
+
+#[derive(Deserialize)]
+struct RawCasmEntryPointsByType {
+    #[serde(rename = "CONSTRUCTOR")]
+    constructor: Vec<RawCasmEntryPoint>,
+    #[serde(rename = "EXTERNAL")]
+    external: Vec<RawCasmEntryPoint>,
+    #[serde(rename = "L1_HANDLER")]
+    l1_handler: Vec<RawCasmEntryPoint>,
+}
+
+#[derive(Deserialize)]
+struct RawCasmEntryPoint {
+    selector: String,
+    offset: u64,
+    builtins: Vec<String>,
+}
+
+impl RawCasmEntryPoint {
+    fn into_entry_point(self) -> CasmEntryPoint {
+        // Selectors are hex strings; normalize them like every other felt we return
+        let selector = parse_felt_hex(&self.selector)
+            .map(|bytes| Felt(bytes).to_hex())
+            .unwrap_or(self.selector);
+        CasmEntryPoint {
+            selector,
+            offset: self.offset,
+            builtins: self.builtins,
+        }
+    }
+}
+
+fn parse_casm(
+    compiled_class_hash: String,
+    raw: RawCompiledSierraWithBlockN,
+) -> Result<CompiledClass, DbError> {
+    let casm: RawCasmClass = serde_json::from_str(&raw.compiled_sierra).map_err(|e| {
+        DbError::Deserialize(format!(
+            "Compiled class {compiled_class_hash} is not valid CASM JSON: {e}"
+        ))
+    })?;
+    let entry_points = |eps: Vec<RawCasmEntryPoint>| {
+        eps.into_iter()
+            .map(RawCasmEntryPoint::into_entry_point)
+            .collect()
+    };
+
+    Ok(CompiledClass {
+        compiled_class_hash,
+        block_number: raw.block_number,
+        compiler_version: casm.compiler_version,
+        prime: casm.prime,
+        bytecode_length: casm.bytecode.len(),
+        entry_points: CasmEntryPointsByType {
+            constructor: entry_points(casm.entry_points_by_type.constructor),
+            external: entry_points(casm.entry_points_by_type.external),
+            l1_handler: entry_points(casm.entry_points_by_type.l1_handler),
+        },
+        hints: casm
+            .hints
+            .into_iter()
+            .map(|(pc, hints)| CasmHint { pc, hints })
+            .collect(),
+    })
+}
+
+impl DbReader {
+    /// Get a compiled class by its compiled class hash
+    pub fn get_compiled_class(
+        &self,
+        compiled_class_hash: &str,
+    ) -> Result<Option<CompiledClass>, DbError> {
+        let Some(key) = parse_felt_hex(compiled_class_hash) else {
+            return Ok(None);
+        };
+        let Some(cf) = self.db.cf_handle("class_compiled") else {
+            return Ok(None);
+        };
+        let Some(value) = self.db.get_cf(&cf, key)? else {
+            return Ok(None);
+        };
+
+        let raw = self.decoder.decode_compiled_class(&key, &value)?;
+        parse_casm(Felt(key).to_hex(), raw).map(Some)
+    }
+
+    /// Get the compiled class of a Sierra class.
+    ///
+    /// `class_compiled` is keyed by either compiled class hash, so the SNIP-34 hash is tried
+    /// first and the Poseidon hash second.
+    pub fn get_class_compiled(
+        &self,
+        class: &ClassDefinition,
+    ) -> Result<Option<CompiledClass>, DbError> {
+        for hash in [&class.compiled_class_hash_v2, &class.compiled_class_hash]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(compiled) = self.get_compiled_class(hash)? {
+                return Ok(Some(compiled));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Compare the compiled class hash stored with a Sierra class against the one its
+    /// declaring block's state diff recorded. Returns `None` for legacy classes.
+    ///
+    /// A class migrated to the SNIP-34 hash keeps the hash it was declared with in
+    /// `compiled_class_hash`, so either stored hash may match.
+    pub fn check_compiled_class_hash(
+        &self,
+        class: &ClassDefinition,
+    ) -> Result<Option<CompiledClassHashCheck>, DbError> {
+        if class.class_type != ClassType::Sierra {
+            return Ok(None);
+        }
+        let class_hash = parse_felt_hex(&class.class_hash).map(|bytes| Felt(bytes).to_hex());
+
+        let declared = self
+            .get_state_diff(class.declared_at)?
+            .and_then(|diff| {
+                diff.declared_classes
+                    .into_iter()
+                    .find(|d| Some(&d.class_hash) == class_hash.as_ref())
+            })
+            .map(|d| d.compiled_class_hash);
+
+        let matches = declared.as_ref().is_some_and(|declared| {
+            [&class.compiled_class_hash, &class.compiled_class_hash_v2]
+                .into_iter()
+                .flatten()
+                .any(|stored| stored == declared)
+        });
+
+        Ok(Some(CompiledClassHashCheck {
+            declared_at: class.declared_at,
+            state_diff_compiled_class_hash: declared,
+            matches,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::open_db;
+    use bincode::Options;
+    use serde_bytes::ByteBuf;
+
+    fn felt(last: u8) -> [u8; 32] {
+        let mut felt = [0u8; 32];
+        felt[31] = last;
+        felt
+    }
+
+    // Sierra class declared at `block` with only a SNIP-34 compiled class hash
+    fn sierra_class(block: u64, compiled_class_hash_v2: u8) -> Vec<u8> {
+        let no_entry_points = Vec::<(ByteBuf, u64)>::new;
+        bincode::DefaultOptions::new()
+            .serialize(&(
+                block,
+                0u32,
+                (
+                    Vec::<ByteBuf>::new(),
+                    "0.1.0",
+                    (no_entry_points(), no_entry_points(), no_entry_points()),
+                    "[]",
+                ),
+                None::<ByteBuf>,
+                Some(ByteBuf::from(vec![compiled_class_hash_v2])),
+            ))
+            .unwrap()
+    }
+
+    #[test]
+    fn decodes_casm_and_checks_declared_compiled_class_hash() {
+        let casm = r#"{
+            "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+            "compiler_version": "2.11.4",
+            "bytecode": ["0xa0680017fff8000", "0x7", "0x482680017ffa8000"],
+            "bytecode_segment_lengths": 3,
+            "hints": [[0, [{"AllocSegment": {"dst": {"register": "AP", "offset": 0}}}]], [2, [{}, {}]]],
+            "entry_points_by_type": {
+                "EXTERNAL": [{"selector": "0x0083afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e", "offset": 0, "builtins": ["range_check"]}],
+                "L1_HANDLER": [],
+                "CONSTRUCTOR": [{"selector": "0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194", "offset": 2, "builtins": []}]
+            }
+        }"#;
+        let opts = bincode::DefaultOptions::new();
+        let no_pairs = Vec::<(ByteBuf, ByteBuf)>::new;
+        // Block 3 declares class 0x1 with compiled class hash 0xcc and class 0x2 with 0xdd
+        let state_diff = opts
+            .serialize(&(
+                Vec::<(ByteBuf, Vec<(ByteBuf, ByteBuf)>)>::new(),
+                Vec::<ByteBuf>::new(),
+                vec![
+                    (ByteBuf::from(vec![1]), ByteBuf::from(vec![0xcc])),
+                    (ByteBuf::from(vec![2]), ByteBuf::from(vec![0xdd])),
+                ],
+                no_pairs(),
+                no_pairs(),
+                no_pairs(),
+                no_pairs(),
+            ))
+            .unwrap();
+
+        let (_dir, reader) = open_db(&[
+            ("class_info", felt(1).to_vec(), sierra_class(3, 0xcc)),
+            ("class_info", felt(2).to_vec(), sierra_class(3, 0xee)),
+            (
+                "class_compiled",
+                felt(0xcc).to_vec(),
+                opts.serialize(&(3u64, casm)).unwrap(),
+            ),
+            ("block_state_diff", 3u32.to_be_bytes().to_vec(), state_diff),
+        ]);
+
+        let class = reader.get_class_definition("0x1").unwrap().unwrap();
+        let compiled = reader.get_class_compiled(&class).unwrap().unwrap();
+        assert_eq!(compiled.compiled_class_hash, "0xcc");
+        assert_eq!(compiled.block_number, 3);
+        assert_eq!(compiled.compiler_version, "2.11.4");
+        assert_eq!(compiled.bytecode_length, 3);
+        assert_eq!(compiled.hints.len(), 2);
+        assert_eq!(compiled.hints_count(), 3);
+        let external = &compiled.entry_points.external[0];
+        assert_eq!(
+            external.selector,
+            "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+        );
+        assert_eq!(external.builtins, ["range_check"]);
+        assert_eq!(compiled.entry_points.constructor[0].offset, 2);
+
+        let check = reader.check_compiled_class_hash(&class).unwrap().unwrap();
+        assert_eq!(check.declared_at, 3);
+        assert_eq!(
+            check.state_diff_compiled_class_hash.as_deref(),
+            Some("0xcc")
+        );
+        assert!(check.matches);
+
+        // Stored as 0xee but declared as 0xdd, and nothing compiled under either hash
+        let class = reader.get_class_definition("0x2").unwrap().unwrap();
+        let check = reader.check_compiled_class_hash(&class).unwrap().unwrap();
+        assert_eq!(
+            check.state_diff_compiled_class_hash.as_deref(),
+            Some("0xdd")
+        );
+        assert!(!check.matches);
+        assert!(reader.get_class_compiled(&class).unwrap().is_none());
+    }
+}
@@ -1,10 +1,14 @@
 //! Contract and class reading functionality
 
 use crate::blocks::Felt;
-use crate::DbReader;
-use rocksdb::IteratorMode;
+use crate::{DbError, DbReader};
+use flate2::read::GzDecoder;
+use rocksdb::{Direction, IteratorMode};
+use serde::de::IgnoredAny;
 use serde::Deserialize;
 use serde_bytes::ByteBuf;
+use std::collections::HashMap;
+use std::io::Read;
 
 /// Contract information
 #[derive(Debug, Clone)]
@@ -19,6 +23,22 @@ pub struct ContractInfo {
 pub struct StorageEntry {
     pub key: String,
     pub value: String,
+    /// Block at which this value was written (decoded from the reversed block suffix)
+    pub block_number: Option<u64>,
+}
+
+/// One historical version of a storage slot
+#[derive(Debug, Clone)]
+pub struct StorageVersion {
+    pub block_number: u64,
+    pub value: String,
+}
+
+/// Class hash of a contract starting at a given block
+#[derive(Debug, Clone)]
+pub struct ClassHashVersion {
+    pub block_number: u64,
+    pub class_hash: String,
 }
 
 /// Class type enum
@@ -44,28 +64,97 @@ impl std::fmt::Display for ClassType {
 pub struct ClassInfo {
     pub class_hash: String,
     pub class_type: ClassType,
+    /// Compiled class hash the class trie commits to: the SNIP-34 hash when present
     pub compiled_class_hash: Option<String>,
 }
 
+/// Contract entry point
+#[derive(Debug, Clone)]
+pub struct EntryPoint {
+    pub selector: String,
+    /// Sierra function index
+    pub function_idx: Option<u64>,
+    /// Legacy bytecode offset
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EntryPointsByType {
+    pub constructor: Vec<EntryPoint>,
+    pub external: Vec<EntryPoint>,
+    pub l1_handler: Vec<EntryPoint>,
+}
+
+/// Fully decoded `class_info` value
+#[derive(Debug, Clone)]
+pub struct ClassDefinition {
+    pub class_hash: String,
+    pub class_type: ClassType,
+    /// Block that declared the class
+    pub declared_at: u64,
+    /// Sierra `contract_class_version`, e.g. "0.1.0"
+    pub contract_class_version: Option<String>,
+    /// Sierra version from the program header, e.g. "1.7.0"
+    pub sierra_version: Option<String>,
+    /// Compiler version from the program header, e.g. "2.11.4"
+    pub compiler_version: Option<String>,
+    /// Sierra program length in felts, or the compressed legacy program size in bytes
+    pub program_length: usize,
+    pub entry_points: EntryPointsByType,
+    /// ABI as JSON; legacy ABIs are re-encoded in the Starknet JSON shape
+    pub abi: Option<String>,
+    /// Poseidon hash of the compiled class
+    pub compiled_class_hash: Option<String>,
+    /// SNIP-34 BLAKE hash of the compiled class
+    pub compiled_class_hash_v2: Option<String>,
+    /// Summary of the decompressed Cairo 0 program, for legacy classes
+    pub legacy_program: Option<LegacyProgram>,
+    /// Why a legacy program could not be summarized; the rest of the definition still is
+    pub legacy_program_error: Option<String>,
+}
+
+/// Summary of a decompressed legacy (Cairo 0) program
+#[derive(Debug, Clone)]
+pub struct LegacyProgram {
+    pub builtins: Vec<String>,
+    pub prime: String,
+    /// Cairo compiler version, absent from classes compiled before 0.10
+    pub compiler_version: Option<String>,
+    pub main_scope: Option<String>,
+    /// Number of bytecode words
+    pub data_length: usize,
+    /// Number of hints, summed over every pc that has any
+    pub hints_count: usize,
+    pub identifiers_count: usize,
+    /// Size of the decompressed program JSON in bytes
+    pub decompressed_size: usize,
+}
+
 // Raw deserialization types for class_info
-// ClassInfo in madara has the structure with class type enum
+// ClassInfoWithBlockN { block_number, class_info: ClassInfo }, where ClassInfo is an enum of
+// Sierra and Legacy class info
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawClassInfo {
-    // The format is: enum variant (CompiledClass) + fields
-    // CompiledClass { class: ContractClass, compiled_class_hash: Option<Felt> }
-    pub class: RawContractClass,
-    pub compiled_class_hash: Option<ByteBuf>,
+pub(crate) struct RawClassInfoWithBlockN {
+    pub block_number: u64,
+    pub class_info: RawClassInfo,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-enum RawContractClass {
-    Sierra(RawFlattenedSierraClass),
+pub(crate) enum RawClassInfo {
+    Sierra(RawSierraClassInfo),
     Legacy(RawCompressedLegacyContractClass),
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawFlattenedSierraClass {
+pub(crate) struct RawSierraClassInfo {
+    pub contract_class: RawFlattenedSierraClass,
+    pub compiled_class_hash: Option<ByteBuf>,
+    pub compiled_class_hash_v2: Option<ByteBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawFlattenedSierraClass {
     pub sierra_program: Vec<ByteBuf>,
     pub contract_class_version: String,
     pub entry_points_by_type: RawEntryPointsByType,
@@ -73,155 +162,553 @@ struct RawFlattenedSierraClass {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawEntryPointsByType {
+pub(crate) struct RawEntryPointsByType {
     pub constructor: Vec<RawSierraEntryPoint>,
     pub external: Vec<RawSierraEntryPoint>,
     pub l1_handler: Vec<RawSierraEntryPoint>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawSierraEntryPoint {
+pub(crate) struct RawSierraEntryPoint {
     pub selector: ByteBuf,
     pub function_idx: u64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawCompressedLegacyContractClass {
-    // Legacy class is compressed, we don't need to fully parse it
-    pub program: Vec<u8>,
+pub(crate) struct RawCompressedLegacyContractClass {
+    /// Gzip-compressed program JSON
+    pub program: ByteBuf,
     pub entry_points_by_type: RawLegacyEntryPointsByType,
     pub abi: Option<Vec<RawAbiEntry>>,
 }
 
+// Only the fields summarized by `LegacyProgram`; the program JSON carries many more
+#[derive(Deserialize)]
+struct RawLegacyProgram {
+    builtins: Vec<String>,
+    prime: String,
+    compiler_version: Option<String>,
+    main_scope: Option<String>,
+    data: Vec<IgnoredAny>,
+    hints: HashMap<String, Vec<IgnoredAny>>,
+    identifiers: HashMap<String, IgnoredAny>,
+}
+
+impl RawCompressedLegacyContractClass {
+    /// Gunzip the program JSON
+    fn decompress_program(&self) -> std::io::Result<Vec<u8>> {
+        let mut program = Vec::new();
+        GzDecoder::new(self.program.as_slice()).read_to_end(&mut program)?;
+        Ok(program)
+    }
+}
+
+fn summarize_legacy_program(program: &[u8]) -> serde_json::Result<LegacyProgram> {
+    let raw: RawLegacyProgram = serde_json::from_slice(program)?;
+    Ok(LegacyProgram {
+        builtins: raw.builtins,
+        prime: raw.prime,
+        compiler_version: raw.compiler_version,
+        main_scope: raw.main_scope,
+        data_length: raw.data.len(),
+        hints_count: raw.hints.values().map(Vec::len).sum(),
+        identifiers_count: raw.identifiers.len(),
+        decompressed_size: program.len(),
+    })
+}
+
 #[derive(Debug, Clone, Deserialize)]
-struct RawLegacyEntryPointsByType {
+pub(crate) struct RawLegacyEntryPointsByType {
     pub constructor: Vec<RawLegacyEntryPoint>,
     pub external: Vec<RawLegacyEntryPoint>,
     pub l1_handler: Vec<RawLegacyEntryPoint>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawLegacyEntryPoint {
+pub(crate) struct RawLegacyEntryPoint {
     pub offset: u64,
     pub selector: ByteBuf,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
-enum RawAbiEntry {
+pub(crate) enum RawAbiEntry {
     Function(RawAbiFunctionEntry),
     Event(RawAbiEventEntry),
     Struct(RawAbiStructEntry),
 }
 
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub(crate) enum RawAbiFunctionType {
+    Function,
+    L1Handler,
+    Constructor,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub(crate) enum RawAbiStateMutability {
+    View,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub(crate) enum RawAbiEventType {
+    Event,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub(crate) enum RawAbiStructType {
+    Struct,
+}
+
 #[derive(Debug, Clone, Deserialize)]
-struct RawAbiFunctionEntry {
-    pub r#type: String,
+pub(crate) struct RawAbiFunctionEntry {
+    pub r#type: RawAbiFunctionType,
     pub name: String,
     pub inputs: Vec<RawAbiInput>,
-    pub outputs: Vec<RawAbiOutput>,
+    pub outputs: Vec<RawAbiInput>,
+    pub state_mutability: Option<RawAbiStateMutability>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawAbiEventEntry {
-    pub r#type: String,
+pub(crate) struct RawAbiEventEntry {
+    pub r#type: RawAbiEventType,
     pub name: String,
-    pub data: Vec<RawAbiInput>,
     pub keys: Vec<RawAbiInput>,
+    pub data: Vec<RawAbiInput>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawAbiStructEntry {
-    pub r#type: String,
+pub(crate) struct RawAbiStructEntry {
+    pub r#type: RawAbiStructType,
     pub name: String,
     pub size: u64,
     pub members: Vec<RawAbiMember>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawAbiInput {
+pub(crate) struct RawAbiInput {
     pub name: String,
     pub r#type: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawAbiOutput {
-    pub r#type: String,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-struct RawAbiMember {
+pub(crate) struct RawAbiMember {
     pub name: String,
     pub r#type: String,
     pub offset: u64,
 }
 
+impl RawAbiEntry {
+    /// The entry in the Starknet legacy ABI JSON shape
+    fn to_json(&self) -> serde_json::Value {
+        let params = |params: &[RawAbiInput]| {
+            params
+                .iter()
+                .map(|p| serde_json::json!({ "name": p.name, "type": p.r#type }))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            RawAbiEntry::Function(f) => {
+                let r#type = match f.r#type {
+                    RawAbiFunctionType::Function => "function",
+                    RawAbiFunctionType::L1Handler => "l1_handler",
+                    RawAbiFunctionType::Constructor => "constructor",
+                };
+                let mut entry = serde_json::json!({
+                    "type": r#type,
+                    "name": f.name,
+                    "inputs": params(&f.inputs),
+                    "outputs": params(&f.outputs),
+                });
+                if let Some(RawAbiStateMutability::View) = f.state_mutability {
+                    entry["stateMutability"] = "view".into();
+                }
+                entry
+            }
+            RawAbiEntry::Event(e) => serde_json::json!({
+                "type": match e.r#type {
+                    RawAbiEventType::Event => "event",
+                },
+                "name": e.name,
+                "keys": params(&e.keys),
+                "data": params(&e.data),
+            }),
+            RawAbiEntry::Struct(s) => serde_json::json!({
+                "type": match s.r#type {
+                    RawAbiStructType::Struct => "struct",
+                },
+                "name": s.name,
+                "size": s.size,
+                "members": s
+                    .members
+                    .iter()
+                    .map(|m| serde_json::json!({ "name": m.name, "type": m.r#type, "offset": m.offset }))
+                    .collect::<Vec<_>>(),
+            }),
+        }
+    }
+}
+
+/// `major.minor.patch` from three header felts of a Sierra program
+fn sierra_header_version(felts: &[ByteBuf]) -> Option<String> {
+    let parts = felts
+        .iter()
+        .map(|f| {
+            let felt = Felt::from_bytes(f);
+            (felt.0[..28].iter().all(|&b| b == 0))
+                .then(|| u32::from_be_bytes(felt.0[28..].try_into().unwrap()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    match parts[..] {
+        [major, minor, patch] => Some(format!("{major}.{minor}.{patch}")),
+        _ => None,
+    }
+}
+
+impl RawClassInfoWithBlockN {
+    /// JSON ABI; legacy ABIs are re-encoded from their bincode entries
+    fn abi(&self) -> Option<String> {
+        match &self.class_info {
+            RawClassInfo::Sierra(info) => Some(info.contract_class.abi.clone()),
+            RawClassInfo::Legacy(class) => class.abi.as_ref().map(|abi| {
+                serde_json::Value::Array(abi.iter().map(RawAbiEntry::to_json).collect()).to_string()
+            }),
+        }
+    }
+
+    fn to_definition(&self, class_hash: String) -> ClassDefinition {
+        let hex = |bytes: &ByteBuf| Felt::from_bytes(bytes).to_hex();
+        match &self.class_info {
+            RawClassInfo::Sierra(info) => {
+                let class = &info.contract_class;
+                let entry_points = |eps: &[RawSierraEntryPoint]| {
+                    eps.iter()
+                        .map(|ep| EntryPoint {
+                            selector: hex(&ep.selector),
+                            function_idx: Some(ep.function_idx),
+                            offset: None,
+                        })
+                        .collect()
+                };
+                // The program starts with the Sierra version then the compiler version
+                let program = &class.sierra_program;
+                ClassDefinition {
+                    class_hash,
+                    class_type: ClassType::Sierra,
+                    declared_at: self.block_number,
+                    contract_class_version: Some(class.contract_class_version.clone()),
+                    sierra_version: program.get(..3).and_then(sierra_header_version),
+                    compiler_version: program.get(3..6).and_then(sierra_header_version),
+                    program_length: program.len(),
+                    entry_points: EntryPointsByType {
+                        constructor: entry_points(&class.entry_points_by_type.constructor),
+                        external: entry_points(&class.entry_points_by_type.external),
+                        l1_handler: entry_points(&class.entry_points_by_type.l1_handler),
+                    },
+                    abi: self.abi(),
+                    compiled_class_hash: info.compiled_class_hash.as_ref().map(hex),
+                    compiled_class_hash_v2: info.compiled_class_hash_v2.as_ref().map(hex),
+                    legacy_program: None,
+                    legacy_program_error: None,
+                }
+            }
+            RawClassInfo::Legacy(class) => {
+                let entry_points = |eps: &[RawLegacyEntryPoint]| {
+                    eps.iter()
+                        .map(|ep| EntryPoint {
+                            selector: hex(&ep.selector),
+                            function_idx: None,
+                            offset: Some(ep.offset),
+                        })
+                        .collect()
+                };
+                ClassDefinition {
+                    class_hash,
+                    class_type: ClassType::Legacy,
+                    declared_at: self.block_number,
+                    contract_class_version: None,
+                    sierra_version: None,
+                    compiler_version: None,
+                    program_length: class.program.len(),
+                    entry_points: EntryPointsByType {
+                        constructor: entry_points(&class.entry_points_by_type.constructor),
+                        external: entry_points(&class.entry_points_by_type.external),
+                        l1_handler: entry_points(&class.entry_points_by_type.l1_handler),
+                    },
+                    abi: self.abi(),
+                    compiled_class_hash: None,
+                    compiled_class_hash_v2: None,
+                    legacy_program: None,
+                    legacy_program_error: None,
+                }
+            }
+        }
+    }
+}
+
+fn decompress_legacy_program(
+    class: &RawCompressedLegacyContractClass,
+    class_hash: &str,
+) -> Result<Vec<u8>, DbError> {
+    class.decompress_program().map_err(|e| {
+        DbError::Deserialize(format!(
+            "Legacy program of class {class_hash} failed to decompress: {e}"
+        ))
+    })
+}
+
+/// Parse a hex string into a 32-byte big-endian felt, left-padding short values
+pub(crate) fn parse_felt_hex(s: &str) -> Option<[u8; 32]> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    let bytes = if s.len() % 2 == 1 {
+        hex::decode(format!("0{s}")).ok()?
+    } else {
+        hex::decode(s).ok()?
+    };
+    if bytes.len() > 32 {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    Some(out)
+}
+
+/// Encode a block number as the `u32::MAX - block_n` big-endian suffix used by versioned keys
+pub(crate) fn encode_reversed_block(block_n: u64) -> [u8; 4] {
+    let block_n = u32::try_from(block_n).unwrap_or(u32::MAX);
+    (u32::MAX - block_n).to_be_bytes()
+}
+
+/// Decode a `u32::MAX - block_n` big-endian suffix back into a block number
+pub(crate) fn decode_reversed_block(suffix: &[u8]) -> Option<u64> {
+    let bytes: [u8; 4] = suffix.get(..4)?.try_into().ok()?;
+    Some(u64::from(u32::MAX - u32::from_be_bytes(bytes)))
+}
+
+/// Decode a bincode-serialized Felt value into its hex representation
+pub(crate) fn decode_felt_value(value: &[u8]) -> Option<String> {
+    use bincode::Options;
+
+    let bytes: ByteBuf = bincode::DefaultOptions::new().deserialize(value).ok()?;
+    Some(Felt::from_bytes(&bytes).to_hex())
+}
+
 impl DbReader {
     /// Get contract information by address
     pub fn get_contract(&self, address: &str) -> Option<ContractInfo> {
-        // Parse address to bytes
-        let address_str = address.strip_prefix("0x").unwrap_or(address);
-        let address_bytes = hex::decode(address_str).ok()?;
+        self.read_contract(address, None)
+    }
 
-        // Pad to 32 bytes
-        let mut key = [0u8; 32];
-        let len = address_bytes.len().min(32);
-        key[32 - len..].copy_from_slice(&address_bytes[..len]);
+    /// Get contract information (nonce and class hash) in effect at `block_n`
+    pub fn get_contract_at(&self, address: &str, block_n: u64) -> Option<ContractInfo> {
+        self.read_contract(address, Some(block_n))
+    }
 
-        // Get class hash
-        let class_hash = self.get_contract_class_hash(&key);
+    fn read_contract(&self, address: &str, block_n: Option<u64>) -> Option<ContractInfo> {
+        let key = parse_felt_hex(address)?;
 
-        // Get nonce
-        let nonce = self.get_contract_nonce(&key);
+        let class_hash = self.get_contract_class_hash(&key, block_n);
+        let nonce = self.get_contract_nonce(&key, block_n);
 
         Some(ContractInfo {
-            address: format!("0x{}", hex::encode(&key)),
+            address: format!("0x{}", hex::encode(key)),
             class_hash,
             nonce,
         })
     }
 
-    /// Get contract class hash
-    fn get_contract_class_hash(&self, address: &[u8; 32]) -> Option<String> {
-        use bincode::Options;
+    /// Get every class hash a contract has had, newest first (deployment plus upgrades)
+    pub fn get_contract_class_history(&self, address: &str) -> Vec<ClassHashVersion> {
+        let Some(address) = parse_felt_hex(address) else {
+            return vec![];
+        };
+        let cf = match self.db.cf_handle("contract_class_hashes") {
+            Some(cf) => cf,
+            None => return vec![],
+        };
 
-        let cf = self.db.cf_handle("contract_class_hashes")?;
-        let value = self.db.get_cf(&cf, address).ok()??;
+        let mut versions = Vec::new();
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&address, Direction::Forward));
 
-        // Value is a ByteBuf (length-prefixed bytes)
-        let opts = bincode::DefaultOptions::new();
-        let hash: ByteBuf = opts.deserialize(&value).ok()?;
+        for (key, value) in iter.flatten() {
+            if key.len() < 32 || key[..32] != address {
+                break;
+            }
+            if let (Some(block_number), Some(class_hash)) =
+                (decode_reversed_block(&key[32..]), decode_felt_value(&value))
+            {
+                versions.push(ClassHashVersion {
+                    block_number,
+                    class_hash,
+                });
+            }
+        }
 
-        Some(Felt::from_bytes(&hash).to_hex())
+        versions
+    }
+
+    /// Find the newest entry of a versioned `address + reversed block_n` column family that is
+    /// at or before `block_n` (the newest overall when `block_n` is `None`).
+    pub(crate) fn get_versioned_contract_value(
+        &self,
+        cf_name: &str,
+        address: &[u8; 32],
+        block_n: Option<u64>,
+    ) -> Option<Vec<u8>> {
+        let cf = self.db.cf_handle(cf_name)?;
+
+        let mut seek = address.to_vec();
+        if let Some(block_n) = block_n {
+            seek.extend_from_slice(&encode_reversed_block(block_n));
+        }
+
+        let mut iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&seek, Direction::Forward));
+        let (key, value) = iter.next()?.ok()?;
+        if key.len() < 32 || key[..32] != address[..] {
+            return None;
+        }
+
+        Some(value.to_vec())
+    }
+
+    /// Get contract class hash
+    pub(crate) fn get_contract_class_hash(
+        &self,
+        address: &[u8; 32],
+        block_n: Option<u64>,
+    ) -> Option<String> {
+        let value = self.get_versioned_contract_value("contract_class_hashes", address, block_n)?;
+        decode_felt_value(&value)
     }
 
     /// Get contract nonce
-    fn get_contract_nonce(&self, address: &[u8; 32]) -> Option<u64> {
+    pub(crate) fn get_contract_nonce(
+        &self,
+        address: &[u8; 32],
+        block_n: Option<u64>,
+    ) -> Option<u64> {
         use bincode::Options;
 
-        let cf = self.db.cf_handle("contract_nonces")?;
-        let value = self.db.get_cf(&cf, address).ok()??;
+        let value = self.get_versioned_contract_value("contract_nonces", address, block_n)?;
 
-        // Value is a u64 with varint encoding
-        let opts = bincode::DefaultOptions::new();
-        let nonce: u64 = opts.deserialize(&value).ok()?;
-
-        Some(nonce)
+        // Value is a Felt; nonces always fit in the low 8 bytes
+        let bytes: ByteBuf = bincode::DefaultOptions::new().deserialize(&value).ok()?;
+        let felt = Felt::from_bytes(&bytes);
+        if felt.0[..24].iter().any(|&b| b != 0) {
+            return None;
+        }
+        Some(u64::from_be_bytes(felt.0[24..].try_into().ok()?))
     }
 
-    /// Get contract storage entries
+    /// Get the latest value of every storage slot of a contract
     pub fn get_contract_storage(&self, address: &str, limit: usize) -> Vec<StorageEntry> {
-        // Parse address to bytes
-        let address_str = address.strip_prefix("0x").unwrap_or(address);
-        let address_bytes = match hex::decode(address_str) {
-            Ok(b) => b,
-            Err(_) => return vec![],
+        self.iter_contract_storage(address, None, limit)
+    }
+
+    /// Get the value of every storage slot of a contract as of `block_n`
+    pub fn get_contract_storage_at(
+        &self,
+        address: &str,
+        block_n: u64,
+        limit: usize,
+    ) -> Vec<StorageEntry> {
+        self.iter_contract_storage(address, Some(block_n), limit)
+    }
+
+    /// Get the value of a single storage slot as of `block_n`
+    pub fn get_storage_at(&self, address: &str, key: &str, block_n: u64) -> Option<StorageEntry> {
+        let address = parse_felt_hex(address)?;
+        let storage_key = parse_felt_hex(key)?;
+
+        let mut prefix = [0u8; 64];
+        prefix[..32].copy_from_slice(&address);
+        prefix[32..].copy_from_slice(&storage_key);
+
+        // Versions are sorted newest first, so seeking to the reversed target block lands on
+        // the most recent write at or before it.
+        let mut seek = [0u8; 68];
+        seek[..64].copy_from_slice(&prefix);
+        seek[64..].copy_from_slice(&encode_reversed_block(block_n));
+
+        let cf = self.db.cf_handle("contract_storage")?;
+        let mut iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&seek, Direction::Forward));
+        let (found_key, value) = iter.next()?.ok()?;
+        if found_key.len() < 64 || found_key[..64] != prefix {
+            return None;
+        }
+
+        Some(StorageEntry {
+            key: format!("0x{}", hex::encode(storage_key)),
+            value: decode_felt_value(&value)?,
+            block_number: decode_reversed_block(&found_key[64..]),
+        })
+    }
+
+    /// Get every version of a single storage slot, newest first
+    pub fn get_storage_history(
+        &self,
+        address: &str,
+        key: &str,
+        limit: usize,
+    ) -> Vec<StorageVersion> {
+        let (Some(address), Some(storage_key)) = (parse_felt_hex(address), parse_felt_hex(key))
+        else {
+            return vec![];
+        };
+
+        let mut prefix = [0u8; 64];
+        prefix[..32].copy_from_slice(&address);
+        prefix[32..].copy_from_slice(&storage_key);
+
+        let cf = match self.db.cf_handle("contract_storage") {
+            Some(cf) => cf,
+            None => return vec![],
         };
 
-        // Pad to 32 bytes
-        let mut prefix = [0u8; 32];
-        let len = address_bytes.len().min(32);
-        prefix[32 - len..].copy_from_slice(&address_bytes[..len]);
+        let mut versions = Vec::new();
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&prefix, Direction::Forward));
+
+        for (key, value) in iter.flatten() {
+            if key.len() < 64 || key[..64] != prefix {
+                break;
+            }
+            let (Some(block_number), Some(value)) =
+                (decode_reversed_block(&key[64..]), decode_felt_value(&value))
+            else {
+                continue;
+            };
+
+            versions.push(StorageVersion {
+                block_number,
+                value,
+            });
+
+            if versions.len() >= limit {
+                break;
+            }
+        }
+
+        versions
+    }
+
+    /// Iterate the storage slots of a contract, keeping only the newest version of each slot
+    /// that is at or before `block_n` (or the newest overall when `block_n` is `None`).
+    fn iter_contract_storage(
+        &self,
+        address: &str,
+        block_n: Option<u64>,
+        limit: usize,
+    ) -> Vec<StorageEntry> {
+        let Some(prefix) = parse_felt_hex(address) else {
+            return vec![];
+        };
 
         let cf = match self.db.cf_handle("contract_storage") {
             Some(cf) => cf,
@@ -229,40 +716,48 @@ impl DbReader {
         };
 
         let mut entries = Vec::new();
+        let mut resolved_slot: Option<Vec<u8>> = None;
 
-        // Iterate with prefix
-        let iter = self.db.iterator_cf(
-            &cf,
-            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
-        );
+        // Key format: address (32 bytes) + storage_key (32 bytes) + reversed block_n (4 bytes)
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&prefix, Direction::Forward));
 
-        for item in iter {
-            if let Ok((key, value)) = item {
-                // Key format: address (32 bytes) + storage_key (32 bytes)
-                if key.len() < 64 {
-                    continue;
-                }
+        for (key, value) in iter.flatten() {
+            if key.len() < 64 {
+                continue;
+            }
 
-                // Check if still same address
-                if &key[..32] != &prefix[..] {
-                    break;
-                }
+            // Check if still same address
+            if key[..32] != prefix[..] {
+                break;
+            }
 
-                let storage_key = &key[32..64];
+            let storage_key = &key[32..64];
+            if resolved_slot.as_deref() == Some(storage_key) {
+                // An older version of a slot we already reported
+                continue;
+            }
 
-                // Value is a ByteBuf (Felt)
-                use bincode::Options;
-                let opts = bincode::DefaultOptions::new();
-                if let Ok(val_bytes) = opts.deserialize::<ByteBuf>(&value) {
-                    entries.push(StorageEntry {
-                        key: format!("0x{}", hex::encode(storage_key)),
-                        value: Felt::from_bytes(&val_bytes).to_hex(),
-                    });
+            let written_at = decode_reversed_block(&key[64..]);
+            if let (Some(target), Some(written_at)) = (block_n, written_at) {
+                if written_at > target {
+                    // Written after the target block, an older version may follow
+                    continue;
                 }
+            }
+            resolved_slot = Some(storage_key.to_vec());
+
+            if let Some(value) = decode_felt_value(&value) {
+                entries.push(StorageEntry {
+                    key: format!("0x{}", hex::encode(storage_key)),
+                    value,
+                    block_number: written_at,
+                });
+            }
 
-                if entries.len() >= limit {
-                    break;
-                }
+            if entries.len() >= limit {
+                break;
             }
         }
 
@@ -271,41 +766,92 @@ impl DbReader {
 
     /// Get class information by hash
     pub fn get_class(&self, class_hash: &str) -> Option<ClassInfo> {
-        use bincode::Options;
-
-        // Parse class hash to bytes
-        let hash_str = class_hash.strip_prefix("0x").unwrap_or(class_hash);
-        let hash_bytes = hex::decode(hash_str).ok()?;
-
-        // Pad to 32 bytes
-        let mut key = [0u8; 32];
-        let len = hash_bytes.len().min(32);
-        key[32 - len..].copy_from_slice(&hash_bytes[..len]);
-
-        let cf = self.db.cf_handle("class_info")?;
-        let value = self.db.get_cf(&cf, key).ok()??;
-
-        let opts = bincode::DefaultOptions::new();
+        let (key, value) = self.get_class_value(class_hash)?;
+        let class_hash = format!("0x{}", hex::encode(key));
+
+        match self.decoder.decode_class_info(&key, &value) {
+            Ok(raw) => {
+                let definition = raw.to_definition(class_hash);
+                Some(ClassInfo {
+                    class_hash: definition.class_hash,
+                    class_type: definition.class_type,
+                    compiled_class_hash: definition
+                        .compiled_class_hash_v2
+                        .or(definition.compiled_class_hash),
+                })
+            }
+            // The detail endpoint reports the decode error; keep the listing usable
+            Err(_) => Some(ClassInfo {
+                class_hash,
+                class_type: self.decoder.class_type(&value),
+                compiled_class_hash: None,
+            }),
+        }
+    }
 
-        // Try to deserialize - but this might fail due to complex structure
-        // Let's just detect the class type from the first byte (enum variant)
-        if value.is_empty() {
-            return None;
+    /// Decode a class with its entry points and ABI
+    pub fn get_class_definition(
+        &self,
+        class_hash: &str,
+    ) -> Result<Option<ClassDefinition>, DbError> {
+        let Some((key, value)) = self.get_class_value(class_hash) else {
+            return Ok(None);
+        };
+        let raw = self.decoder.decode_class_info(&key, &value)?;
+        let mut definition = raw.to_definition(format!("0x{}", hex::encode(key)));
+        if let RawClassInfo::Legacy(class) = &raw.class_info {
+            let summary = class
+                .decompress_program()
+                .map_err(|e| format!("failed to decompress: {e}"))
+                .and_then(|program| {
+                    summarize_legacy_program(&program).map_err(|e| format!("not valid JSON: {e}"))
+                });
+            match summary {
+                Ok(summary) => definition.legacy_program = Some(summary),
+                Err(e) => definition.legacy_program_error = Some(e),
+            }
         }
+        Ok(Some(definition))
+    }
+
+    /// ABI of a class, without decompressing a legacy program
+    pub(crate) fn get_class_abi(&self, class_hash: &str) -> Result<Option<String>, DbError> {
+        let Some((key, value)) = self.get_class_value(class_hash) else {
+            return Ok(None);
+        };
+        Ok(self.decoder.decode_class_info(&key, &value)?.abi())
+    }
 
-        let class_type = match value[0] {
-            0 => ClassType::Sierra,
-            1 => ClassType::Legacy,
-            _ => ClassType::Unknown,
+    /// Program JSON of a class: the decompressed Cairo 0 program for legacy classes, or the
+    /// Sierra program as an array of felts. Returned with the class hash as stored.
+    pub fn get_class_program(
+        &self,
+        class_hash: &str,
+    ) -> Result<Option<(String, Vec<u8>)>, DbError> {
+        let Some((key, value)) = self.get_class_value(class_hash) else {
+            return Ok(None);
+        };
+        let class_hash = format!("0x{}", hex::encode(key));
+        let program = match self.decoder.decode_class_info(&key, &value)?.class_info {
+            RawClassInfo::Legacy(class) => decompress_legacy_program(&class, &class_hash)?,
+            RawClassInfo::Sierra(info) => {
+                let felts: Vec<String> = info
+                    .contract_class
+                    .sierra_program
+                    .iter()
+                    .map(|felt| Felt::from_bytes(felt).to_hex())
+                    .collect();
+                serde_json::to_vec(&felts).map_err(|e| DbError::Deserialize(e.to_string()))?
+            }
         };
+        Ok(Some((class_hash, program)))
+    }
 
-        // For compiled class hash, we need to try parsing
-        // For now, return basic info
-        Some(ClassInfo {
-            class_hash: format!("0x{}", hex::encode(&key)),
-            class_type,
-            compiled_class_hash: None, // Would need full deserialization
-        })
+    fn get_class_value(&self, class_hash: &str) -> Option<([u8; 32], Vec<u8>)> {
+        let key = parse_felt_hex(class_hash)?;
+        let cf = self.db.cf_handle("class_info")?;
+        let value = self.db.get_cf(&cf, key).ok()??;
+        (!value.is_empty()).then_some((key, value))
     }
 
     /// List contracts (first N contracts from contract_class_hashes)
@@ -316,55 +862,31 @@ impl DbReader {
         };
 
         let mut contracts = Vec::new();
+        let mut last_address: Option<[u8; 32]> = None;
         let iter = self.db.iterator_cf(&cf, IteratorMode::Start);
 
-        for item in iter {
-            if let Ok((key, value)) = item {
-                // Keys could be 32 bytes or have different lengths
-                // Try to extract address and class hash directly
-                if key.len() >= 32 {
-                    // Use first 32 bytes as address
-                    let address = format!("0x{}", hex::encode(&key[..32]));
-
-                    // Try to parse value as class hash
-                    use bincode::Options;
-                    let opts = bincode::DefaultOptions::new();
-                    let class_hash = opts
-                        .deserialize::<serde_bytes::ByteBuf>(&value)
-                        .ok()
-                        .map(|h| Felt::from_bytes(&h).to_hex());
-
-                    // Get nonce
-                    let mut addr_bytes = [0u8; 32];
-                    addr_bytes.copy_from_slice(&key[..32]);
-                    let nonce = self.get_contract_nonce(&addr_bytes);
-
-                    contracts.push(ContractInfo {
-                        address,
-                        class_hash,
-                        nonce,
-                    });
-                } else if !key.is_empty() {
-                    // Shorter key - still try to use it
-                    let address = format!("0x{}", hex::encode(&key));
-
-                    use bincode::Options;
-                    let opts = bincode::DefaultOptions::new();
-                    let class_hash = opts
-                        .deserialize::<serde_bytes::ByteBuf>(&value)
-                        .ok()
-                        .map(|h| Felt::from_bytes(&h).to_hex());
-
-                    contracts.push(ContractInfo {
-                        address,
-                        class_hash,
-                        nonce: None,
-                    });
-                }
+        for (key, value) in iter.flatten() {
+            if key.len() < 32 {
+                continue;
+            }
 
-                if contracts.len() >= limit {
-                    break;
-                }
+            // Keys are address + reversed block_n, so the first key of each address is the
+            // current class hash and the rest are older versions.
+            let mut address = [0u8; 32];
+            address.copy_from_slice(&key[..32]);
+            if last_address == Some(address) {
+                continue;
+            }
+            last_address = Some(address);
+
+            contracts.push(ContractInfo {
+                address: format!("0x{}", hex::encode(address)),
+                class_hash: decode_felt_value(&value),
+                nonce: self.get_contract_nonce(&address, None),
+            });
+
+            if contracts.len() >= limit {
+                break;
             }
         }
 
@@ -386,15 +908,7 @@ impl DbReader {
                 if key.len() == 32 {
                     let class_hash = format!("0x{}", hex::encode(&key));
 
-                    let class_type = if value.is_empty() {
-                        ClassType::Unknown
-                    } else {
-                        match value[0] {
-                            0 => ClassType::Sierra,
-                            1 => ClassType::Legacy,
-                            _ => ClassType::Unknown,
-                        }
-                    };
+                    let class_type = self.decoder.class_type(&value);
 
                     classes.push(ClassInfo {
                         class_hash,
@@ -412,3 +926,295 @@ impl DbReader {
         classes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::open_db;
+    use tempfile::TempDir;
+
+    fn storage_key(address: u8, slot: u8, block_n: u64) -> Vec<u8> {
+        let mut key = vec![0u8; 68];
+        key[31] = address;
+        key[63] = slot;
+        key[64..].copy_from_slice(&encode_reversed_block(block_n));
+        key
+    }
+
+    fn felt_value(v: u8) -> Vec<u8> {
+        use bincode::Options;
+        bincode::DefaultOptions::new()
+            .serialize(&ByteBuf::from(vec![v]))
+            .unwrap()
+    }
+
+    fn contract_key(address: u8, block_n: u64) -> Vec<u8> {
+        let mut key = vec![0u8; 36];
+        key[31] = address;
+        key[32..].copy_from_slice(&encode_reversed_block(block_n));
+        key
+    }
+
+    fn open_with_storage(writes: &[(u8, u8, u64, u8)]) -> (TempDir, DbReader) {
+        let writes: Vec<_> = writes
+            .iter()
+            .map(|&(address, slot, block_n, value)| {
+                (
+                    "contract_storage",
+                    storage_key(address, slot, block_n),
+                    felt_value(value),
+                )
+            })
+            .collect();
+        open_db(&writes)
+    }
+
+    #[test]
+    fn reversed_block_roundtrip() {
+        assert_eq!(encode_reversed_block(0), [0xff; 4]);
+        assert_eq!(
+            decode_reversed_block(&encode_reversed_block(1234)),
+            Some(1234)
+        );
+        assert_eq!(decode_reversed_block(&[0, 0]), None);
+    }
+
+    #[test]
+    fn storage_at_block_picks_latest_version_before_target() {
+        let (_dir, db) = open_with_storage(&[(1, 1, 5, 10), (1, 1, 9, 20), (1, 2, 7, 30)]);
+
+        let at = |block_n| db.get_storage_at("0x1", "0x1", block_n).map(|e| e.value);
+        assert_eq!(at(4), None);
+        assert_eq!(at(5).as_deref(), Some("0x0a"));
+        assert_eq!(at(8).as_deref(), Some("0x0a"));
+        assert_eq!(at(100).as_deref(), Some("0x14"));
+        assert!(db.get_storage_at("0x2", "0x1", 100).is_none());
+    }
+
+    #[test]
+    fn contract_storage_dedupes_slots() {
+        let (_dir, db) =
+            open_with_storage(&[(1, 1, 5, 10), (1, 1, 9, 20), (1, 2, 7, 30), (2, 1, 1, 40)]);
+
+        let latest = db.get_contract_storage("0x1", 10);
+        let latest: Vec<_> = latest
+            .iter()
+            .map(|e| (e.key.as_str(), e.value.as_str(), e.block_number))
+            .collect();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].1, "0x14");
+        assert_eq!(latest[0].2, Some(9));
+        assert_eq!(latest[1].1, "0x1e");
+
+        let at_6 = db.get_contract_storage_at("0x1", 6, 10);
+        assert_eq!(at_6.len(), 1);
+        assert_eq!(at_6[0].value, "0x0a");
+        assert_eq!(at_6[0].block_number, Some(5));
+    }
+
+    #[test]
+    fn storage_history_lists_versions_newest_first() {
+        let (_dir, db) = open_with_storage(&[(1, 1, 5, 10), (1, 1, 9, 20), (1, 2, 7, 30)]);
+
+        let history: Vec<_> = db
+            .get_storage_history("0x1", "0x1", 10)
+            .into_iter()
+            .map(|v| (v.block_number, v.value))
+            .collect();
+        assert_eq!(
+            history,
+            vec![(9, "0x14".to_string()), (5, "0x0a".to_string())]
+        );
+        assert_eq!(db.get_storage_history("0x1", "0x1", 1).len(), 1);
+    }
+
+    #[test]
+    fn contract_at_block_resolves_nonce_and_class_hash() {
+        let (_dir, db) = open_db(&[
+            (
+                "contract_class_hashes",
+                contract_key(1, 2),
+                felt_value(0xaa),
+            ),
+            (
+                "contract_class_hashes",
+                contract_key(1, 8),
+                felt_value(0xbb),
+            ),
+            ("contract_nonces", contract_key(1, 3), felt_value(1)),
+            ("contract_nonces", contract_key(1, 6), felt_value(2)),
+            (
+                "contract_class_hashes",
+                contract_key(2, 4),
+                felt_value(0xcc),
+            ),
+        ]);
+
+        let latest = db.get_contract("0x1").unwrap();
+        assert_eq!(latest.class_hash.as_deref(), Some("0xbb"));
+        assert_eq!(latest.nonce, Some(2));
+
+        let at_4 = db.get_contract_at("0x1", 4).unwrap();
+        assert_eq!(at_4.class_hash.as_deref(), Some("0xaa"));
+        assert_eq!(at_4.nonce, Some(1));
+
+        let at_1 = db.get_contract_at("0x1", 1).unwrap();
+        assert_eq!(at_1.class_hash, None);
+        assert_eq!(at_1.nonce, None);
+
+        let history: Vec<_> = db
+            .get_contract_class_history("0x1")
+            .into_iter()
+            .map(|v| (v.block_number, v.class_hash))
+            .collect();
+        assert_eq!(
+            history,
+            vec![(8, "0xbb".to_string()), (2, "0xaa".to_string())]
+        );
+
+        assert_eq!(db.list_contracts(10).len(), 2);
+    }
+
+    #[test]
+    fn decodes_sierra_and_legacy_classes() {
+        use bincode::Options;
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+        let opts = bincode::DefaultOptions::new();
+        let buf = |b: &[u8]| ByteBuf::from(b.to_vec());
+
+        // Declared at block 8; the program header is Sierra 1.7.0 from compiler 2.11.4
+        let program: Vec<_> = [1u8, 7, 0, 2, 11, 4, 0x2a]
+            .iter()
+            .map(|&b| buf(&[b]))
+            .collect();
+        let sierra = opts
+            .serialize(&(
+                8u64,
+                0u32,
+                (
+                    program,
+                    "0.1.0",
+                    (
+                        vec![(buf(&[0x28]), 3u64)],
+                        vec![(buf(&[0x54]), 0u64), (buf(&[0x74]), 1u64)],
+                        Vec::<(ByteBuf, u64)>::new(),
+                    ),
+                    r#"[{"type":"function","name":"transfer"}]"#,
+                ),
+                None::<ByteBuf>,
+                Some(buf(&[0xcc])),
+            ))
+            .unwrap();
+
+        // Legacy class with one external entry point and an event in its ABI
+        let abi = vec![(
+            1u32,
+            (
+                0u32,
+                "Deployed",
+                Vec::<(String, String)>::new(),
+                vec![("address", "felt")],
+            ),
+        )];
+        let program_json = br#"{"builtins":["pedersen","range_check"],"prime":"0x800000000000011000000000000000000000000000000000000000000000001","compiler_version":"0.10.0","main_scope":"__main__","data":["0x40780017fff7fff","0x1"],"hints":{"0":[{"code":"memory[ap] = segments.add()"}],"5":[{},{}]},"identifiers":{"__main__.foo":{}}}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(program_json).unwrap();
+        let program = encoder.finish().unwrap();
+        let legacy = |program: &[u8]| {
+            opts.serialize(&(
+                0u64,
+                1u32,
+                buf(program),
+                (
+                    Vec::<(u64, ByteBuf)>::new(),
+                    vec![(155u64, buf(&[0x99]))],
+                    Vec::<(u64, ByteBuf)>::new(),
+                ),
+                Some(&abi),
+            ))
+            .unwrap()
+        };
+
+        let (_dir, db) = open_db(&[
+            ("class_info", [[0u8; 31].as_slice(), &[1]].concat(), sierra),
+            (
+                "class_info",
+                [[0u8; 31].as_slice(), &[2]].concat(),
+                legacy(&program),
+            ),
+            (
+                "class_info",
+                [[0u8; 31].as_slice(), &[3]].concat(),
+                legacy(b"not gzip"),
+            ),
+        ]);
+
+        let class = db.get_class_definition("0x1").unwrap().unwrap();
+        assert_eq!(class.class_type, ClassType::Sierra);
+        assert_eq!(class.declared_at, 8);
+        assert_eq!(class.sierra_version.as_deref(), Some("1.7.0"));
+        assert_eq!(class.compiler_version.as_deref(), Some("2.11.4"));
+        assert_eq!(class.program_length, 7);
+        assert_eq!(class.entry_points.constructor[0].function_idx, Some(3));
+        assert_eq!(class.entry_points.external[1].selector, "0x74");
+        assert_eq!(class.compiled_class_hash, None);
+        assert_eq!(class.compiled_class_hash_v2.as_deref(), Some("0xcc"));
+        let info = db.get_class("0x1").unwrap();
+        assert_eq!(info.compiled_class_hash.as_deref(), Some("0xcc"));
+
+        let class = db.get_class_definition("0x2").unwrap().unwrap();
+        assert_eq!(class.class_type, ClassType::Legacy);
+        assert_eq!(class.program_length, program.len());
+        assert_eq!(class.entry_points.external[0].offset, Some(155));
+        let summary = class.legacy_program.unwrap();
+        assert_eq!(summary.builtins, ["pedersen", "range_check"]);
+        assert_eq!(summary.compiler_version.as_deref(), Some("0.10.0"));
+        assert_eq!(summary.data_length, 2);
+        assert_eq!(summary.hints_count, 3);
+        assert_eq!(summary.identifiers_count, 1);
+        assert_eq!(class.legacy_program_error, None);
+        let (class_hash, downloaded) = db.get_class_program("0x02").unwrap().unwrap();
+        assert_eq!(
+            class_hash,
+            "0x0000000000000000000000000000000000000000000000000000000000000002"
+        );
+        assert_eq!(downloaded, program_json);
+        assert_eq!(
+            db.get_class_program("0x1").unwrap().unwrap().1,
+            br#"["0x01","0x07","0x00","0x02","0x0b","0x04","0x2a"]"#
+        );
+        let abi: serde_json::Value = serde_json::from_str(class.abi.as_deref().unwrap()).unwrap();
+        assert_eq!(abi[0]["type"], "event");
+        assert_eq!(abi[0]["data"][0]["name"], "address");
+        // The ABI is read without touching the program
+        assert_eq!(db.get_class_abi("0x2").unwrap(), class.abi);
+        assert_eq!(db.get_class_abi("0x3").unwrap(), class.abi);
+
+        // A broken program is reported next to the rest of the definition
+        let broken = db.get_class_definition("0x3").unwrap().unwrap();
+        assert!(broken.legacy_program.is_none());
+        assert!(broken
+            .legacy_program_error
+            .unwrap()
+            .starts_with("failed to decompress"));
+        assert_eq!(broken.entry_points.external[0].offset, Some(155));
+        assert!(db.get_class_program("0x3").is_err());
+        assert!(db
+            .get_class_abi("0x1")
+            .unwrap()
+            .unwrap()
+            .contains("transfer"));
+
+        let types: Vec<_> = db
+            .list_classes(10)
+            .into_iter()
+            .map(|c| c.class_type)
+            .collect();
+        assert_eq!(
+            types,
+            [ClassType::Sierra, ClassType::Legacy, ClassType::Legacy]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Per-version value decoders.
+//!
+//! Madara changes the bincode layout of some column families between DB schema
+//! versions. Each supported version gets its own module implementing
+//! [`MadaraDecoder`]; `DbReader::open` picks one from the `.db-version` file and
+//! every block, transaction, state diff, bouncer weight, class, compiled class, mempool,
+//! messaging, meta and preconfirmed read dispatches through it.
+//!
+//! Supporting a new schema version means adding a module here and registering it
+//! in [`decoder_for_version`].
+
+mod v8;
+mod v9;
+
+use crate::blocks::RawMadaraBlockInfo;
+use crate::bouncer::RawBlockBouncerWeights;
+use crate::casm::RawCompiledSierraWithBlockN;
+use crate::contracts::RawClassInfoWithBlockN;
+use crate::mempool::RawValidatedTransaction;
+use crate::messaging::RawL1HandlerTransactionWithFee;
+use crate::meta::RawMetaValue;
+use crate::preconfirmed::RawPreconfirmedExecutedTransaction;
+use crate::state_diff::RawStateDiff;
+use crate::transactions::RawTransactionWithReceipt;
+use crate::{ClassType, DecodeError, TrieNode};
+
+pub(crate) use v8::V8Decoder;
+pub(crate) use v9::V9Decoder;
+
+/// Madara DB schema versions with a registered decoder.
+// Keep in sync with upstream Madara `.db-versions.yml` as we validate more versions.
+pub const SUPPORTED_MADARA_DB_VERSIONS: &[u32] = &[8, 9];
+
+/// Decodes raw column family values for one Madara DB schema version
+pub(crate) trait MadaraDecoder: Send + Sync {
+    /// DB schema version this decoder reads
+    fn version(&self) -> u32;
+
+    /// Decode a `block_info` value
+    fn decode_block_info(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawMadaraBlockInfo, DecodeError>;
+
+    /// Decode a `block_transactions` value
+    fn decode_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawTransactionWithReceipt, DecodeError>;
+
+    /// Decode a `block_state_diff` value
+    fn decode_state_diff(&self, key: &[u8], value: &[u8]) -> Result<RawStateDiff, DecodeError>;
+
+    /// Decode a `block_bouncer_weight` value
+    fn decode_bouncer_weights(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawBlockBouncerWeights, DecodeError>;
+
+    /// Decode a `class_info` value
+    fn decode_class_info(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawClassInfoWithBlockN, DecodeError>;
+
+    /// Decode a `class_compiled` value
+    fn decode_compiled_class(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawCompiledSierraWithBlockN, DecodeError>;
+
+    /// Decode a `mempool_transactions` value
+    fn decode_mempool_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawValidatedTransaction, DecodeError>;
+
+    /// Decode a `l1_to_l2_pending_message_by_nonce` value
+    fn decode_pending_message(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawL1HandlerTransactionWithFee, DecodeError>;
+
+    /// Decode a `preconfirmed` value
+    fn decode_preconfirmed_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawPreconfirmedExecutedTransaction, DecodeError>;
+
+    /// Decode a `meta` value according to its key
+    fn decode_meta(&self, key: &[u8], value: &[u8]) -> Result<RawMetaValue, DecodeError>;
+
+    /// Classify a `class_info` value without decoding the whole class
+    fn class_type(&self, value: &[u8]) -> ClassType;
+
+    /// Decode a bonsai trie node read from one of the `bonsai_*_trie` column families
+    fn decode_trie_node(&self, cf: &str, key: &[u8], value: &[u8])
+        -> Result<TrieNode, DecodeError>;
+}
+
+/// Select the decoder for a detected DB version.
+///
+/// A missing or unsupported version falls back to the newest decoder; callers that
+/// care can compare [`MadaraDecoder::version`] against what was detected.
+pub(crate) fn decoder_for_version(version: Option<u32>) -> Box<dyn MadaraDecoder> {
+    match version {
+        Some(8) => Box::new(V8Decoder),
+        _ => Box::new(V9Decoder),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_decoder_by_version() {
+        for &version in SUPPORTED_MADARA_DB_VERSIONS {
+            assert_eq!(decoder_for_version(Some(version)).version(), version);
+        }
+    }
+
+    #[test]
+    fn unknown_version_falls_back_to_newest() {
+        let newest = *SUPPORTED_MADARA_DB_VERSIONS.iter().max().unwrap();
+        assert_eq!(decoder_for_version(None).version(), newest);
+        assert_eq!(decoder_for_version(Some(1000)).version(), newest);
+    }
+}
@@ -0,0 +1,120 @@
+//! Decoder for Madara DB schema version 8
+
+use super::MadaraDecoder;
+use crate::blocks::RawMadaraBlockInfo;
+use crate::bouncer::RawBlockBouncerWeights;
+use crate::casm::RawCompiledSierraWithBlockN;
+use crate::contracts::RawClassInfoWithBlockN;
+use crate::mempool::RawValidatedTransaction;
+use crate::messaging::RawL1HandlerTransactionWithFee;
+use crate::meta::RawMetaValue;
+use crate::preconfirmed::RawPreconfirmedExecutedTransaction;
+use crate::state_diff::RawStateDiff;
+use crate::transactions::RawTransactionWithReceipt;
+use crate::{bouncer, decode_value, meta, tries, ClassType, DecodeError, TrieNode};
+
+pub(crate) struct V8Decoder;
+
+impl MadaraDecoder for V8Decoder {
+    fn version(&self) -> u32 {
+        8
+    }
+
+    fn decode_block_info(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawMadaraBlockInfo, DecodeError> {
+        decode_value("block_info", key, value)
+    }
+
+    fn decode_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawTransactionWithReceipt, DecodeError> {
+        decode_value("block_transactions", key, value)
+    }
+
+    fn decode_state_diff(&self, key: &[u8], value: &[u8]) -> Result<RawStateDiff, DecodeError> {
+        decode_value("block_state_diff", key, value)
+    }
+
+    fn decode_bouncer_weights(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawBlockBouncerWeights, DecodeError> {
+        bouncer::decode_bouncer_weights(key, value)
+    }
+
+    fn decode_class_info(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawClassInfoWithBlockN, DecodeError> {
+        decode_value("class_info", key, value)
+    }
+
+    fn decode_compiled_class(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawCompiledSierraWithBlockN, DecodeError> {
+        decode_value("class_compiled", key, value)
+    }
+
+    fn decode_mempool_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawValidatedTransaction, DecodeError> {
+        decode_value("mempool_transactions", key, value)
+    }
+
+    fn decode_pending_message(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawL1HandlerTransactionWithFee, DecodeError> {
+        decode_value("l1_to_l2_pending_message_by_nonce", key, value)
+    }
+
+    fn decode_preconfirmed_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawPreconfirmedExecutedTransaction, DecodeError> {
+        decode_value("preconfirmed", key, value)
+    }
+
+    fn decode_meta(&self, key: &[u8], value: &[u8]) -> Result<RawMetaValue, DecodeError> {
+        meta::decode_meta(key, value)
+    }
+
+    fn class_type(&self, value: &[u8]) -> ClassType {
+        // `ClassInfoWithBlockN` starts with the varint block number, then the `ClassInfo`
+        // enum tag
+        let tag = match value.first() {
+            Some(&b) if b < 251 => value.get(1),
+            Some(251) => value.get(3),
+            Some(252) => value.get(5),
+            Some(253) => value.get(9),
+            _ => None,
+        };
+        match tag {
+            Some(0) => ClassType::Sierra,
+            Some(1) => ClassType::Legacy,
+            _ => ClassType::Unknown,
+        }
+    }
+
+    fn decode_trie_node(
+        &self,
+        cf: &str,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<TrieNode, DecodeError> {
+        tries::decode_node(cf, key, value)
+    }
+}
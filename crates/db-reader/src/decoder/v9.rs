@@ -0,0 +1,109 @@
+//! Decoder for Madara DB schema version 9
+
+use super::{MadaraDecoder, V8Decoder};
+use crate::blocks::RawMadaraBlockInfo;
+use crate::bouncer::RawBlockBouncerWeights;
+use crate::casm::RawCompiledSierraWithBlockN;
+use crate::contracts::RawClassInfoWithBlockN;
+use crate::mempool::RawValidatedTransaction;
+use crate::messaging::RawL1HandlerTransactionWithFee;
+use crate::meta::RawMetaValue;
+use crate::preconfirmed::RawPreconfirmedExecutedTransaction;
+use crate::state_diff::RawStateDiff;
+use crate::transactions::RawTransactionWithReceipt;
+use crate::{ClassType, DecodeError, TrieNode};
+
+/// Version 9 kept the version 8 layout for every column family decoded here, so
+/// this delegates; override a method once a layout diverges.
+pub(crate) struct V9Decoder;
+
+impl MadaraDecoder for V9Decoder {
+    fn version(&self) -> u32 {
+        9
+    }
+
+    fn decode_block_info(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawMadaraBlockInfo, DecodeError> {
+        V8Decoder.decode_block_info(key, value)
+    }
+
+    fn decode_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawTransactionWithReceipt, DecodeError> {
+        V8Decoder.decode_transaction(key, value)
+    }
+
+    fn decode_state_diff(&self, key: &[u8], value: &[u8]) -> Result<RawStateDiff, DecodeError> {
+        V8Decoder.decode_state_diff(key, value)
+    }
+
+    fn decode_bouncer_weights(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawBlockBouncerWeights, DecodeError> {
+        V8Decoder.decode_bouncer_weights(key, value)
+    }
+
+    fn decode_class_info(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawClassInfoWithBlockN, DecodeError> {
+        V8Decoder.decode_class_info(key, value)
+    }
+
+    fn decode_compiled_class(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawCompiledSierraWithBlockN, DecodeError> {
+        V8Decoder.decode_compiled_class(key, value)
+    }
+
+    fn decode_mempool_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawValidatedTransaction, DecodeError> {
+        V8Decoder.decode_mempool_transaction(key, value)
+    }
+
+    fn decode_pending_message(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawL1HandlerTransactionWithFee, DecodeError> {
+        V8Decoder.decode_pending_message(key, value)
+    }
+
+    fn decode_preconfirmed_transaction(
+        &self,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<RawPreconfirmedExecutedTransaction, DecodeError> {
+        V8Decoder.decode_preconfirmed_transaction(key, value)
+    }
+
+    fn decode_meta(&self, key: &[u8], value: &[u8]) -> Result<RawMetaValue, DecodeError> {
+        V8Decoder.decode_meta(key, value)
+    }
+
+    fn class_type(&self, value: &[u8]) -> ClassType {
+        V8Decoder.class_type(value)
+    }
+
+    fn decode_trie_node(
+        &self,
+        cf: &str,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<TrieNode, DecodeError> {
+        V8Decoder.decode_trie_node(cf, key, value)
+    }
+}
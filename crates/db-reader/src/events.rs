@@ -0,0 +1,526 @@
+//! Event search over `block_transactions`, pruned with the `events_bloom` filters.
+//!
+//! Madara stores one `EventBloomWriter` per block that emitted events, keyed by the block
+//! number as `u32` big-endian. The value is the filter's bit array as a bincode `Vec<u64>`;
+//! bit `i` lives in word `i / 64` at position `i % 64`. Every event inserts its `from_address`
+//! and each of its keys tagged with the key position, and each item sets
+//! [`BLOOM_HASH_COUNT`] bits picked by double hashing its xxh3-128 digest.
+//!
+//! A block without a filter emitted no events, so the search only reads receipts of blocks
+//! whose filter may contain every item the query requires.
+
+use std::collections::HashMap;
+
+use rocksdb::{Direction, IteratorMode};
+use serde::Deserialize;
+
+use crate::contracts::parse_felt_hex;
+use crate::transactions::{RawEvent, RawTransactionWithReceipt};
+use crate::{decode_value, DbError, DbReader, DecodeError, Felt};
+
+/// Bits set per inserted item
+pub const BLOOM_HASH_COUNT: u32 = 7;
+
+/// Most events a single search returns
+pub const MAX_EVENT_SEARCH_LIMIT: usize = 1000;
+
+/// Most blocks covered by one search
+pub const MAX_EVENT_SCAN_BLOCKS: u64 = 10_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawEventBloomWriter {
+    pub filter: Vec<u64>,
+}
+
+/// A decoded per-block event bloom filter
+#[derive(Debug, Clone)]
+pub struct EventBloom {
+    words: Vec<u64>,
+}
+
+/// Bloom item for an event's `from_address`
+fn address_item(address: &[u8; 32]) -> [u8; 33] {
+    let mut item = [0u8; 33];
+    item[1..].copy_from_slice(address);
+    item
+}
+
+/// Bloom item for the key at `position`; positions past 254 are not indexed
+fn key_item(position: usize, key: &[u8; 32]) -> Option<[u8; 33]> {
+    let tag = u8::try_from(position + 1).ok().filter(|&t| t != u8::MAX)?;
+    let mut item = [0u8; 33];
+    item[0] = tag;
+    item[1..].copy_from_slice(key);
+    Some(item)
+}
+
+impl EventBloom {
+    pub fn from_words(words: Vec<u64>) -> Self {
+        Self { words }
+    }
+
+    /// Filter size in bits
+    pub fn bits(&self) -> u64 {
+        self.words.len() as u64 * 64
+    }
+
+    pub fn set_bits(&self) -> u64 {
+        self.words.iter().map(|w| u64::from(w.count_ones())).sum()
+    }
+
+    /// Chance that an item never inserted still hits all of its bits
+    pub fn false_positive_rate(&self) -> f64 {
+        if self.words.is_empty() {
+            return 1.0;
+        }
+        let fill = self.set_bits() as f64 / self.bits() as f64;
+        fill.powi(BLOOM_HASH_COUNT as i32)
+    }
+
+    fn bit_indexes(&self, item: &[u8]) -> impl Iterator<Item = u64> {
+        let digest = xxhash_rust::xxh3::xxh3_128(item);
+        let (h1, h2) = (digest as u64, (digest >> 64) as u64);
+        let bits = self.bits().max(1);
+        (0..u64::from(BLOOM_HASH_COUNT)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % bits)
+    }
+
+    #[cfg(test)]
+    fn insert(&mut self, item: &[u8]) {
+        for bit in self.bit_indexes(item).collect::<Vec<_>>() {
+            self.words[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    fn contains(&self, item: &[u8]) -> bool {
+        !self.words.is_empty()
+            && self
+                .bit_indexes(item)
+                .all(|bit| self.words[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    pub fn may_contain_address(&self, address: &[u8; 32]) -> bool {
+        self.contains(&address_item(address))
+    }
+
+    pub fn may_contain_key(&self, position: usize, key: &[u8; 32]) -> bool {
+        key_item(position, key).is_none_or(|item| self.contains(&item))
+    }
+
+    /// Whether the filter may hold an event matching `filter`
+    pub fn may_match(&self, filter: &EventFilter) -> bool {
+        filter
+            .address
+            .as_ref()
+            .is_none_or(|a| self.may_contain_address(a))
+            && filter.keys.iter().enumerate().all(|(position, keys)| {
+                keys.is_empty() || keys.iter().any(|k| self.may_contain_key(position, k))
+            })
+    }
+
+    /// Whether every item of `events` is in the filter; a bloom filter never misses an item
+    /// that was inserted, so `false` means the filter was not built the way we read it
+    fn covers(&self, events: &[RawEvent]) -> bool {
+        events.iter().all(|event| {
+            self.may_contain_address(&felt_bytes(&event.from_address))
+                && event
+                    .keys
+                    .iter()
+                    .enumerate()
+                    .all(|(position, key)| self.may_contain_key(position, &felt_bytes(key)))
+        })
+    }
+}
+
+fn felt_bytes(bytes: &[u8]) -> [u8; 32] {
+    Felt::from_bytes(bytes).0
+}
+
+/// Event search criteria, following `starknet_getEvents`
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub from_block: u64,
+    pub to_block: u64,
+    /// Emitting contract
+    pub address: Option<[u8; 32]>,
+    /// Accepted values per key position; an empty position matches any key
+    pub keys: Vec<Vec<[u8; 32]>>,
+    pub limit: usize,
+}
+
+impl EventFilter {
+    /// Build a filter from hex strings, rejecting values that are not felts
+    pub fn parse(
+        from_block: u64,
+        to_block: u64,
+        address: Option<&str>,
+        keys: &[Vec<String>],
+        limit: usize,
+    ) -> Result<Self, DbError> {
+        let felt = |value: &str| {
+            parse_felt_hex(value)
+                .ok_or_else(|| DbError::InvalidInput(format!("invalid felt: {value}")))
+        };
+        if from_block > to_block {
+            return Err(DbError::InvalidInput(format!(
+                "from_block {from_block} is after to_block {to_block}"
+            )));
+        }
+        Ok(Self {
+            from_block,
+            to_block,
+            address: address.map(felt).transpose()?,
+            keys: keys
+                .iter()
+                .map(|position| position.iter().map(|k| felt(k)).collect())
+                .collect::<Result<_, _>>()?,
+            limit: limit.clamp(1, MAX_EVENT_SEARCH_LIMIT),
+        })
+    }
+
+    fn matches(&self, event: &RawEvent) -> bool {
+        self.address
+            .as_ref()
+            .is_none_or(|a| felt_bytes(&event.from_address) == *a)
+            && self.keys.iter().enumerate().all(|(position, keys)| {
+                keys.is_empty()
+                    || event
+                        .keys
+                        .get(position)
+                        .is_some_and(|k| keys.contains(&felt_bytes(k)))
+            })
+    }
+}
+
+/// An event matching a search
+#[derive(Debug, Clone)]
+pub struct EventMatch {
+    pub block_number: u64,
+    pub tx_index: usize,
+    /// Position of the event within its transaction receipt
+    pub event_index: usize,
+    pub tx_hash: String,
+    pub from_address: String,
+    pub keys: Vec<String>,
+    pub data: Vec<String>,
+}
+
+/// How well the bloom filters pruned a search
+#[derive(Debug, Clone, Default)]
+pub struct EventSearchStats {
+    /// Blocks between `from_block` and the last block scanned
+    pub blocks_in_range: u64,
+    /// Blocks in range with an `events_bloom` entry
+    pub blocks_with_bloom: u64,
+    /// Blocks whose receipts were read
+    pub blocks_scanned: u64,
+    /// Blocks whose filter passed the query
+    pub candidate_blocks: u64,
+    /// Candidate blocks that held no matching event
+    pub false_positive_blocks: u64,
+    /// `false_positive_blocks / candidate_blocks`
+    pub false_positive_rate: f64,
+    /// Mean false-positive rate of a single item across the filters read, from how full
+    /// they are
+    pub expected_item_false_positive_rate: f64,
+    /// `false` when the search read every block in range instead of trusting the filters
+    pub bloom_pruning: bool,
+    /// `false` when a scanned block had events its filter does not contain
+    pub bloom_consistent: bool,
+    /// Transactions skipped because they failed to decode
+    pub undecodable_transactions: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventSearchResult {
+    pub events: Vec<EventMatch>,
+    pub stats: EventSearchStats,
+    /// End of the range, lowered to the latest block
+    pub to_block: u64,
+    /// Set when the search stopped at `limit` or after [`MAX_EVENT_SCAN_BLOCKS`] blocks;
+    /// resume from the block after it
+    pub last_block_scanned: Option<u64>,
+}
+
+impl DbReader {
+    /// Get the event bloom filter of a block, if it emitted events
+    pub fn get_event_bloom(&self, block_n: u64) -> Result<Option<EventBloom>, DbError> {
+        let Some(cf) = self.db.cf_handle("events_bloom") else {
+            return Ok(None);
+        };
+        let Ok(block_n) = u32::try_from(block_n) else {
+            return Ok(None);
+        };
+        let key = block_n.to_be_bytes();
+        let Some(value) = self.db.get_cf(&cf, key)? else {
+            return Ok(None);
+        };
+        Ok(Some(decode_bloom(&key, &value)?))
+    }
+
+    /// Blocks in `[from, to]` with a bloom filter, in order
+    fn event_blooms(&self, from: u64, to: u64) -> Result<Vec<(u64, EventBloom)>, DbError> {
+        let Some(cf) = self.db.cf_handle("events_bloom") else {
+            return Ok(vec![]);
+        };
+        let start = u32::try_from(from).unwrap_or(u32::MAX).to_be_bytes();
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&start, Direction::Forward));
+
+        let mut blooms = Vec::new();
+        for item in iter {
+            let (key, value) = item?;
+            let Ok(block_n) = <[u8; 4]>::try_from(key.as_ref()).map(u32::from_be_bytes) else {
+                continue;
+            };
+            if u64::from(block_n) > to {
+                break;
+            }
+            blooms.push((u64::from(block_n), decode_bloom(&key, &value)?));
+        }
+        Ok(blooms)
+    }
+
+    /// Search events in `[filter.from_block, filter.to_block]`, reading only the receipts of
+    /// blocks whose bloom filter may match. `to_block` is lowered to the latest block.
+    ///
+    /// The first filter in range is checked against its own block's events before any block
+    /// is skipped; if it misses one of them, the filters cannot be trusted and every block in
+    /// range is scanned instead.
+    pub fn search_events(&self, filter: &EventFilter) -> Result<EventSearchResult, DbError> {
+        let to_block = filter
+            .to_block
+            .min(self.get_latest_block_number().unwrap_or(0));
+        let mut stats = EventSearchStats {
+            bloom_consistent: true,
+            ..Default::default()
+        };
+        if filter.from_block > to_block {
+            return Ok(EventSearchResult {
+                events: vec![],
+                stats,
+                to_block,
+                last_block_scanned: None,
+            });
+        }
+        let scan_to = to_block.min(filter.from_block.saturating_add(MAX_EVENT_SCAN_BLOCKS - 1));
+        let blooms = self.event_blooms(filter.from_block, scan_to)?;
+        stats.blocks_with_bloom = blooms.len() as u64;
+
+        // Receipts of the block used to check the filters, reused by the scan below
+        let mut probe = None;
+        if let Some((block_n, bloom)) = blooms.first() {
+            let txs = self.block_raw_events(*block_n, &mut stats);
+            stats.bloom_consistent = txs.iter().all(|(_, tx)| bloom.covers(tx.raw_events()));
+            probe = Some((*block_n, txs));
+        }
+        stats.bloom_pruning = stats.bloom_consistent && !blooms.is_empty();
+
+        let by_block: HashMap<u64, &EventBloom> = blooms.iter().map(|(n, b)| (*n, b)).collect();
+        let candidates: Box<dyn Iterator<Item = (u64, Option<&EventBloom>)> + '_> =
+            if stats.bloom_pruning {
+                Box::new(blooms.iter().map(|(n, b)| (*n, Some(b))))
+            } else {
+                Box::new((filter.from_block..=scan_to).map(|n| (n, by_block.get(&n).copied())))
+            };
+
+        let mut events = Vec::new();
+        let mut last_block_scanned = None;
+        let mut fill_rates = Vec::new();
+        for (block_n, bloom) in candidates {
+            let passes = bloom.is_none_or(|b| b.may_match(filter));
+            if let Some(bloom) = bloom {
+                fill_rates.push(bloom.false_positive_rate());
+                if passes {
+                    stats.candidate_blocks += 1;
+                }
+            }
+            if stats.bloom_pruning && !passes {
+                continue;
+            }
+
+            stats.blocks_scanned += 1;
+            let mut matched = false;
+            let txs = match probe.take() {
+                Some((probe_block, txs)) if probe_block == block_n => txs,
+                _ => self.block_raw_events(block_n, &mut stats),
+            };
+            for (tx_index, tx) in txs {
+                if bloom.is_some_and(|b| !b.covers(tx.raw_events())) {
+                    stats.bloom_consistent = false;
+                }
+                for (event_index, event) in tx.raw_events().iter().enumerate() {
+                    if !filter.matches(event) {
+                        continue;
+                    }
+                    matched = true;
+                    events.push(EventMatch {
+                        block_number: block_n,
+                        tx_index,
+                        event_index,
+                        tx_hash: tx.get_tx_hash(),
+                        from_address: Felt::from_bytes(&event.from_address).to_hex(),
+                        keys: event
+                            .keys
+                            .iter()
+                            .map(|k| Felt::from_bytes(k).to_hex())
+                            .collect(),
+                        data: event
+                            .data
+                            .iter()
+                            .map(|d| Felt::from_bytes(d).to_hex())
+                            .collect(),
+                    });
+                }
+            }
+            if bloom.is_some() && passes && !matched {
+                stats.false_positive_blocks += 1;
+            }
+            if events.len() >= filter.limit {
+                events.truncate(filter.limit);
+                last_block_scanned = Some(block_n);
+                break;
+            }
+        }
+
+        if last_block_scanned.is_none() && scan_to < to_block {
+            last_block_scanned = Some(scan_to);
+        }
+        let scanned_to = last_block_scanned.unwrap_or(to_block);
+        stats.blocks_in_range = scanned_to.saturating_sub(filter.from_block) + 1;
+        if last_block_scanned.is_some() {
+            stats.blocks_with_bloom =
+                blooms.iter().filter(|(n, _)| *n <= scanned_to).count() as u64;
+        }
+        if stats.candidate_blocks > 0 {
+            stats.false_positive_rate =
+                stats.false_positive_blocks as f64 / stats.candidate_blocks as f64;
+        }
+        if !fill_rates.is_empty() {
+            stats.expected_item_false_positive_rate =
+                fill_rates.iter().sum::<f64>() / fill_rates.len() as f64;
+        }
+
+        Ok(EventSearchResult {
+            events,
+            stats,
+            to_block,
+            last_block_scanned,
+        })
+    }
+
+    /// Decoded transactions of a block, counting the ones that fail to decode
+    fn block_raw_events(
+        &self,
+        block_n: u64,
+        stats: &mut EventSearchStats,
+    ) -> Vec<(usize, RawTransactionWithReceipt)> {
+        self.get_raw_block_transactions(block_n)
+            .into_iter()
+            .filter_map(|(tx_index, tx)| match tx {
+                Ok(tx) => Some((tx_index, tx)),
+                Err(_) => {
+                    stats.undecodable_transactions += 1;
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn decode_bloom(key: &[u8], value: &[u8]) -> Result<EventBloom, DecodeError> {
+    let raw: RawEventBloomWriter = decode_value("events_bloom", key, value)?;
+    Ok(EventBloom::from_words(raw.filter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{chain_tip, open_db};
+
+    fn felt(last: u8) -> [u8; 32] {
+        let mut felt = [0u8; 32];
+        felt[31] = last;
+        felt
+    }
+
+    fn bloom_value(addresses: &[[u8; 32]]) -> Vec<u8> {
+        use bincode::Options;
+        let mut bloom = EventBloom::from_words(vec![0; 2]);
+        for address in addresses {
+            bloom.insert(&address_item(address));
+        }
+        bincode::DefaultOptions::new()
+            .serialize(&bloom.words)
+            .unwrap()
+    }
+
+    #[test]
+    fn decodes_bloom_written_by_madara() {
+        // Block 4 of the sample DB: a single STRK fee transfer
+        let value = hex::decode("01fdc8da250285e82240").unwrap();
+        let bloom = decode_bloom(&4u32.to_be_bytes(), &value).unwrap();
+        assert_eq!(bloom.bits(), 64);
+        assert_eq!(bloom.set_bits(), 22);
+
+        let err = decode_bloom(&4u32.to_be_bytes(), &value[..5]).unwrap_err();
+        assert_eq!(err.expected_type, "EventBloomWriter");
+    }
+
+    #[test]
+    fn search_skips_blocks_the_bloom_rules_out() {
+        let (emitter, other) = (felt(0xa), felt(0xb));
+        let bloom = |block_n: u32, addresses: &[[u8; 32]]| {
+            (
+                "events_bloom",
+                block_n.to_be_bytes().to_vec(),
+                bloom_value(addresses),
+            )
+        };
+        let (_dir, reader) = open_db(&[
+            chain_tip(10),
+            bloom(1, &[other]),
+            bloom(2, &[emitter, other]),
+            bloom(4, &[other]),
+        ]);
+
+        let filter = EventFilter::parse(0, 10, Some("0xa"), &[], 100).unwrap();
+        let result = reader.search_events(&filter).unwrap();
+        let stats = result.stats;
+        assert!(stats.bloom_pruning);
+        assert_eq!(stats.blocks_in_range, 11);
+        assert_eq!(stats.blocks_with_bloom, 3);
+        assert_eq!(stats.candidate_blocks, 1);
+        assert_eq!(stats.blocks_scanned, 1);
+        // Block 2 has no receipts, so its filter matched for nothing
+        assert_eq!(stats.false_positive_blocks, 1);
+        assert_eq!(stats.false_positive_rate, 1.0);
+        assert!(result.events.is_empty());
+
+        assert!(EventFilter::parse(0, 10, Some("0xzz"), &[], 100).is_err());
+        assert!(EventFilter::parse(5, 1, None, &[], 100).is_err());
+    }
+
+    #[test]
+    fn search_is_bounded_by_the_chain() {
+        let (_dir, reader) = open_db(&[chain_tip(20_000)]);
+
+        let filter = EventFilter::parse(0, u64::MAX, None, &[], 100).unwrap();
+        let result = reader.search_events(&filter).unwrap();
+        assert!(!result.stats.bloom_pruning);
+        assert_eq!(result.to_block, 20_000);
+        assert_eq!(result.last_block_scanned, Some(MAX_EVENT_SCAN_BLOCKS - 1));
+        assert_eq!(result.stats.blocks_scanned, MAX_EVENT_SCAN_BLOCKS);
+        assert_eq!(result.stats.blocks_in_range, MAX_EVENT_SCAN_BLOCKS);
+
+        let filter = EventFilter::parse(15_000, u64::MAX, None, &[], 100).unwrap();
+        let result = reader.search_events(&filter).unwrap();
+        assert_eq!(result.last_block_scanned, None);
+        assert_eq!(result.stats.blocks_in_range, 5_001);
+
+        let filter = EventFilter::parse(30_000, u64::MAX, None, &[], 100).unwrap();
+        let result = reader.search_events(&filter).unwrap();
+        assert!(result.events.is_empty());
+        assert_eq!(result.stats.blocks_scanned, 0);
+    }
+}
@@ -0,0 +1,52 @@
+//! Database values shared by unit tests
+
+/// `block_info` value of block 4 of a Madara devnet on 0.14.1, which holds [`DECLARE_V3`]
+pub(crate) const BLOCK_INFO: &str = concat!(
+    "2005638db6083a64c1babdd7180439eb0a76dbe1dc200aab0f0017ef5ab2978cf5042006e927fa53b5db85ea",
+    "a8845891e18794f7103965f82a0a156dbbb086565e244b020123fcd6777c69012003d965bb1887f963aed7cd",
+    "eadc40b902a7a03258c4fbcca9f32c944c000a8a810120030c71516137d499c50aabfe87e16c82705572aa32",
+    "912c1f677b3501539a28a50109012001d8d289f811fce0ffea2e8246306788b48d4028718dd8f821435a147a",
+    "fa1dd70120019387f45d0fc79a0283534b7de1750d6c40bdae00dfb0131b8d28cfbbadf12f000e0100808080",
+    "80fc9f860100fca086010001200309d73fff79c7ac1255f39544b56de199bc7bff6299272212f23dc848e077",
+    "43fc00fcf0260120068dc917fe3e96612b4547f85853c3021c49059c9ca5b49a8d7b5425765ab6fe",
+);
+
+/// `block_state_diff` value of the same block
+pub(crate) const STATE_DIFF: &str = concat!(
+    "022004718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d022005c9f465f699f4b0",
+    "b12df48940c006bf64af23c4fa77aeaa17a7389bab8fb0f80a021e19e08e4f3bba2000200709c6298f62a901",
+    "1c05499b9f5ccce4ecc3e0753e48096edef484c409c25181063b6b7685e0000102052005c9f465f699f4b0b1",
+    "2df48940c006bf64af23c4fa77aeaa17a7389bab8fb0f80180200709c6298f62a9011c05499b9f5ccce4ecc3",
+    "e0753e48096edef484c409c2518101812004718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858",
+    "f4287c938d01820100018420055be462e718c4166d656d11f89e341115b8bc82389c3762a10eade04fcb225d",
+    "018300012003813488a19a9361784e5f46a9fa82f007ca680c3e02d2678fb1d806115a9f8a2006ad580fc695",
+    "566497e5e4a22fccf3d1f7f680b32aa29457843a7144397a525b00000120055be462e718c4166d656d11f89e",
+    "341115b8bc82389c3762a10eade04fcb225d010100",
+);
+
+/// `block_transactions` value of block 4, transaction 0 of a Madara devnet on 0.14.1:
+/// a declare V3
+pub(crate) const DECLARE_V3: &str = concat!(
+    "020320055be462e718c4166d656d11f89e341115b8bc82389c3762a10eade04fcb225d2006ad580fc6955664",
+    "97e5e4a22fccf3d1f7f680b32aa29457843a7144397a525b022002c935b79888dcc4016081bfd189ef253140",
+    "c3f8c40d4a24750c8530b9e2b4a720030bed2c823a2f09bf088cf230ec6bc7b36e7ce75758ae88fc1733c52d",
+    "14ec1001002003813488a19a9361784e5f46a9fa82f007ca680c3e02d2678fb1d806115a9f8a083078313030",
+    "3030300c3078313030303030303030300b30783266613634646230300c307831303030303030303030010830",
+    "783130303030300c30783130303030303030303000000000000220068dc917fe3e96612b4547f85853c3021c",
+    "49059c9ca5b49a8d7b5425765ab6fe063b6b7685e0000100012004718f5a0fc34cc1af16a1cdee98ffb20c31",
+    "f5cd61d6ab07201858f4287c938d031f99cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e",
+    "6196e920055be462e718c4166d656d11f89e341115b8bc82389c3762a10eade04fcb225d02012302063b6b76",
+    "85e0000100fb24071d350000000000000000c00000c0fc00fcf02600",
+);
+
+/// L1 handler (version 0, nonce 7) calling selector 0xabcd of 0x1234 with [0xeeee, 1, 2],
+/// followed by an L1 handler receipt for message hash 0x1111..11 whose other fields,
+/// including the transaction hash, are those of the [`DECLARE_V3`] receipt
+pub(crate) const L1_HANDLER: &str = concat!(
+    "01000702123402abcd0302eeee01010102011111111111111111111111111111111111111111111111111111",
+    "11111111111120068dc917fe3e96612b4547f85853c3021c49059c9ca5b49a8d7b5425765ab6fe063b6b7685",
+    "e0000100012004718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d031f99cd8bde",
+    "557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e920055be462e718c4166d656d11f89e3411",
+    "15b8bc82389c3762a10eade04fcb225d02012302063b6b7685e0000100fb24071d350000000000000000c000",
+    "00c0fc00fcf02600",
+);
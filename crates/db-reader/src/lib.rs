@@ -4,20 +4,54 @@ use rocksdb::{DBWithThreadMode, MultiThreaded, Options};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use decoder::{decoder_for_version, MadaraDecoder};
+
+pub mod abi;
+pub mod audit;
 pub mod blocks;
+pub mod bouncer;
+pub mod casm;
 pub mod contracts;
+mod decoder;
+pub mod events;
+#[cfg(test)]
+mod fixtures;
+pub mod mempool;
+pub mod messaging;
+pub mod meta;
+pub mod preconfirmed;
+pub mod proofs;
 mod queries;
 pub mod raw;
 pub mod state_diff;
+#[cfg(test)]
+mod test_utils;
 pub mod transactions;
+pub mod trie_log;
+pub mod tries;
+pub mod verify;
 pub mod version;
 
+pub use abi::*;
+pub use audit::*;
 pub use blocks::*;
+pub use bouncer::*;
+pub use casm::*;
 pub use contracts::*;
+pub use decoder::SUPPORTED_MADARA_DB_VERSIONS;
+pub use events::*;
+pub use mempool::*;
+pub use messaging::*;
+pub use meta::*;
+pub use preconfirmed::*;
+pub use proofs::*;
 pub use queries::*;
 pub use raw::*;
 pub use state_diff::*;
 pub use transactions::*;
+pub use trie_log::*;
+pub use tries::*;
+pub use verify::*;
 pub use version::*;
 
 #[derive(Error, Debug)]
@@ -28,6 +62,78 @@ pub enum DbError {
     PathNotFound(PathBuf),
     #[error("Deserialization error: {0}")]
     Deserialize(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+}
+
+/// A value exists in the database but could not be decoded
+#[derive(Error, Debug, Clone)]
+#[error("{cf} value at key 0x{key} failed to decode as {expected_type} at byte offset {offset}: {message}")]
+pub struct DecodeError {
+    /// Column family the value was read from
+    pub cf: String,
+    /// Hex-encoded key (without 0x prefix)
+    pub key: String,
+    /// Number of bytes consumed before decoding failed
+    pub offset: usize,
+    /// Madara type the value was expected to hold
+    pub expected_type: String,
+    /// Underlying bincode error
+    pub message: String,
+}
+
+/// Decode a bincode value read from `cf` at `key`, recording where decoding stopped on failure
+pub(crate) fn decode_value<T: serde::de::DeserializeOwned>(
+    cf: &str,
+    key: &[u8],
+    value: &[u8],
+) -> Result<T, DecodeError> {
+    use bincode::Options;
+
+    let error = |offset: usize, message: String| DecodeError {
+        cf: cf.to_string(),
+        key: hex::encode(key),
+        offset,
+        expected_type: expected_type_name::<T>(),
+        message,
+    };
+
+    // Reading through a slice advances it, so the remaining length tells us how far we got
+    let mut remaining = value;
+    let decoded = bincode::DefaultOptions::new()
+        .deserialize_from::<_, T>(&mut remaining)
+        .map_err(|e| error(value.len() - remaining.len(), e.to_string()))?;
+
+    if !remaining.is_empty() {
+        return Err(error(
+            value.len() - remaining.len(),
+            format!("{} trailing bytes", remaining.len()),
+        ));
+    }
+
+    Ok(decoded)
+}
+
+/// `RawTransactionWithReceipt` -> `TransactionWithReceipt`, matching the Madara type name.
+/// Module paths and `Raw` prefixes are dropped inside generic arguments and tuples too.
+fn expected_type_name<T>() -> String {
+    let full = std::any::type_name::<T>();
+    let is_delimiter = |c: char| "<>()[],;& ".contains(c);
+    let mut name = String::with_capacity(full.len());
+    let mut rest = full;
+    while !rest.is_empty() {
+        let end = rest.find(is_delimiter).unwrap_or(rest.len());
+        let path = &rest[..end];
+        let ident = path.rsplit("::").next().unwrap_or(path);
+        name.push_str(ident.strip_prefix("Raw").unwrap_or(ident));
+        // Delimiters are all ASCII
+        let next = (end + 1).min(rest.len());
+        name.push_str(&rest[end..next]);
+        rest = &rest[next..];
+    }
+    name
 }
 
 type DB = DBWithThreadMode<MultiThreaded>;
@@ -36,6 +142,9 @@ type DB = DBWithThreadMode<MultiThreaded>;
 pub struct DbReader {
     db: DB,
     path: PathBuf,
+    decoder: Box<dyn MadaraDecoder>,
+    /// Opened as a secondary instance that can catch up with a running node
+    secondary: bool,
 }
 
 impl DbReader {
@@ -56,7 +165,69 @@ impl DbReader {
         // Open in read-only mode with all existing column families
         let db = DB::open_cf_for_read_only(&opts, &path, cf_names.iter(), false)?;
 
-        Ok(Self { db, path })
+        Ok(Self::from_db(db, path, false))
+    }
+
+    /// Open a RocksDB database as a secondary instance of a running node.
+    ///
+    /// Unlike [`DbReader::open`], the view is not frozen: call
+    /// [`DbReader::catch_up_with_primary`] to pick up writes made since opening.
+    /// `secondary_path` is where RocksDB keeps the secondary's own info logs.
+    pub fn open_secondary(
+        path: impl AsRef<Path>,
+        secondary_path: impl AsRef<Path>,
+    ) -> Result<Self, DbError> {
+        let path = path.as_ref().to_path_buf();
+
+        if !path.exists() {
+            return Err(DbError::PathNotFound(path));
+        }
+
+        // Secondary instances must keep every file open to follow the primary
+        let mut opts = Options::default();
+        opts.set_max_open_files(-1);
+
+        let cf_names = DB::list_cf(&opts, &path).unwrap_or_default();
+        let db = DB::open_cf_as_secondary(
+            &opts,
+            path.as_path(),
+            secondary_path.as_ref(),
+            cf_names.iter(),
+        )?;
+
+        Ok(Self::from_db(db, path, true))
+    }
+
+    fn from_db(db: DB, path: PathBuf, secondary: bool) -> Self {
+        let decoder = decoder_for_version(detect_madara_db_version_for_db_path(&path).version);
+        Self {
+            db,
+            path,
+            decoder,
+            secondary,
+        }
+    }
+
+    /// Whether this reader was opened with [`DbReader::open_secondary`]
+    pub fn is_secondary(&self) -> bool {
+        self.secondary
+    }
+
+    /// Replay the primary's latest writes into a secondary instance.
+    ///
+    /// A no-op for read-only instances, which always see the snapshot taken at open.
+    pub fn catch_up_with_primary(&self) -> Result<(), DbError> {
+        if self.secondary {
+            self.db.try_catch_up_with_primary()?;
+        }
+        Ok(())
+    }
+
+    /// DB schema version whose layouts are used to decode values.
+    ///
+    /// Equals the detected version when it is supported, otherwise the newest supported one.
+    pub fn decoder_version(&self) -> u32 {
+        self.decoder.version()
     }
 
     /// Get the database path
@@ -75,3 +246,42 @@ impl DbReader {
         &self.db
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions::RawExecutionResult;
+
+    #[test]
+    fn names_types_like_madara() {
+        assert_eq!(
+            expected_type_name::<RawExecutionResult>(),
+            "ExecutionResult"
+        );
+        assert_eq!(
+            expected_type_name::<Vec<Option<RawExecutionResult>>>(),
+            "Vec<Option<ExecutionResult>>"
+        );
+        assert_eq!(expected_type_name::<(u32, u16)>(), "(u32, u16)");
+    }
+
+    #[test]
+    fn decode_errors_report_offset_and_trailing_bytes() {
+        // Some, then a variant ExecutionResult does not have
+        let err = decode_value::<Option<RawExecutionResult>>("cf", b"k", &[1, 7]).unwrap_err();
+        assert_eq!(err.cf, "cf");
+        assert_eq!(err.key, "6b");
+        assert_eq!(err.offset, 2);
+        assert_eq!(err.expected_type, "Option<ExecutionResult>");
+
+        let err = decode_value::<(u32, u16)>("tx_hash_to_index", b"k", &[4, 1, 0, 0]).unwrap_err();
+        assert_eq!(err.offset, 2);
+        assert_eq!(err.message, "2 trailing bytes");
+        assert_eq!(err.expected_type, "(u32, u16)");
+
+        assert_eq!(
+            decode_value::<(u32, u16)>("tx_hash_to_index", b"k", &[4, 1]).unwrap(),
+            (4, 1)
+        );
+    }
+}
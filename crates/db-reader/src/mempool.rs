@@ -0,0 +1,414 @@
+//! Mempool reading from `mempool_transactions`
+
+use crate::blocks::Felt;
+use crate::contracts::{parse_felt_hex, RawCompressedLegacyContractClass, RawSierraClassInfo};
+use crate::transactions::{
+    RawTransaction, ResourceBoundsMapping, TransactionBody, TransactionType,
+};
+use crate::{DbError, DbReader};
+use rocksdb::IteratorMode;
+use serde::Deserialize;
+use serde_bytes::ByteBuf;
+use std::collections::BTreeMap;
+
+/// Most nonces listed as missing for one account; a larger gap is reported by its size
+pub const MAX_LISTED_MISSING_NONCES: usize = 100;
+
+/// A validated transaction waiting in the mempool
+#[derive(Debug, Clone)]
+pub struct MempoolTransaction {
+    pub tx_hash: String,
+    pub tx_type: TransactionType,
+    /// Sender, or the address being deployed for DeployAccount
+    pub contract_address: String,
+    /// `None` for V0 transactions, which have no nonce
+    pub nonce: Option<u64>,
+    pub version: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub arrived_at: u128,
+    /// V0 to V2 only
+    pub max_fee: Option<String>,
+    /// V3 only
+    pub tip: Option<u64>,
+    /// V3 only
+    pub resource_bounds: Option<ResourceBoundsMapping>,
+    /// Fee paid on L1, for L1 handler transactions
+    pub paid_fee_on_l1: Option<u128>,
+    pub charge_fee: bool,
+    /// Class carried by a Declare transaction
+    pub declared_class_hash: Option<String>,
+    /// A nonce between the account's current nonce and this one is missing from the
+    /// mempool, so this transaction cannot be included yet
+    pub blocked_by_nonce_gap: bool,
+}
+
+/// Mempool nonces of one account, checked against its latest nonce
+#[derive(Debug, Clone)]
+pub struct MempoolAccount {
+    pub address: String,
+    /// Latest nonce from `contract_nonces`; `None` for accounts not deployed yet
+    pub current_nonce: Option<u64>,
+    /// Nonces of this account's mempool transactions, ascending
+    pub nonces: Vec<u64>,
+    /// Nonces needed before the highest pending one can be included, at most
+    /// [`MAX_LISTED_MISSING_NONCES`]
+    pub missing_nonces: Vec<u64>,
+    /// Total number of missing nonces
+    pub missing_count: u64,
+}
+
+/// Mempool ordering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MempoolSort {
+    /// By sender, then nonce
+    #[default]
+    Sender,
+    /// By nonce, then sender
+    Nonce,
+    /// Oldest first
+    Arrival,
+}
+
+impl MempoolSort {
+    pub fn parse(sort: &str) -> Result<Self, DbError> {
+        match sort {
+            "sender" => Ok(MempoolSort::Sender),
+            "nonce" => Ok(MempoolSort::Nonce),
+            "arrival" => Ok(MempoolSort::Arrival),
+            other => Err(DbError::InvalidInput(format!(
+                "Unknown mempool sort '{other}', expected sender, nonce or arrival"
+            ))),
+        }
+    }
+}
+
+/// Every mempool transaction, plus per-account nonce gaps
+#[derive(Debug, Clone, Default)]
+pub struct MempoolSnapshot {
+    pub transactions: Vec<MempoolTransaction>,
+    /// Sorted by address
+    pub accounts: Vec<MempoolAccount>,
+}
+
+// Raw deserialization types for mempool_transactions
+// ValidatedTransaction { transaction, paid_fee_on_l1, contract_address, arrived_at, declared_class,
+// hash, charge_fee }; the key is the bincode-serialized transaction hash
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawValidatedTransaction {
+    pub transaction: RawTransaction,
+    pub paid_fee_on_l1: Option<u128>,
+    pub contract_address: ByteBuf,
+    /// `TxTimestamp`: milliseconds since the Unix epoch
+    pub arrived_at: u128,
+    pub declared_class: Option<RawConvertedClass>,
+    pub hash: ByteBuf,
+    pub charge_fee: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) enum RawConvertedClass {
+    Legacy(RawLegacyConvertedClass),
+    Sierra(RawSierraConvertedClass),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawLegacyConvertedClass {
+    pub class_hash: ByteBuf,
+    pub info: RawLegacyClassInfo,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawLegacyClassInfo {
+    pub contract_class: RawCompressedLegacyContractClass,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawSierraConvertedClass {
+    pub class_hash: ByteBuf,
+    pub info: RawSierraClassInfo,
+    /// CASM JSON
+    pub compiled: String,
+}
+
+impl RawConvertedClass {
+    pub(crate) fn class_hash(&self) -> String {
+        match self {
+            RawConvertedClass::Legacy(c) => Felt::from_bytes(&c.class_hash).to_hex(),
+            RawConvertedClass::Sierra(c) => Felt::from_bytes(&c.class_hash).to_hex(),
+        }
+    }
+}
+
+impl RawValidatedTransaction {
+    pub(crate) fn to_mempool_transaction(&self) -> MempoolTransaction {
+        let (_, _, _, nonce, version) = self.transaction.get_tx_fields();
+        let (max_fee, v3) = match self.transaction.get_body() {
+            TransactionBody::Invoke { max_fee, v3, .. }
+            | TransactionBody::Declare { max_fee, v3, .. }
+            | TransactionBody::DeployAccount { max_fee, v3, .. } => (max_fee, v3),
+            TransactionBody::L1Handler { .. } | TransactionBody::Deploy { .. } => (None, None),
+        };
+
+        MempoolTransaction {
+            tx_hash: Felt::from_bytes(&self.hash).to_hex(),
+            tx_type: self.transaction.get_tx_type(),
+            contract_address: Felt::from_bytes(&self.contract_address).to_hex(),
+            nonce: nonce.as_deref().and_then(nonce_value),
+            version,
+            arrived_at: self.arrived_at,
+            max_fee,
+            tip: v3.as_ref().map(|v3| v3.tip),
+            resource_bounds: v3.map(|v3| v3.resource_bounds),
+            paid_fee_on_l1: self.paid_fee_on_l1,
+            charge_fee: self.charge_fee,
+            declared_class_hash: self
+                .declared_class
+                .as_ref()
+                .map(RawConvertedClass::class_hash),
+            blocked_by_nonce_gap: false,
+        }
+    }
+}
+
+fn nonce_value(nonce: &str) -> Option<u64> {
+    let felt = parse_felt_hex(nonce)?;
+    felt[..24]
+        .iter()
+        .all(|&b| b == 0)
+        .then(|| u64::from_be_bytes(felt[24..].try_into().unwrap()))
+}
+
+impl DbReader {
+    /// Read the whole mempool. Entries that fail to decode are skipped; the raw browser still
+    /// shows them.
+    pub fn get_mempool(&self, sort: MempoolSort) -> MempoolSnapshot {
+        let Some(cf) = self.db.cf_handle("mempool_transactions") else {
+            return MempoolSnapshot::default();
+        };
+
+        let mut transactions: Vec<MempoolTransaction> = self
+            .db
+            .iterator_cf(&cf, IteratorMode::Start)
+            .flatten()
+            .filter_map(|(key, value)| {
+                let raw = self.decoder.decode_mempool_transaction(&key, &value).ok()?;
+                Some(raw.to_mempool_transaction())
+            })
+            .collect();
+
+        let mut by_account: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+        for tx in &transactions {
+            if let Some(nonce) = tx.nonce {
+                by_account
+                    .entry(tx.contract_address.clone())
+                    .or_default()
+                    .push(nonce);
+            }
+        }
+
+        let accounts: Vec<MempoolAccount> = by_account
+            .into_iter()
+            .map(|(address, mut nonces)| {
+                nonces.sort_unstable();
+                nonces.dedup();
+                let current_nonce =
+                    parse_felt_hex(&address).and_then(|a| self.get_contract_nonce(&a, None));
+                let (missing_nonces, missing_count) = missing_nonces(current_nonce, &nonces);
+                MempoolAccount {
+                    address,
+                    current_nonce,
+                    nonces,
+                    missing_nonces,
+                    missing_count,
+                }
+            })
+            .collect();
+
+        for tx in &mut transactions {
+            let (Some(nonce), Ok(i)) = (
+                tx.nonce,
+                accounts.binary_search_by(|a| a.address.cmp(&tx.contract_address)),
+            ) else {
+                continue;
+            };
+            let account = &accounts[i];
+            let first_missing = match account.missing_nonces.first() {
+                Some(&first) => Some(first),
+                // Only the count survives when the listed nonces were capped at zero
+                None if account.missing_count > 0 => account.current_nonce,
+                None => None,
+            };
+            tx.blocked_by_nonce_gap = first_missing.is_some_and(|missing| missing < nonce);
+        }
+
+        match sort {
+            MempoolSort::Sender => transactions.sort_by(|a, b| {
+                (&a.contract_address, a.nonce).cmp(&(&b.contract_address, b.nonce))
+            }),
+            MempoolSort::Nonce => transactions.sort_by(|a, b| {
+                (a.nonce, &a.contract_address).cmp(&(b.nonce, &b.contract_address))
+            }),
+            MempoolSort::Arrival => transactions.sort_by_key(|tx| tx.arrived_at),
+        }
+
+        MempoolSnapshot {
+            transactions,
+            accounts,
+        }
+    }
+}
+
+/// Nonces missing between the account's current nonce (or its lowest pending nonce, for an
+/// account not deployed yet) and its highest pending nonce
+fn missing_nonces(current_nonce: Option<u64>, nonces: &[u64]) -> (Vec<u64>, u64) {
+    let (Some(&lowest), Some(&highest)) = (nonces.first(), nonces.last()) else {
+        return (vec![], 0);
+    };
+    let start = current_nonce.unwrap_or(lowest);
+    if highest < start {
+        return (vec![], 0);
+    }
+
+    let mut missing = Vec::new();
+    let mut count = 0u64;
+    let mut pending = nonces.iter().peekable();
+    let mut expected = start;
+    while expected <= highest {
+        match pending.peek() {
+            Some(&&nonce) if nonce < expected => {
+                // Already included on chain; the node has yet to evict it
+                pending.next();
+            }
+            Some(&&nonce) if nonce == expected => {
+                pending.next();
+                expected += 1;
+            }
+            Some(&&nonce) => {
+                count += nonce - expected;
+                missing.extend((expected..nonce).take(MAX_LISTED_MISSING_NONCES - missing.len()));
+                expected = nonce;
+            }
+            None => break,
+        }
+    }
+    (missing, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::open_db;
+    use bincode::Options;
+
+    /// bincode `ValidatedTransaction` holding an invoke V1 from `sender`
+    fn validated_invoke(sender: u8, nonce: u8, arrived_at: u128) -> (Vec<u8>, Vec<u8>) {
+        let felt = |b: u8| ByteBuf::from(vec![b]);
+        let hash = sender << 4 | nonce;
+        let value = bincode::DefaultOptions::new()
+            .serialize(&(
+                // Transaction::Invoke(InvokeTransaction::V1 { .. })
+                0u32,
+                1u32,
+                felt(sender),
+                vec![felt(1)],
+                felt(0x10),
+                Vec::<ByteBuf>::new(),
+                felt(nonce),
+                // paid_fee_on_l1, contract_address, arrived_at, declared_class, hash, charge_fee
+                None::<u128>,
+                felt(sender),
+                arrived_at,
+                None::<()>,
+                felt(hash),
+                true,
+            ))
+            .unwrap();
+        (vec![hash], value)
+    }
+
+    #[test]
+    fn reads_mempool_and_flags_nonce_gaps() {
+        let mut writes = vec![];
+        // 0xa is deployed with nonce 5, so its nonce 7 waits on 6; 0xb is not deployed yet
+        for (sender, nonce, arrived_at) in
+            [(0xa, 7, 100), (0xb, 0, 200), (0xa, 5, 300), (0xb, 1, 400)]
+        {
+            let (key, value) = validated_invoke(sender, nonce, arrived_at);
+            writes.push(("mempool_transactions", key, value));
+        }
+        let mut nonce_key = [0u8; 32].to_vec();
+        nonce_key[31] = 0xa;
+        nonce_key.extend_from_slice(&crate::encode_reversed_block(0));
+        let nonce = bincode::DefaultOptions::new()
+            .serialize(&ByteBuf::from(vec![5]))
+            .unwrap();
+        writes.push(("contract_nonces", nonce_key, nonce));
+        let (_dir, reader) = open_db(&writes);
+
+        let mempool = reader.get_mempool(MempoolSort::Sender);
+        let order: Vec<_> = mempool
+            .transactions
+            .iter()
+            .map(|tx| {
+                (
+                    tx.contract_address.as_str(),
+                    tx.nonce,
+                    tx.blocked_by_nonce_gap,
+                )
+            })
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("0x0a", Some(5), false),
+                ("0x0a", Some(7), true),
+                ("0x0b", Some(0), false),
+                ("0x0b", Some(1), false),
+            ]
+        );
+        let tx = &mempool.transactions[1];
+        assert_eq!(tx.tx_hash, "0xa7");
+        assert_eq!(tx.tx_type, TransactionType::Invoke);
+        assert_eq!(tx.arrived_at, 100);
+        assert_eq!(tx.max_fee.as_deref(), Some("0x10"));
+        assert!(tx.charge_fee);
+        assert_eq!(tx.paid_fee_on_l1, None);
+        assert_eq!(tx.declared_class_hash, None);
+
+        let account = &mempool.accounts[0];
+        assert_eq!(account.current_nonce, Some(5));
+        assert_eq!(account.missing_nonces, [6]);
+        assert_eq!(mempool.accounts[1].current_nonce, None);
+        assert_eq!(mempool.accounts[1].missing_count, 0);
+
+        let arrival: Vec<_> = reader
+            .get_mempool(MempoolSort::Arrival)
+            .transactions
+            .iter()
+            .map(|tx| tx.arrived_at)
+            .collect();
+        assert_eq!(arrival, [100, 200, 300, 400]);
+        let nonces: Vec<_> = reader
+            .get_mempool(MempoolSort::Nonce)
+            .transactions
+            .iter()
+            .map(|tx| tx.nonce)
+            .collect();
+        assert_eq!(nonces, [Some(0), Some(1), Some(5), Some(7)]);
+    }
+
+    #[test]
+    fn missing_nonces_start_at_the_account_nonce() {
+        assert_eq!(missing_nonces(Some(5), &[5, 6, 7]), (vec![], 0));
+        assert_eq!(missing_nonces(Some(5), &[7, 9]), (vec![5, 6, 8], 3));
+        // Stale nonces below the account nonce do not count as gaps
+        assert_eq!(missing_nonces(Some(5), &[3, 5]), (vec![], 0));
+        // Undeployed accounts are only checked between their own pending nonces
+        assert_eq!(missing_nonces(None, &[0, 2]), (vec![1], 1));
+
+        let (listed, count) = missing_nonces(Some(0), &[1_000_000]);
+        assert_eq!(listed.len(), MAX_LISTED_MISSING_NONCES);
+        assert_eq!(count, 1_000_000);
+    }
+}
@@ -0,0 +1,510 @@
+//! L1↔L2 messaging: L1→L2 messages from the `l1_to_l2_*` column families and L2→L1
+//! messages from transaction receipts.
+//!
+//! Both messaging column families are keyed by the core contract's L1→L2 nonce as a `u64`
+//! big-endian. `l1_to_l2_pending_message_by_nonce` holds a bincode
+//! `L1HandlerTransactionWithFee` until the message is included in a block; Madara then
+//! deletes it and writes the raw 32-byte L1 handler transaction hash to
+//! `l1_to_l2_txn_hash_by_nonce`.
+
+use std::collections::BTreeMap;
+
+use rocksdb::{Direction, IteratorMode};
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+
+use crate::transactions::{
+    RawL1HandlerTransaction, RawTransaction, RawTransactionReceipt, RawTransactionWithReceipt,
+};
+use crate::{DbError, DbReader, Felt};
+
+/// Most messages returned by one listing
+pub const MAX_MESSAGE_LIMIT: usize = 1000;
+
+/// Most blocks read by one L2→L1 listing
+pub const MAX_MESSAGE_SCAN_BLOCKS: u64 = 10_000;
+
+/// Where an L1→L2 message is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1ToL2MessageStatus {
+    /// Received from L1, waiting for its L1 handler transaction
+    Pending,
+    /// Consumed by an L1 handler transaction
+    Consumed,
+}
+
+impl std::fmt::Display for L1ToL2MessageStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            L1ToL2MessageStatus::Pending => write!(f, "PENDING"),
+            L1ToL2MessageStatus::Consumed => write!(f, "CONSUMED"),
+        }
+    }
+}
+
+/// An L1→L2 message and the L1 handler transaction that consumed it
+#[derive(Debug, Clone)]
+pub struct L1ToL2Message {
+    /// Core contract nonce
+    pub nonce: u64,
+    pub status: L1ToL2MessageStatus,
+    /// Hash the core contract tracks the message by; `None` when the handler transaction
+    /// could not be read
+    pub message_hash: Option<String>,
+    /// L1 sender, the first calldata felt of the handler
+    pub from_address: Option<String>,
+    /// L2 recipient
+    pub to_address: Option<String>,
+    pub entry_point_selector: Option<String>,
+    /// Calldata after the L1 sender
+    pub payload: Vec<String>,
+    /// Pending messages only; the fee of consumed ones is not stored
+    pub paid_fee_on_l1: Option<u128>,
+    pub tx_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub tx_index: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct L1ToL2MessageList {
+    pub messages: Vec<L1ToL2Message>,
+    /// Nonces skipped because their pending message or handler transaction failed to decode
+    pub undecodable_messages: u64,
+}
+
+/// An L2→L1 message sent by a transaction
+#[derive(Debug, Clone)]
+pub struct L2ToL1Message {
+    pub block_number: u64,
+    pub tx_index: usize,
+    /// Position of the message within its transaction receipt
+    pub message_index: usize,
+    pub tx_hash: String,
+    pub from_address: String,
+    pub to_address: String,
+    pub payload: Vec<String>,
+    /// Hash the core contract tracks the message by
+    pub message_hash: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct L2ToL1MessageList {
+    pub messages: Vec<L2ToL1Message>,
+    /// End of the range, lowered to the latest block
+    pub to_block: u64,
+    /// Set when the listing stopped at `limit` or after [`MAX_MESSAGE_SCAN_BLOCKS`] blocks;
+    /// resume from the block after it
+    pub last_block_scanned: Option<u64>,
+    /// Transactions skipped because they failed to decode
+    pub undecodable_transactions: u64,
+}
+
+// Raw deserialization types for l1_to_l2_pending_message_by_nonce
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawL1HandlerTransactionWithFee {
+    pub tx: RawL1HandlerTransaction,
+    pub paid_fee_on_l1: u128,
+}
+
+/// Values stored under one nonce in the two messaging column families
+#[derive(Default)]
+struct MessageEntries {
+    pending: Option<Box<[u8]>>,
+    tx_hash: Option<Box<[u8]>>,
+}
+
+/// Keccak-256 over 32-byte big-endian words, as the core contract packs message hashes
+fn keccak_words<'a>(words: impl IntoIterator<Item = &'a [u8; 32]>) -> String {
+    let packed: Vec<u8> = words.into_iter().flatten().copied().collect();
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Keccak256::digest(&packed));
+    format!("0x{}", hex::encode(hash))
+}
+
+fn u64_word(n: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&n.to_be_bytes());
+    word
+}
+
+/// `keccak256(from, to, nonce, selector, payload.len, payload)`, with `from` the L1 sender
+pub fn l1_to_l2_message_hash(
+    from_address: &[u8; 32],
+    to_address: &[u8; 32],
+    nonce: u64,
+    selector: &[u8; 32],
+    payload: &[[u8; 32]],
+) -> String {
+    let header = [
+        *from_address,
+        *to_address,
+        u64_word(nonce),
+        *selector,
+        u64_word(payload.len() as u64),
+    ];
+    keccak_words(header.iter().chain(payload))
+}
+
+/// `keccak256(from, to, payload.len, payload)`, with `from` the L2 sender
+pub fn l2_to_l1_message_hash(
+    from_address: &[u8; 32],
+    to_address: &[u8; 32],
+    payload: &[[u8; 32]],
+) -> String {
+    let header = [*from_address, *to_address, u64_word(payload.len() as u64)];
+    keccak_words(header.iter().chain(payload))
+}
+
+impl RawL1HandlerTransaction {
+    fn to_message(&self, status: L1ToL2MessageStatus) -> L1ToL2Message {
+        let calldata: Vec<[u8; 32]> = self
+            .calldata
+            .iter()
+            .map(|c| Felt::from_bytes(c).0)
+            .collect();
+        let to_address = Felt::from_bytes(&self.contract_address);
+        let selector = Felt::from_bytes(&self.entry_point_selector);
+        let message_hash = calldata.split_first().map(|(from, payload)| {
+            l1_to_l2_message_hash(from, &to_address.0, self.nonce, &selector.0, payload)
+        });
+
+        L1ToL2Message {
+            nonce: self.nonce,
+            status,
+            message_hash,
+            from_address: calldata.first().map(|from| Felt(*from).to_hex()),
+            to_address: Some(to_address.to_hex()),
+            entry_point_selector: Some(selector.to_hex()),
+            payload: calldata.iter().skip(1).map(|p| Felt(*p).to_hex()).collect(),
+            paid_fee_on_l1: None,
+            tx_hash: None,
+            block_number: None,
+            tx_index: None,
+        }
+    }
+}
+
+impl DbReader {
+    /// List L1→L2 messages by nonce, pending and consumed, starting at `from_nonce`
+    pub fn list_l1_to_l2_messages(
+        &self,
+        from_nonce: u64,
+        limit: usize,
+    ) -> Result<L1ToL2MessageList, DbError> {
+        let limit = limit.clamp(1, MAX_MESSAGE_LIMIT);
+        let pending =
+            self.messaging_entries("l1_to_l2_pending_message_by_nonce", from_nonce, limit);
+        let consumed = self.messaging_entries("l1_to_l2_txn_hash_by_nonce", from_nonce, limit);
+
+        // Both listings are sorted, so the first `limit` nonces of their union are complete
+        let mut by_nonce: BTreeMap<u64, MessageEntries> = BTreeMap::new();
+        for (nonce, value) in pending {
+            by_nonce.entry(nonce).or_default().pending = Some(value);
+        }
+        for (nonce, value) in consumed {
+            by_nonce.entry(nonce).or_default().tx_hash = Some(value);
+        }
+
+        let mut list = L1ToL2MessageList::default();
+        for (nonce, entries) in by_nonce.into_iter().take(limit) {
+            match self.l1_to_l2_message(nonce, entries.pending, entries.tx_hash) {
+                Ok(Some(message)) => list.messages.push(message),
+                Ok(None) => {}
+                Err(DbError::Decode(_)) => list.undecodable_messages += 1,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(list)
+    }
+
+    /// Get one L1→L2 message by its core contract nonce
+    pub fn get_l1_to_l2_message(&self, nonce: u64) -> Result<Option<L1ToL2Message>, DbError> {
+        let read = |cf_name: &str| -> Result<Option<Box<[u8]>>, DbError> {
+            let Some(cf) = self.db.cf_handle(cf_name) else {
+                return Ok(None);
+            };
+            Ok(self
+                .db
+                .get_cf(&cf, nonce.to_be_bytes())?
+                .map(Vec::into_boxed_slice))
+        };
+        let pending = read("l1_to_l2_pending_message_by_nonce")?;
+        let tx_hash = read("l1_to_l2_txn_hash_by_nonce")?;
+        self.l1_to_l2_message(nonce, pending, tx_hash)
+    }
+
+    fn messaging_entries(
+        &self,
+        cf_name: &str,
+        from_nonce: u64,
+        limit: usize,
+    ) -> Vec<(u64, Box<[u8]>)> {
+        let Some(cf) = self.db.cf_handle(cf_name) else {
+            return vec![];
+        };
+        let start = from_nonce.to_be_bytes();
+        self.db
+            .iterator_cf(&cf, IteratorMode::From(&start, Direction::Forward))
+            .flatten()
+            .filter_map(|(key, value)| {
+                let nonce = u64::from_be_bytes(key.as_ref().try_into().ok()?);
+                Some((nonce, value))
+            })
+            .take(limit)
+            .collect()
+    }
+
+    fn l1_to_l2_message(
+        &self,
+        nonce: u64,
+        pending: Option<Box<[u8]>>,
+        tx_hash: Option<Box<[u8]>>,
+    ) -> Result<Option<L1ToL2Message>, DbError> {
+        let key = nonce.to_be_bytes();
+
+        let tx_hash = match (pending, tx_hash) {
+            (_, Some(tx_hash)) => tx_hash,
+            (Some(pending), None) => {
+                let raw = self.decoder.decode_pending_message(&key, &pending)?;
+                let mut message = raw.tx.to_message(L1ToL2MessageStatus::Pending);
+                message.paid_fee_on_l1 = Some(raw.paid_fee_on_l1);
+                return Ok(Some(message));
+            }
+            (None, None) => return Ok(None),
+        };
+
+        // Consumed: follow the hash to the L1 handler transaction for the message itself
+        let tx_hash = Felt::from_bytes(&tx_hash).to_hex();
+        let location = self.find_transaction_by_hash(&tx_hash);
+        let handler = match location {
+            Some((block_n, tx_index)) => self.get_raw_transaction(block_n, tx_index)?,
+            None => None,
+        };
+
+        let mut message = match &handler {
+            Some(RawTransactionWithReceipt {
+                transaction: RawTransaction::L1Handler(tx),
+                receipt,
+            }) => {
+                let mut message = tx.to_message(L1ToL2MessageStatus::Consumed);
+                if let RawTransactionReceipt::L1Handler(receipt) = receipt {
+                    message.message_hash = Some(format!("0x{}", hex::encode(receipt.message_hash)));
+                }
+                message
+            }
+            _ => L1ToL2Message {
+                nonce,
+                status: L1ToL2MessageStatus::Consumed,
+                message_hash: None,
+                from_address: None,
+                to_address: None,
+                entry_point_selector: None,
+                payload: vec![],
+                paid_fee_on_l1: None,
+                tx_hash: None,
+                block_number: None,
+                tx_index: None,
+            },
+        };
+        message.nonce = nonce;
+        message.tx_hash = Some(tx_hash);
+        message.block_number = location.map(|(block_n, _)| block_n);
+        message.tx_index = location.map(|(_, tx_index)| tx_index);
+        Ok(Some(message))
+    }
+
+    /// List the L2→L1 messages sent in a block range, oldest first. `to_block` is lowered to
+    /// the latest block.
+    pub fn list_l2_to_l1_messages(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        limit: usize,
+    ) -> Result<L2ToL1MessageList, DbError> {
+        if from_block > to_block {
+            return Err(DbError::InvalidInput(format!(
+                "from_block {from_block} is after to_block {to_block}"
+            )));
+        }
+        let limit = limit.clamp(1, MAX_MESSAGE_LIMIT);
+        let to_block = to_block.min(self.get_latest_block_number().unwrap_or(0));
+        let mut list = L2ToL1MessageList {
+            to_block,
+            ..Default::default()
+        };
+        if from_block > to_block {
+            return Ok(list);
+        }
+        let scan_to = to_block.min(from_block.saturating_add(MAX_MESSAGE_SCAN_BLOCKS - 1));
+
+        for block_n in from_block..=scan_to {
+            for (tx_index, tx) in self.get_raw_block_transactions(block_n) {
+                let Ok(tx) = tx else {
+                    list.undecodable_transactions += 1;
+                    continue;
+                };
+                let messages = tx.raw_messages();
+                if messages.is_empty() {
+                    continue;
+                }
+                let tx_hash = tx.get_tx_hash();
+                for (message_index, m) in messages.iter().enumerate() {
+                    let from = Felt::from_bytes(&m.from_address);
+                    let to = Felt::from_bytes(&m.to_address);
+                    let payload: Vec<[u8; 32]> =
+                        m.payload.iter().map(|p| Felt::from_bytes(p).0).collect();
+                    list.messages.push(L2ToL1Message {
+                        block_number: block_n,
+                        tx_index,
+                        message_index,
+                        tx_hash: tx_hash.clone(),
+                        from_address: from.to_hex(),
+                        to_address: to.to_hex(),
+                        message_hash: l2_to_l1_message_hash(&from.0, &to.0, &payload),
+                        payload: payload.iter().map(|p| Felt(*p).to_hex()).collect(),
+                    });
+                }
+            }
+            // Whole blocks only, so resuming from the next block misses nothing
+            if list.messages.len() >= limit && block_n < to_block {
+                list.last_block_scanned = Some(block_n);
+                return Ok(list);
+            }
+        }
+        if scan_to < to_block {
+            list.last_block_scanned = Some(scan_to);
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::L1_HANDLER;
+    use crate::test_utils::{chain_tip, open_db};
+
+    fn felt(n: u64) -> [u8; 32] {
+        u64_word(n)
+    }
+
+    #[test]
+    fn message_hashes_pack_words_like_the_core_contract() {
+        // keccak256(abi.encodePacked(uint256(1), uint256(2), uint256(1), uint256(7)))
+        let mut packed = Vec::new();
+        for word in [felt(1), felt(2), felt(1), felt(7)] {
+            packed.extend_from_slice(&word);
+        }
+        let expected = format!("0x{}", hex::encode(Keccak256::digest(&packed)));
+        assert_eq!(
+            l2_to_l1_message_hash(&felt(1), &felt(2), &[felt(7)]),
+            expected
+        );
+
+        let mut packed = Vec::new();
+        for word in [felt(1), felt(2), felt(3), felt(4), felt(0)] {
+            packed.extend_from_slice(&word);
+        }
+        let expected = format!("0x{}", hex::encode(Keccak256::digest(&packed)));
+        assert_eq!(
+            l1_to_l2_message_hash(&felt(1), &felt(2), 3, &felt(4), &[]),
+            expected
+        );
+    }
+
+    #[test]
+    fn l2_to_l1_listing_is_bounded_by_the_chain() {
+        let (_dir, reader) = open_db(&[chain_tip(20_000)]);
+
+        let list = reader.list_l2_to_l1_messages(0, u64::MAX, 100).unwrap();
+        assert_eq!(list.to_block, 20_000);
+        assert_eq!(list.last_block_scanned, Some(MAX_MESSAGE_SCAN_BLOCKS - 1));
+
+        let list = reader
+            .list_l2_to_l1_messages(15_000, u64::MAX, 100)
+            .unwrap();
+        assert_eq!(list.last_block_scanned, None);
+        let list = reader
+            .list_l2_to_l1_messages(30_000, u64::MAX, 100)
+            .unwrap();
+        assert!(list.messages.is_empty());
+    }
+
+    #[test]
+    fn l1_to_l2_listing_merges_pending_and_consumed() {
+        // L1 handler with nonce 3 calling 0xabcd of 0x1234 with [0xeeee, 5], paid 1000 wei
+        let pending = hex::decode("000302123402abcd0202eeee0105fbe803").unwrap();
+        let handler_hash =
+            hex::decode("068dc917fe3e96612b4547f85853c3021c49059c9ca5b49a8d7b5425765ab6fe")
+                .unwrap();
+        let (_dir, reader) = open_db(&[
+            (
+                "l1_to_l2_pending_message_by_nonce",
+                3u64.to_be_bytes().to_vec(),
+                pending,
+            ),
+            (
+                "l1_to_l2_pending_message_by_nonce",
+                5u64.to_be_bytes().to_vec(),
+                vec![0xff],
+            ),
+            (
+                "l1_to_l2_txn_hash_by_nonce",
+                7u64.to_be_bytes().to_vec(),
+                handler_hash.clone(),
+            ),
+            // Block 4, transaction 1
+            ("tx_hash_to_index", handler_hash, vec![4, 1]),
+            (
+                "block_transactions",
+                vec![0, 0, 0, 4, 0, 1],
+                hex::decode(L1_HANDLER).unwrap(),
+            ),
+        ]);
+
+        let list = reader.list_l1_to_l2_messages(0, 100).unwrap();
+        assert_eq!(list.undecodable_messages, 1);
+        let [pending, consumed] = &list.messages[..] else {
+            panic!("expected two messages, got {:?}", list.messages);
+        };
+
+        assert_eq!(pending.nonce, 3);
+        assert_eq!(pending.status, L1ToL2MessageStatus::Pending);
+        assert_eq!(pending.paid_fee_on_l1, Some(1000));
+        assert_eq!(pending.from_address.as_deref(), Some("0xeeee"));
+        assert_eq!(pending.to_address.as_deref(), Some("0x1234"));
+        assert_eq!(pending.payload, ["0x05"]);
+        assert_eq!(
+            pending.message_hash,
+            Some(l1_to_l2_message_hash(
+                &felt(0xeeee),
+                &felt(0x1234),
+                3,
+                &felt(0xabcd),
+                &[felt(5)]
+            ))
+        );
+        assert_eq!(pending.tx_hash, None);
+
+        assert_eq!(consumed.nonce, 7);
+        assert_eq!(consumed.status, L1ToL2MessageStatus::Consumed);
+        assert_eq!(
+            consumed.tx_hash.as_deref(),
+            Some("0x068dc917fe3e96612b4547f85853c3021c49059c9ca5b49a8d7b5425765ab6fe")
+        );
+        assert_eq!(consumed.block_number, Some(4));
+        assert_eq!(consumed.tx_index, Some(1));
+        // Taken from the handler receipt rather than recomputed
+        assert_eq!(
+            consumed.message_hash,
+            Some(format!("0x{}", "11".repeat(32)))
+        );
+        assert_eq!(consumed.payload, ["0x01", "0x02"]);
+        assert_eq!(consumed.paid_fee_on_l1, None);
+
+        let list = reader.list_l1_to_l2_messages(4, 100).unwrap();
+        assert_eq!(list.messages.len(), 1);
+        assert_eq!(list.messages[0].nonce, 7);
+    }
+}
@@ -0,0 +1,352 @@
+//! Typed decoding of the `meta` column family.
+//!
+//! `meta` holds one value per well-known ASCII key. Most are bincode; the block markers
+//! written by the L1 sync and the trie updater have been stored both as bincode varints and
+//! as raw `u64` big-endian, so both are accepted.
+
+use rocksdb::IteratorMode;
+use serde::Deserialize;
+use serde_bytes::ByteBuf;
+
+use crate::blocks::{RawGasPrices, RawL1DataAvailabilityMode, RawStarknetVersion};
+use crate::{decode_value, DbError, DbReader, DecodeError, Felt, GasPrices};
+
+pub const META_CHAIN_TIP: &str = "CHAIN_TIP";
+pub const META_CHAIN_INFO: &str = "CHAIN_INFO";
+pub const META_LAST_SYNCED_L1_EVENT_BLOCK: &str = "LAST_SYNCED_L1_EVENT_BLOCK";
+pub const META_CONFIRMED_ON_L1_TIP: &str = "CONFIRMED_ON_L1_TIP";
+pub const META_LATEST_APPLIED_TRIE_UPDATE: &str = "LATEST_APPLIED_TRIE_UPDATE";
+pub const META_DEVNET_KEYS: &str = "DEVNET_KEYS";
+pub const META_RUNTIME_EXEC_CONFIG: &str = "RUNTIME_EXEC_CONFIG";
+pub const META_SNAP_SYNC_LATEST_BLOCK: &str = "SNAP_SYNC_LATEST_BLOCK";
+
+/// Where the node's chain currently ends
+#[derive(Debug, Clone)]
+pub enum ChainTip {
+    /// Last closed block
+    Confirmed(u64),
+    /// A block is being built on top of the confirmed chain; its transactions are in
+    /// `preconfirmed`
+    Preconfirmed(PreconfirmedHeader),
+}
+
+/// Header of the block under construction
+#[derive(Debug, Clone)]
+pub struct PreconfirmedHeader {
+    pub block_number: u64,
+    pub sequencer_address: String,
+    pub timestamp: u64,
+    pub protocol_version: String,
+    pub gas_prices: GasPrices,
+    /// "CALLDATA" or "BLOB"
+    pub l1_da_mode: String,
+}
+
+/// Network this database belongs to
+#[derive(Debug, Clone)]
+pub struct ChainInfo {
+    pub chain_id: String,
+    pub chain_name: String,
+}
+
+/// Predeployed devnet account. The private key is decoded but only public fields are
+/// meant to be shown.
+#[derive(Debug, Clone)]
+pub struct DevnetAccount {
+    pub address: String,
+    pub private_key: String,
+    pub public_key: String,
+    pub class_hash: String,
+}
+
+/// Execution configuration saved with the block under construction
+#[derive(Debug, Clone)]
+pub struct RuntimeExecConfig {
+    /// The node's chain config, as YAML
+    pub chain_config: Option<String>,
+    pub protocol_version: String,
+    pub no_charge_fee: bool,
+}
+
+/// A `meta` value that failed to decode
+#[derive(Debug, Clone)]
+pub struct MetaDecodeFailure {
+    pub key: String,
+    pub error: DecodeError,
+}
+
+/// Every known `meta` key, decoded
+#[derive(Debug, Clone, Default)]
+pub struct NodeMeta {
+    pub chain_tip: Option<ChainTip>,
+    pub chain_info: Option<ChainInfo>,
+    /// Hash of block 0, from `block_info`
+    pub genesis_block_hash: Option<String>,
+    /// Latest closed block, from the chain tip or `block_info`
+    pub latest_block: Option<u64>,
+    /// Latest block whose state update was seen on L1
+    pub confirmed_on_l1_tip: Option<u64>,
+    /// L1 block the messaging sync has processed events up to
+    pub last_synced_l1_event_block: Option<u64>,
+    /// Latest block applied to the bonsai tries
+    pub latest_applied_trie_update: Option<u64>,
+    /// Block at which snap sync computed the tries
+    pub snap_sync_latest_block: Option<u64>,
+    pub devnet_accounts: Vec<DevnetAccount>,
+    pub runtime_exec_config: Option<RuntimeExecConfig>,
+    /// Keys outside the known set
+    pub unknown_keys: Vec<String>,
+    pub failures: Vec<MetaDecodeFailure>,
+}
+
+impl NodeMeta {
+    /// Closed blocks not yet confirmed on L1
+    pub fn l1_confirmation_lag(&self) -> Option<u64> {
+        Some(self.latest_block?.saturating_sub(self.confirmed_on_l1_tip?))
+    }
+
+    /// Closed blocks not yet applied to the tries
+    pub fn trie_lag(&self) -> Option<u64> {
+        Some(
+            self.latest_block?
+                .saturating_sub(self.latest_applied_trie_update?),
+        )
+    }
+}
+
+// Raw deserialization types for meta values
+
+/// `StoredChainTipWithoutContent`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) enum RawStoredChainTip {
+    Confirmed(u64),
+    Preconfirmed(RawPreconfirmedHeader),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawPreconfirmedHeader {
+    pub block_number: u64,
+    pub sequencer_address: ByteBuf,
+    pub block_timestamp: u64,
+    pub protocol_version: RawStarknetVersion,
+    pub gas_prices: RawGasPrices,
+    pub l1_da_mode: RawL1DataAvailabilityMode,
+}
+
+impl From<RawPreconfirmedHeader> for PreconfirmedHeader {
+    fn from(raw: RawPreconfirmedHeader) -> Self {
+        Self {
+            block_number: raw.block_number,
+            sequencer_address: Felt::from_bytes(&raw.sequencer_address).to_hex(),
+            timestamp: raw.block_timestamp,
+            protocol_version: raw.protocol_version.to_string(),
+            gas_prices: raw.gas_prices.into(),
+            l1_da_mode: raw.l1_da_mode.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawStoredChainInfo {
+    pub chain_id: String,
+    pub chain_name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawDevnetPredeployedAccount {
+    pub address: ByteBuf,
+    pub secret: ByteBuf,
+    pub pubkey: ByteBuf,
+    pub class_hash: ByteBuf,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawRuntimeExecConfig {
+    pub chain_config: Option<String>,
+    pub protocol_version: String,
+    pub no_charge_fee: bool,
+}
+
+/// A decoded `meta` value, by key
+#[derive(Debug, Clone)]
+pub(crate) enum RawMetaValue {
+    ChainTip(RawStoredChainTip),
+    ChainInfo(RawStoredChainInfo),
+    /// One of the block number markers
+    BlockMarker(u64),
+    DevnetKeys(Vec<RawDevnetPredeployedAccount>),
+    RuntimeExecConfig(RawRuntimeExecConfig),
+    Unknown,
+}
+
+/// Decode a `meta` value according to its key
+pub(crate) fn decode_meta(key: &[u8], value: &[u8]) -> Result<RawMetaValue, DecodeError> {
+    match std::str::from_utf8(key).unwrap_or_default() {
+        META_CHAIN_TIP => decode_value("meta", key, value).map(RawMetaValue::ChainTip),
+        META_CHAIN_INFO => decode_value("meta", key, value).map(RawMetaValue::ChainInfo),
+        META_DEVNET_KEYS => decode_value("meta", key, value).map(RawMetaValue::DevnetKeys),
+        META_RUNTIME_EXEC_CONFIG => {
+            decode_value("meta", key, value).map(RawMetaValue::RuntimeExecConfig)
+        }
+        META_LAST_SYNCED_L1_EVENT_BLOCK
+        | META_CONFIRMED_ON_L1_TIP
+        | META_LATEST_APPLIED_TRIE_UPDATE
+        | META_SNAP_SYNC_LATEST_BLOCK => {
+            decode_block_marker(key, value).map(RawMetaValue::BlockMarker)
+        }
+        _ => Ok(RawMetaValue::Unknown),
+    }
+}
+
+/// A varint is never 8 bytes long, so an 8-byte value is a raw big-endian `u64`
+fn decode_block_marker(key: &[u8], value: &[u8]) -> Result<u64, DecodeError> {
+    if let Ok(bytes) = <[u8; 8]>::try_from(value) {
+        return Ok(u64::from_be_bytes(bytes));
+    }
+    decode_value::<u64>("meta", key, value).or_else(|e| {
+        decode_value::<Option<u64>>("meta", key, value)
+            .ok()
+            .flatten()
+            .ok_or(e)
+    })
+}
+
+impl DbReader {
+    /// Decode the `CHAIN_TIP` entry
+    pub fn get_chain_tip(&self) -> Result<Option<ChainTip>, DbError> {
+        let Some(cf) = self.db.cf_handle("meta") else {
+            return Ok(None);
+        };
+        let Some(value) = self.db.get_cf(&cf, META_CHAIN_TIP)? else {
+            return Ok(None);
+        };
+        match self
+            .decoder
+            .decode_meta(META_CHAIN_TIP.as_bytes(), &value)?
+        {
+            RawMetaValue::ChainTip(tip) => Ok(Some(tip.into())),
+            _ => Ok(None),
+        }
+    }
+
+    /// Decode every `meta` entry. A value that fails to decode is reported in
+    /// [`NodeMeta::failures`] instead of failing the whole read.
+    pub fn get_node_meta(&self) -> Result<NodeMeta, DbError> {
+        let mut meta = NodeMeta {
+            latest_block: self.get_latest_block_number(),
+            genesis_block_hash: self
+                .get_block_summary(0)
+                .ok()
+                .flatten()
+                .map(|b| b.block_hash),
+            ..Default::default()
+        };
+        let Some(cf) = self.db.cf_handle("meta") else {
+            return Ok(meta);
+        };
+
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = item?;
+            let key_name = String::from_utf8_lossy(&key).into_owned();
+            let decoded = match self.decoder.decode_meta(&key, &value) {
+                Ok(decoded) => decoded,
+                Err(error) => {
+                    meta.failures.push(MetaDecodeFailure {
+                        key: key_name,
+                        error,
+                    });
+                    continue;
+                }
+            };
+
+            match decoded {
+                RawMetaValue::ChainTip(tip) => meta.chain_tip = Some(tip.into()),
+                RawMetaValue::ChainInfo(info) => {
+                    meta.chain_info = Some(ChainInfo {
+                        chain_id: info.chain_id,
+                        chain_name: info.chain_name,
+                    })
+                }
+                RawMetaValue::BlockMarker(block_n) => {
+                    let marker = match key_name.as_str() {
+                        META_CONFIRMED_ON_L1_TIP => &mut meta.confirmed_on_l1_tip,
+                        META_LAST_SYNCED_L1_EVENT_BLOCK => &mut meta.last_synced_l1_event_block,
+                        META_LATEST_APPLIED_TRIE_UPDATE => &mut meta.latest_applied_trie_update,
+                        _ => &mut meta.snap_sync_latest_block,
+                    };
+                    *marker = Some(block_n);
+                }
+                RawMetaValue::DevnetKeys(accounts) => {
+                    meta.devnet_accounts = accounts
+                        .into_iter()
+                        .map(|a| DevnetAccount {
+                            address: Felt::from_bytes(&a.address).to_hex(),
+                            private_key: Felt::from_bytes(&a.secret).to_hex(),
+                            public_key: Felt::from_bytes(&a.pubkey).to_hex(),
+                            class_hash: Felt::from_bytes(&a.class_hash).to_hex(),
+                        })
+                        .collect()
+                }
+                RawMetaValue::RuntimeExecConfig(config) => {
+                    meta.runtime_exec_config = Some(RuntimeExecConfig {
+                        chain_config: config.chain_config,
+                        protocol_version: config.protocol_version,
+                        no_charge_fee: config.no_charge_fee,
+                    })
+                }
+                RawMetaValue::Unknown => meta.unknown_keys.push(key_name),
+            }
+        }
+        Ok(meta)
+    }
+}
+
+impl From<RawStoredChainTip> for ChainTip {
+    fn from(raw: RawStoredChainTip) -> Self {
+        match raw {
+            RawStoredChainTip::Confirmed(block_n) => ChainTip::Confirmed(block_n),
+            RawStoredChainTip::Preconfirmed(header) => ChainTip::Preconfirmed(header.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_meta_values_by_key() {
+        // Confirmed(184)
+        let tip = decode_meta(b"CHAIN_TIP", &[0x00, 0xb8]).unwrap();
+        assert!(matches!(
+            tip,
+            RawMetaValue::ChainTip(RawStoredChainTip::Confirmed(184))
+        ));
+
+        let mut chain_info = vec![0x0a];
+        chain_info.extend_from_slice(b"POC_DEVNET");
+        chain_info.push(0x06);
+        chain_info.extend_from_slice(b"Madara");
+        let RawMetaValue::ChainInfo(info) = decode_meta(b"CHAIN_INFO", &chain_info).unwrap() else {
+            panic!("expected chain info");
+        };
+        assert_eq!(info.chain_id, "POC_DEVNET");
+        assert_eq!(info.chain_name, "Madara");
+
+        // Block markers: raw big-endian, bincode varint, or bincode Some(varint)
+        for value in [
+            &1000u64.to_be_bytes()[..],
+            &[0xfb, 0xe8, 0x03],
+            &[0x01, 0xfb, 0xe8, 0x03],
+        ] {
+            let marker = decode_meta(b"CONFIRMED_ON_L1_TIP", value).unwrap();
+            assert!(matches!(marker, RawMetaValue::BlockMarker(1000)));
+        }
+
+        assert!(matches!(
+            decode_meta(b"SOMETHING_NEW", &[1, 2, 3]).unwrap(),
+            RawMetaValue::Unknown
+        ));
+        assert!(decode_meta(b"CHAIN_TIP", &[0x07]).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! The block under construction, from `preconfirmed`
+//!
+//! While the sequencer builds a block, `CHAIN_TIP` holds its header and every executed
+//! transaction is appended to `preconfirmed`, keyed by its index as a big-endian `u16`.
+//! Nothing reaches `block_info` or `block_transactions` until the block is closed.
+
+use crate::blocks::Felt;
+use crate::mempool::RawConvertedClass;
+use crate::meta::{ChainTip, PreconfirmedHeader};
+use crate::state_diff::{
+    ContractStorageDiff, DeclaredClass, DeployedContract, NonceUpdateInfo, ReplacedClass,
+    StateDiffInfo, StorageDiffEntry,
+};
+use crate::transactions::{RawTransactionWithReceipt, TransactionDetail};
+use crate::{DbError, DbReader};
+use rocksdb::IteratorMode;
+use serde::Deserialize;
+use serde_bytes::ByteBuf;
+
+/// The block being built when the database was written
+#[derive(Debug, Clone)]
+pub struct PreconfirmedBlock {
+    pub header: PreconfirmedHeader,
+    /// In execution order
+    pub transactions: Vec<PreconfirmedTransaction>,
+    /// Entries that failed to decode; the raw browser still shows them
+    pub undecodable_transactions: u64,
+}
+
+/// A transaction executed into the block under construction
+#[derive(Debug, Clone)]
+pub struct PreconfirmedTransaction {
+    /// Transaction and receipt; `block_number` is the preconfirmed block
+    pub detail: TransactionDetail,
+    /// State changes made by this transaction alone
+    pub state_diff: StateDiffInfo,
+    /// Class carried by a Declare transaction
+    pub declared_class_hash: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub arrived_at: u128,
+    /// Fee paid on L1, for L1 handler transactions
+    pub paid_fee_on_l1: Option<u128>,
+}
+
+// Raw deserialization types for preconfirmed
+// PreconfirmedExecutedTransaction { transaction, state_diff, declared_class, arrived_at,
+// paid_fee_on_l1 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawPreconfirmedExecutedTransaction {
+    pub transaction: RawTransactionWithReceipt,
+    pub state_diff: RawTransactionStateUpdate,
+    pub declared_class: Option<RawConvertedClass>,
+    /// `TxTimestamp`: milliseconds since the Unix epoch
+    pub arrived_at: u128,
+    pub paid_fee_on_l1: Option<u128>,
+}
+
+/// `TransactionStateUpdate`: hash maps, read as lists of pairs
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RawTransactionStateUpdate {
+    pub nonces: Vec<(ByteBuf, ByteBuf)>,
+    /// `(contract address, key) -> value`
+    pub storage_diffs: Vec<((ByteBuf, ByteBuf), ByteBuf)>,
+    pub contract_class_hashes: Vec<(ByteBuf, RawDeployedOrReplaced)>,
+    pub declared_classes: Vec<(ByteBuf, RawDeclaredClassCompiledClass)>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) enum RawDeployedOrReplaced {
+    Deployed(ByteBuf),
+    Replaced(ByteBuf),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) enum RawDeclaredClassCompiledClass {
+    Sierra(ByteBuf),
+    Legacy,
+}
+
+impl RawTransactionStateUpdate {
+    fn to_state_diff(&self) -> StateDiffInfo {
+        let hex = |bytes: &ByteBuf| Felt::from_bytes(bytes).to_hex();
+
+        let mut storage_diffs: Vec<ContractStorageDiff> = Vec::new();
+        for ((address, key), value) in &self.storage_diffs {
+            let address = hex(address);
+            let entry = StorageDiffEntry {
+                key: hex(key),
+                value: hex(value),
+            };
+            match storage_diffs.iter_mut().find(|d| d.address == address) {
+                Some(diff) => diff.storage_entries.push(entry),
+                None => storage_diffs.push(ContractStorageDiff {
+                    address,
+                    storage_entries: vec![entry],
+                }),
+            }
+        }
+
+        let mut deployed_contracts = Vec::new();
+        let mut replaced_classes = Vec::new();
+        for (address, class) in &self.contract_class_hashes {
+            match class {
+                RawDeployedOrReplaced::Deployed(class_hash) => {
+                    deployed_contracts.push(DeployedContract {
+                        address: hex(address),
+                        class_hash: hex(class_hash),
+                    })
+                }
+                RawDeployedOrReplaced::Replaced(class_hash) => {
+                    replaced_classes.push(ReplacedClass {
+                        contract_address: hex(address),
+                        class_hash: hex(class_hash),
+                    })
+                }
+            }
+        }
+
+        StateDiffInfo {
+            deployed_contracts,
+            storage_diffs,
+            declared_classes: self
+                .declared_classes
+                .iter()
+                .map(|(class_hash, compiled)| DeclaredClass {
+                    class_hash: hex(class_hash),
+                    // Legacy classes have no compiled class hash
+                    compiled_class_hash: match compiled {
+                        RawDeclaredClassCompiledClass::Sierra(hash) => hex(hash),
+                        RawDeclaredClassCompiledClass::Legacy => "0x00".to_string(),
+                    },
+                })
+                .collect(),
+            nonces: self
+                .nonces
+                .iter()
+                .map(|(address, nonce)| NonceUpdateInfo {
+                    contract_address: hex(address),
+                    nonce: hex(nonce),
+                })
+                .collect(),
+            replaced_classes,
+        }
+    }
+}
+
+impl DbReader {
+    /// Read the block under construction. Returns `None` unless `CHAIN_TIP` is
+    /// `Preconfirmed`; entries left in `preconfirmed` behind a confirmed tip belong to a
+    /// block that was already closed.
+    pub fn get_preconfirmed_block(&self) -> Result<Option<PreconfirmedBlock>, DbError> {
+        let Some(ChainTip::Preconfirmed(header)) = self.get_chain_tip()? else {
+            return Ok(None);
+        };
+        let mut block = PreconfirmedBlock {
+            header,
+            transactions: Vec::new(),
+            undecodable_transactions: 0,
+        };
+        let Some(cf) = self.db.cf_handle("preconfirmed") else {
+            return Ok(Some(block));
+        };
+
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = item?;
+            let (Ok(index), Ok(raw)) = (
+                <[u8; 2]>::try_from(&key[..]).map(u16::from_be_bytes),
+                self.decoder.decode_preconfirmed_transaction(&key, &value),
+            ) else {
+                block.undecodable_transactions += 1;
+                continue;
+            };
+
+            block.transactions.push(PreconfirmedTransaction {
+                detail: raw
+                    .transaction
+                    .to_detail(block.header.block_number, usize::from(index)),
+                state_diff: raw.state_diff.to_state_diff(),
+                declared_class_hash: raw
+                    .declared_class
+                    .as_ref()
+                    .map(RawConvertedClass::class_hash),
+                arrived_at: raw.arrived_at,
+                paid_fee_on_l1: raw.paid_fee_on_l1,
+            });
+        }
+        Ok(Some(block))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bincode::Options;
+
+    #[test]
+    fn groups_storage_diffs_by_contract() {
+        let felt = |v: u8| ByteBuf::from(vec![v]);
+        let bytes = bincode::DefaultOptions::new()
+            .serialize(&(
+                vec![(felt(1), felt(5))],
+                vec![
+                    ((felt(2), felt(10)), felt(11)),
+                    ((felt(3), felt(20)), felt(21)),
+                    ((felt(2), felt(12)), felt(13)),
+                ],
+                // One deployed contract: `Deployed` is variant 0
+                vec![(felt(4), (0u32, felt(9)))],
+                // One legacy declare: `Legacy` is variant 1
+                vec![(felt(6), 1u32)],
+            ))
+            .unwrap();
+        let raw: RawTransactionStateUpdate =
+            crate::decode_value("preconfirmed", &[0, 0], &bytes).unwrap();
+        let diff = raw.to_state_diff();
+
+        assert_eq!(diff.nonces[0].nonce, "0x05");
+        assert_eq!(diff.storage_diffs.len(), 2);
+        assert_eq!(diff.storage_diffs[0].address, "0x02");
+        assert_eq!(diff.storage_diffs[0].storage_entries.len(), 2);
+        assert_eq!(diff.deployed_contracts[0].class_hash, "0x09");
+        assert_eq!(diff.declared_classes[0].compiled_class_hash, "0x00");
+    }
+}
@@ -0,0 +1,664 @@
+//! Merkle proofs over the bonsai tries and an offline verifier.
+//!
+//! Proofs follow the shape of `starknet_getStorageProof`: a node-hash-to-node mapping per
+//! trie, the contract leaf preimages, and the contract and class trie roots. Proofs are
+//! built from the tries as they are on disk, which is the state at the latest block.
+//!
+//! Hashing follows the Starknet state commitment:
+//! - binary node: `H(left, right)`, edge node: `H(child, path) + length`, with `H` Pedersen
+//!   for the contract and storage tries and Poseidon for the class trie
+//! - contract leaf: `pedersen(pedersen(pedersen(class_hash, storage_root), nonce), 0)`
+//! - global state root: `poseidon("STARKNET_STATE_V0", contracts_root, classes_root)`, or the
+//!   contracts root alone while the class trie is empty
+
+use std::collections::{HashMap, HashSet};
+
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use crate::tries::{
+    key_bits, parse_trie_key, trie_identifier, CLASS_IDENTIFIER, CONTRACT_IDENTIFIER,
+};
+use crate::{decode_felt_value, DbError, DbReader, NodeHandle, Trie, TrieNode, TRIE_HEIGHT};
+
+/// Trie node as it appears in a proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MerkleNode {
+    Binary {
+        left: String,
+        right: String,
+    },
+    Edge {
+        path: String,
+        length: u8,
+        child: String,
+    },
+}
+
+/// A proof node keyed by its hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofNode {
+    pub node_hash: String,
+    pub node: MerkleNode,
+}
+
+/// Preimage of a contract trie leaf
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractLeafData {
+    pub nonce: String,
+    pub class_hash: String,
+    /// Not every node returns it; without it the contract leaf cannot be recomputed
+    pub storage_root: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalRoots {
+    pub contracts_tree_root: String,
+    pub classes_tree_root: String,
+    pub block_hash: String,
+}
+
+/// Storage keys to prove for one contract
+#[derive(Debug, Clone, Default)]
+pub struct ContractStorageKeys {
+    pub contract_address: String,
+    pub storage_keys: Vec<String>,
+}
+
+/// What to prove, as in the `starknet_getStorageProof` request
+#[derive(Debug, Clone, Default)]
+pub struct StorageProofRequest {
+    pub class_hashes: Vec<String>,
+    pub contract_addresses: Vec<String>,
+    pub contracts_storage_keys: Vec<ContractStorageKeys>,
+}
+
+/// A `starknet_getStorageProof` result
+#[derive(Debug, Clone)]
+pub struct StorageProof {
+    pub classes_proof: Vec<ProofNode>,
+    pub contracts_proof: Vec<ProofNode>,
+    /// One entry per requested contract address, in request order
+    pub contract_leaves_data: Vec<ContractLeafData>,
+    /// One mapping per `contracts_storage_keys` entry, in request order
+    pub contracts_storage_proofs: Vec<Vec<ProofNode>>,
+    pub global_roots: GlobalRoots,
+}
+
+/// Outcome of checking one requested key against a proof
+#[derive(Debug, Clone)]
+pub struct ProofCheck {
+    pub trie: Trie,
+    /// Contract owning the storage trie, for storage keys
+    pub contract_address: Option<String>,
+    pub key: String,
+    /// Leaf value the proof commits to; `None` when it proves the key is absent
+    pub value: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Result of verifying a proof against a state root
+#[derive(Debug, Clone)]
+pub struct ProofVerification {
+    /// `None` when the proof's trie roots are not valid felts
+    pub computed_state_root: Option<String>,
+    pub expected_state_root: Option<String>,
+    pub state_root_matches: bool,
+    pub checks: Vec<ProofCheck>,
+}
+
+impl ProofVerification {
+    /// The state root matches and every requested key checked out
+    pub fn is_valid(&self) -> bool {
+        self.state_root_matches && self.checks.iter().all(|c| c.error.is_none())
+    }
+}
+
+pub(crate) fn trie_hash(trie: Trie, a: &Felt, b: &Felt) -> Felt {
+    match trie {
+        Trie::Class => Poseidon::hash(a, b),
+        Trie::Contract | Trie::ContractStorage => Pedersen::hash(a, b),
+    }
+}
+
+fn parse_felt(value: &str) -> Result<Felt, String> {
+    Felt::from_hex(value).map_err(|_| format!("invalid felt: {value}"))
+}
+
+pub(crate) fn felt_hex(felt: &Felt) -> String {
+    format!("{felt:#x}")
+}
+
+/// Edge path bits as the integer a proof carries (MSB first)
+pub(crate) fn bits_to_felt(bits: &[bool]) -> Felt {
+    let mut bytes = [0u8; 32];
+    for (i, &bit) in bits.iter().rev().enumerate() {
+        if bit {
+            bytes[31 - i / 8] |= 1 << (i % 8);
+        }
+    }
+    Felt::from_bytes_be(&bytes)
+}
+
+/// The lowest `length` bits of an edge path, MSB first
+fn felt_to_bits(path: &Felt, length: usize) -> Vec<bool> {
+    let bytes = path.to_bytes_be();
+    (0..length)
+        .rev()
+        .map(|i| bytes[31 - i / 8] & (1 << (i % 8)) != 0)
+        .collect()
+}
+
+/// Hash a proof node the way the trie commits to it
+pub fn merkle_node_hash(trie: Trie, node: &MerkleNode) -> Result<String, String> {
+    let hash = match node {
+        MerkleNode::Binary { left, right } => {
+            trie_hash(trie, &parse_felt(left)?, &parse_felt(right)?)
+        }
+        MerkleNode::Edge {
+            path,
+            length,
+            child,
+        } => trie_hash(trie, &parse_felt(child)?, &parse_felt(path)?) + Felt::from(*length as u64),
+    };
+    Ok(felt_hex(&hash))
+}
+
+/// `pedersen(pedersen(pedersen(class_hash, storage_root), nonce), 0)`
+pub fn contract_state_hash(
+    class_hash: &str,
+    storage_root: &str,
+    nonce: &str,
+) -> Result<String, String> {
+    Ok(felt_hex(&contract_leaf_hash(
+        &parse_felt(class_hash)?,
+        &parse_felt(storage_root)?,
+        &parse_felt(nonce)?,
+    )))
+}
+
+pub(crate) fn contract_leaf_hash(class_hash: &Felt, storage_root: &Felt, nonce: &Felt) -> Felt {
+    Pedersen::hash(
+        &Pedersen::hash(&Pedersen::hash(class_hash, storage_root), nonce),
+        &Felt::ZERO,
+    )
+}
+
+/// `poseidon("CONTRACT_CLASS_LEAF_V0", compiled_class_hash)`, a class trie leaf
+pub(crate) fn class_leaf_hash(compiled_class_hash: &Felt) -> Felt {
+    let prefix = Felt::from_bytes_be_slice(b"CONTRACT_CLASS_LEAF_V0");
+    Poseidon::hash(&prefix, compiled_class_hash)
+}
+
+/// Global state root from the contract and class trie roots
+pub fn global_state_root(
+    contracts_tree_root: &str,
+    classes_tree_root: &str,
+) -> Result<String, String> {
+    Ok(felt_hex(&state_root_hash(
+        &parse_felt(contracts_tree_root)?,
+        &parse_felt(classes_tree_root)?,
+    )))
+}
+
+pub(crate) fn state_root_hash(contracts_tree_root: &Felt, classes_tree_root: &Felt) -> Felt {
+    if *classes_tree_root == Felt::ZERO {
+        return *contracts_tree_root;
+    }
+    let prefix = Felt::from_bytes_be_slice(b"STARKNET_STATE_V0");
+    Poseidon::hash_array(&[prefix, *contracts_tree_root, *classes_tree_root])
+}
+
+fn handle_hash(handle: &NodeHandle) -> Result<String, DbError> {
+    match handle {
+        NodeHandle::Hash(hash) => Ok(hash.clone()),
+        NodeHandle::InMemory(id) => Err(DbError::InvalidInput(format!(
+            "trie references uncommitted node #{id}"
+        ))),
+    }
+}
+
+fn to_merkle_node(node: &TrieNode) -> Result<MerkleNode, DbError> {
+    Ok(match node {
+        TrieNode::Binary(binary) => MerkleNode::Binary {
+            left: handle_hash(&binary.left)?,
+            right: handle_hash(&binary.right)?,
+        },
+        TrieNode::Edge(edge) => MerkleNode::Edge {
+            path: felt_hex(&bits_to_felt(&edge.path)),
+            length: edge.path.len() as u8,
+            child: handle_hash(&edge.child)?,
+        },
+    })
+}
+
+/// Collects proof nodes for one trie, skipping nodes shared between paths
+struct ProofBuilder {
+    trie: Trie,
+    nodes: Vec<ProofNode>,
+    seen: HashSet<String>,
+}
+
+impl ProofBuilder {
+    fn new(trie: Trie) -> Self {
+        Self {
+            trie,
+            nodes: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    fn add_path(&mut self, db: &DbReader, identifier: &[u8], key: &str) -> Result<(), DbError> {
+        let key = parse_trie_key(key)?;
+        let (path, _) = db.walk_trie(self.trie, identifier, &key)?;
+        for visited in path {
+            let node = to_merkle_node(&visited.node)?;
+            let node_hash = merkle_node_hash(self.trie, &node).map_err(DbError::InvalidInput)?;
+            if self.seen.insert(node_hash.clone()) {
+                self.nodes.push(ProofNode { node_hash, node });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DbReader {
+    /// Root hash of the trie stored under `identifier` (zero for an empty trie)
+    pub(crate) fn trie_root(&self, trie: Trie, identifier: &[u8]) -> Result<String, DbError> {
+        let (path, _) = self.walk_trie(trie, identifier, &[0u8; 32])?;
+        match path.first() {
+            Some(root) => {
+                merkle_node_hash(trie, &to_merkle_node(&root.node)?).map_err(DbError::InvalidInput)
+            }
+            None => Ok(felt_hex(&Felt::ZERO)),
+        }
+    }
+
+    /// Build a `starknet_getStorageProof`-shaped proof from the tries on disk
+    pub fn get_storage_proof(
+        &self,
+        request: &StorageProofRequest,
+    ) -> Result<StorageProof, DbError> {
+        let mut classes = ProofBuilder::new(Trie::Class);
+        for class_hash in &request.class_hashes {
+            classes.add_path(self, CLASS_IDENTIFIER, class_hash)?;
+        }
+
+        let mut contracts = ProofBuilder::new(Trie::Contract);
+        let mut contract_leaves_data = Vec::with_capacity(request.contract_addresses.len());
+        for address in &request.contract_addresses {
+            contracts.add_path(self, CONTRACT_IDENTIFIER, address)?;
+
+            let identifier = trie_identifier(Trie::ContractStorage, Some(address))?;
+            let address_bytes: [u8; 32] =
+                identifier.as_slice().try_into().expect("32-byte address");
+            let zero = || felt_hex(&Felt::ZERO);
+            contract_leaves_data.push(ContractLeafData {
+                nonce: self
+                    .get_versioned_contract_value("contract_nonces", &address_bytes, None)
+                    .and_then(|value| decode_felt_value(&value))
+                    .unwrap_or_else(zero),
+                class_hash: self
+                    .get_contract_class_hash(&address_bytes, None)
+                    .unwrap_or_else(zero),
+                storage_root: Some(self.trie_root(Trie::ContractStorage, &identifier)?),
+            });
+        }
+
+        let mut contracts_storage_proofs = Vec::with_capacity(request.contracts_storage_keys.len());
+        for entry in &request.contracts_storage_keys {
+            let identifier = trie_identifier(Trie::ContractStorage, Some(&entry.contract_address))?;
+            let mut storage = ProofBuilder::new(Trie::ContractStorage);
+            for key in &entry.storage_keys {
+                storage.add_path(self, &identifier, key)?;
+            }
+            contracts_storage_proofs.push(storage.nodes);
+        }
+
+        let block_hash = self
+            .get_latest_block_number()
+            .and_then(|block_n| self.get_block_summary(block_n).ok().flatten())
+            .map(|block| block.block_hash)
+            .unwrap_or_else(|| felt_hex(&Felt::ZERO));
+
+        Ok(StorageProof {
+            classes_proof: classes.nodes,
+            contracts_proof: contracts.nodes,
+            contract_leaves_data,
+            contracts_storage_proofs,
+            global_roots: GlobalRoots {
+                contracts_tree_root: self.trie_root(Trie::Contract, CONTRACT_IDENTIFIER)?,
+                classes_tree_root: self.trie_root(Trie::Class, CLASS_IDENTIFIER)?,
+                block_hash,
+            },
+        })
+    }
+
+    /// Verify `proof` against the state root of `block_n` (the latest block when `None`)
+    pub fn verify_storage_proof_at(
+        &self,
+        proof: &StorageProof,
+        request: &StorageProofRequest,
+        block_n: Option<u64>,
+    ) -> Result<ProofVerification, DbError> {
+        let block_n = block_n.or_else(|| self.get_latest_block_number());
+        let expected = match block_n {
+            Some(block_n) => self
+                .get_block_detail(block_n)?
+                .map(|block| block.state_root),
+            None => None,
+        };
+        Ok(verify_storage_proof(proof, request, expected.as_deref()))
+    }
+}
+
+/// Walk a proof from `root` towards `key`, checking every node hash on the way.
+///
+/// Returns the leaf value, or `None` when the proof shows the key is absent.
+fn walk_proof(
+    trie: Trie,
+    nodes: &HashMap<Felt, &MerkleNode>,
+    root: &str,
+    key: &str,
+) -> Result<Option<String>, String> {
+    let root = parse_felt(root)?;
+    if root == Felt::ZERO {
+        return Ok(None);
+    }
+    let key = parse_trie_key(key).map_err(|e| e.to_string())?;
+    let bits = key_bits(&key);
+
+    let mut hash = root;
+    let mut depth = 0;
+    while depth < TRIE_HEIGHT {
+        let node = nodes
+            .get(&hash)
+            .ok_or_else(|| format!("proof is missing node {} at depth {depth}", felt_hex(&hash)))?;
+        let computed = parse_felt(&merkle_node_hash(trie, node)?)?;
+        if computed != hash {
+            return Err(format!(
+                "node {} hashes to {} at depth {depth}",
+                felt_hex(&hash),
+                felt_hex(&computed)
+            ));
+        }
+
+        match node {
+            MerkleNode::Binary { left, right } => {
+                hash = parse_felt(if bits[depth] { right } else { left })?;
+                depth += 1;
+            }
+            MerkleNode::Edge {
+                path,
+                length,
+                child,
+            } => {
+                let length = *length as usize;
+                if length == 0 || depth + length > TRIE_HEIGHT {
+                    return Err(format!(
+                        "edge of length {length} at depth {depth} overflows the trie"
+                    ));
+                }
+                if felt_to_bits(&parse_felt(path)?, length) != bits[depth..depth + length] {
+                    return Ok(None);
+                }
+                hash = parse_felt(child)?;
+                depth += length;
+            }
+        }
+    }
+
+    Ok(Some(felt_hex(&hash)))
+}
+
+fn index_nodes(nodes: &[ProofNode]) -> Result<HashMap<Felt, &MerkleNode>, String> {
+    nodes
+        .iter()
+        .map(|n| Ok((parse_felt(&n.node_hash)?, &n.node)))
+        .collect()
+}
+
+/// Verify a proof for `request` and compare the global root it implies with `expected_state_root`
+pub fn verify_storage_proof(
+    proof: &StorageProof,
+    request: &StorageProofRequest,
+    expected_state_root: Option<&str>,
+) -> ProofVerification {
+    let roots = &proof.global_roots;
+    let mut checks = Vec::new();
+
+    let computed_state_root =
+        global_state_root(&roots.contracts_tree_root, &roots.classes_tree_root).ok();
+    let state_root_matches = match (&computed_state_root, expected_state_root) {
+        (Some(computed), Some(expected)) => parse_felt(computed).ok() == parse_felt(expected).ok(),
+        _ => false,
+    };
+
+    let check = |trie: Trie,
+                 contract_address: Option<&str>,
+                 key: &str,
+                 result: Result<Option<String>, String>| {
+        let (value, error) = match result {
+            Ok(value) => (value, None),
+            Err(e) => (None, Some(e)),
+        };
+        ProofCheck {
+            trie,
+            contract_address: contract_address.map(str::to_string),
+            key: key.to_string(),
+            value,
+            error,
+        }
+    };
+
+    // Classes
+    let class_nodes = index_nodes(&proof.classes_proof);
+    for class_hash in &request.class_hashes {
+        let result = class_nodes
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|nodes| walk_proof(Trie::Class, nodes, &roots.classes_tree_root, class_hash));
+        checks.push(check(Trie::Class, None, class_hash, result));
+    }
+
+    // Contracts: the leaf must be the state hash of the claimed leaf data
+    let contract_nodes = index_nodes(&proof.contracts_proof);
+    let mut storage_roots = HashMap::new();
+    for (i, address) in request.contract_addresses.iter().enumerate() {
+        let leaf_data = proof.contract_leaves_data.get(i);
+        let result = contract_nodes
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|nodes| {
+                walk_proof(Trie::Contract, nodes, &roots.contracts_tree_root, address)
+            })
+            .and_then(|leaf| {
+                let (Some(leaf), Some(data)) = (&leaf, leaf_data) else {
+                    return Ok(leaf);
+                };
+                let storage_root = data.storage_root.as_deref().ok_or(
+                    "contract leaf data has no storage_root, cannot recompute the leaf".to_string(),
+                )?;
+                let expected = contract_state_hash(&data.class_hash, storage_root, &data.nonce)?;
+                if parse_felt(&expected)? != parse_felt(leaf)? {
+                    return Err(format!(
+                        "contract leaf {leaf} does not match leaf data (expected {expected})"
+                    ));
+                }
+                Ok(Some(leaf.clone()))
+            });
+        if let (
+            Ok(Some(_)),
+            Some(ContractLeafData {
+                storage_root: Some(root),
+                ..
+            }),
+        ) = (&result, leaf_data)
+        {
+            if let Ok(address) = parse_felt(address) {
+                storage_roots.insert(address, root.clone());
+            }
+        }
+        checks.push(check(Trie::Contract, None, address, result));
+    }
+
+    // Storage: only bound to the state root through a proven contract leaf
+    for (i, entry) in request.contracts_storage_keys.iter().enumerate() {
+        let storage_root = parse_felt(&entry.contract_address)
+            .ok()
+            .and_then(|address| storage_roots.get(&address));
+        let nodes = proof
+            .contracts_storage_proofs
+            .get(i)
+            .ok_or_else(|| "proof has no storage mapping for this contract".to_string())
+            .and_then(|nodes| index_nodes(nodes));
+        for key in &entry.storage_keys {
+            let result = match (storage_root, &nodes) {
+                (None, _) => Err(
+                    "contract is not proven in contracts_proof, storage root unknown".to_string(),
+                ),
+                (_, Err(e)) => Err(e.clone()),
+                (Some(root), Ok(nodes)) => walk_proof(Trie::ContractStorage, nodes, root, key),
+            };
+            checks.push(check(
+                Trie::ContractStorage,
+                Some(&entry.contract_address),
+                key,
+                result,
+            ));
+        }
+    }
+
+    ProofVerification {
+        computed_state_root,
+        expected_state_root: expected_state_root.map(str::to_string),
+        state_root_matches,
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::open_db_with_cfs;
+    use crate::tries::node_db_key;
+    use tempfile::TempDir;
+
+    fn felt_bytes(hex_value: &str) -> [u8; 32] {
+        parse_felt(hex_value).unwrap().to_bytes_be()
+    }
+
+    /// A committed edge node covering the whole key
+    fn leaf_edge(key: &str, leaf: &str) -> Vec<u8> {
+        let bits = key_bits(&felt_bytes(key));
+        let mut node = vec![1, 0];
+        node.extend(0u64.to_le_bytes());
+        node.extend(&node_db_key(&[], &bits));
+        node.push(0);
+        node.extend(felt_bytes(leaf));
+        node
+    }
+
+    fn setup() -> (TempDir, DbReader, StorageProofRequest) {
+        let contract = "0x123";
+        let storage_key = "0x5";
+        let storage_value = "0x2a";
+        let class_hash = "0xc1a55";
+        let nonce = "0x3";
+
+        let storage_node = leaf_edge(storage_key, storage_value);
+        let storage_root = merkle_node_hash(
+            Trie::ContractStorage,
+            &MerkleNode::Edge {
+                path: felt_hex(&bits_to_felt(&key_bits(&felt_bytes(storage_key)))),
+                length: TRIE_HEIGHT as u8,
+                child: storage_value.to_string(),
+            },
+        )
+        .unwrap();
+        let contract_leaf = contract_state_hash(class_hash, &storage_root, nonce).unwrap();
+
+        let address = felt_bytes(contract);
+        let felt_value = |v: &str| {
+            use bincode::Options;
+            bincode::DefaultOptions::new()
+                .serialize(&serde_bytes::ByteBuf::from(felt_bytes(v).to_vec()))
+                .unwrap()
+        };
+        let mut versioned = address.to_vec();
+        versioned.extend_from_slice(&crate::encode_reversed_block(0));
+        // The class trie stays empty
+        let (dir, reader) = open_db_with_cfs(
+            &["bonsai_class_trie"],
+            &[
+                (
+                    "bonsai_contract_storage_trie",
+                    node_db_key(&address, &[]),
+                    storage_node,
+                ),
+                (
+                    "bonsai_contract_trie",
+                    node_db_key(CONTRACT_IDENTIFIER, &[]),
+                    leaf_edge(contract, &contract_leaf),
+                ),
+                ("contract_nonces", versioned.clone(), felt_value(nonce)),
+                ("contract_class_hashes", versioned, felt_value(class_hash)),
+            ],
+        );
+
+        let request = StorageProofRequest {
+            class_hashes: vec![],
+            contract_addresses: vec![contract.to_string()],
+            contracts_storage_keys: vec![ContractStorageKeys {
+                contract_address: contract.to_string(),
+                storage_keys: vec![storage_key.to_string()],
+            }],
+        };
+        (dir, reader, request)
+    }
+
+    #[test]
+    fn generated_proof_verifies_against_state_root() {
+        let (_dir, db, request) = setup();
+        let proof = db.get_storage_proof(&request).unwrap();
+
+        assert_eq!(proof.contracts_proof.len(), 1);
+        assert_eq!(proof.contracts_storage_proofs[0].len(), 1);
+        assert_eq!(proof.global_roots.classes_tree_root, "0x0");
+
+        // Empty class trie: the state root is the contract trie root
+        let state_root = proof.global_roots.contracts_tree_root.clone();
+        let verification = verify_storage_proof(&proof, &request, Some(&state_root));
+        assert!(verification.is_valid(), "{verification:?}");
+        let storage = &verification.checks[1];
+        assert_eq!(storage.trie, Trie::ContractStorage);
+        assert_eq!(storage.value.as_deref(), Some("0x2a"));
+    }
+
+    #[test]
+    fn tampered_proof_is_rejected() {
+        let (_dir, db, request) = setup();
+        let mut proof = db.get_storage_proof(&request).unwrap();
+        let state_root = proof.global_roots.contracts_tree_root.clone();
+
+        if let MerkleNode::Edge { child, .. } = &mut proof.contracts_storage_proofs[0][0].node {
+            *child = "0x2b".to_string();
+        }
+        let verification = verify_storage_proof(&proof, &request, Some(&state_root));
+        assert!(!verification.is_valid());
+        assert!(verification.checks[1].error.is_some());
+
+        let verification = verify_storage_proof(
+            &db.get_storage_proof(&request).unwrap(),
+            &request,
+            Some("0x1"),
+        );
+        assert!(!verification.state_root_matches);
+    }
+
+    #[test]
+    fn edge_path_bits_roundtrip() {
+        let bits = vec![true, false, true, true, false, false, false, false, true];
+        assert_eq!(felt_to_bits(&bits_to_felt(&bits), bits.len()), bits);
+    }
+}
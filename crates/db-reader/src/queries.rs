@@ -1,6 +1,6 @@
 //! Query functions for reading data from the database
 
-use crate::{DbError, DbReader};
+use crate::{ChainTip, DbError, DbReader};
 use rocksdb::IteratorMode;
 
 /// Database statistics
@@ -31,7 +31,7 @@ impl DbReader {
 
     /// Get the latest confirmed block number from the database
     pub fn get_latest_block_number(&self) -> Option<u64> {
-        // Try chain tip first (bincode varint encoding)
+        // Try the chain tip first
         if let Some(block_n) = self.get_chain_tip_block() {
             return Some(block_n);
         }
@@ -40,46 +40,13 @@ impl DbReader {
         self.get_highest_block_from_block_info()
     }
 
-    /// Parse chain tip from meta column
-    /// The format is bincode DefaultOptions which uses varint encoding:
-    /// - variant 0 (Confirmed): 1 byte + varint u64
+    /// Block number of a confirmed chain tip. While a block is being built, the tip is its
+    /// preconfirmed header and the caller falls back to `block_info`.
     fn get_chain_tip_block(&self) -> Option<u64> {
-        let cf = self.db.cf_handle("meta")?;
-        let value = self.db.get_cf(&cf, b"CHAIN_TIP").ok()??;
-
-        // First byte is variant index (0 = Confirmed, 1 = Preconfirmed)
-        if value.is_empty() || value[0] != 0 {
-            return None;
-        }
-
-        // Rest is varint-encoded u64 block number
-        // For small numbers (< 251), it's just one byte
-        // For larger numbers, it uses multi-byte encoding
-        if value.len() == 2 {
-            // Single byte block number
-            return Some(value[1] as u64);
-        } else if value.len() >= 2 {
-            // Try to decode varint
-            // Bincode uses a custom varint format:
-            // 0-250: single byte
-            // 251: 2-byte LE
-            // 252: 4-byte LE
-            // 253: 8-byte LE
-            let first = value[1];
-            if first <= 250 {
-                return Some(first as u64);
-            } else if first == 251 && value.len() >= 4 {
-                return Some(u16::from_le_bytes([value[2], value[3]]) as u64);
-            } else if first == 252 && value.len() >= 6 {
-                return Some(u32::from_le_bytes([value[2], value[3], value[4], value[5]]) as u64);
-            } else if first == 253 && value.len() >= 10 {
-                return Some(u64::from_le_bytes([
-                    value[2], value[3], value[4], value[5], value[6], value[7], value[8], value[9],
-                ]));
-            }
+        match self.get_chain_tip().ok()?? {
+            ChainTip::Confirmed(block_n) => Some(block_n),
+            ChainTip::Preconfirmed(_) => None,
         }
-
-        None
     }
 
     /// Fallback method to find the highest block number by scanning block_info column
@@ -133,7 +100,11 @@ impl DbReader {
 
         // Try to parse as block number first
         if let Ok(block_n) = query.parse::<u64>() {
-            if self.get_block_detail(block_n).is_some() {
+            // A block that exists but fails to decode is still a match; its page shows why
+            if matches!(
+                self.get_block_detail(block_n),
+                Ok(Some(_)) | Err(DbError::Decode(_))
+            ) {
                 return SearchResult::Block(block_n);
             }
         }
@@ -1,5 +1,7 @@
 //! Raw column family browsing functionality
 
+use crate::bouncer::RawBlockBouncerWeights;
+use crate::meta::{RawMetaValue, RawStoredChainTip};
 use crate::DbReader;
 use rocksdb::IteratorMode;
 
@@ -240,9 +242,107 @@ impl DbReader {
                 }
                 None
             }
+            "events_bloom" if key.len() == 4 => {
+                let block_num = u32::from_be_bytes(key.try_into().ok()?);
+                let bloom = crate::events::decode_bloom(key, value).ok()?;
+                Some(format!(
+                    "block {block_num}, {}-bit event filter, {} bits set",
+                    bloom.bits(),
+                    bloom.set_bits()
+                ))
+            }
+            "block_bouncer_weight" if key.len() == 4 => {
+                let block_num = u32::from_be_bytes(key.try_into().ok()?);
+                let raw = self.decoder.decode_bouncer_weights(key, value).ok()?;
+                Some(match raw {
+                    RawBlockBouncerWeights::Current(w) => format!(
+                        "block {block_num}: {} txs, sierra_gas {}, proving_gas {}, l1_gas {}",
+                        w.n_txs, w.sierra_gas, w.proving_gas, w.l1_gas
+                    ),
+                    RawBlockBouncerWeights::Legacy(w) => format!(
+                        "block {block_num}: n_steps {}, gas {}, {} builtins",
+                        w.n_steps,
+                        w.gas,
+                        w.builtin_count.total()
+                    ),
+                })
+            }
+            // Mempool
+            "mempool_transactions" => {
+                let tx = self
+                    .decoder
+                    .decode_mempool_transaction(key, value)
+                    .ok()?
+                    .to_mempool_transaction();
+                let nonce = tx.nonce.map_or("none".to_string(), |n| n.to_string());
+                Some(format!(
+                    "{} from {}, nonce {nonce}",
+                    tx.tx_type, tx.contract_address
+                ))
+            }
+            "preconfirmed" if key.len() == 2 => {
+                let index = u16::from_be_bytes(key.try_into().ok()?);
+                let raw = self
+                    .decoder
+                    .decode_preconfirmed_transaction(key, value)
+                    .ok()?;
+                Some(format!(
+                    "tx_index: {index}, {} {}",
+                    raw.transaction.transaction.get_tx_type(),
+                    raw.transaction.get_tx_hash()
+                ))
+            }
+            // Messaging: keyed by the core contract nonce
+            "l1_to_l2_pending_message_by_nonce" if key.len() == 8 => {
+                let nonce = u64::from_be_bytes(key.try_into().ok()?);
+                let raw = self.decoder.decode_pending_message(key, value).ok()?;
+                Some(format!(
+                    "pending L1 message nonce {nonce} to {}, paid {} wei on L1",
+                    crate::Felt::from_bytes(&raw.tx.contract_address).to_hex(),
+                    raw.paid_fee_on_l1
+                ))
+            }
+            "l1_to_l2_txn_hash_by_nonce" if key.len() == 8 => {
+                let nonce = u64::from_be_bytes(key.try_into().ok()?);
+                Some(format!(
+                    "L1 message nonce {nonce} consumed by tx {}",
+                    crate::Felt::from_bytes(value).to_hex()
+                ))
+            }
+            // Node metadata
+            "meta" => match self.decoder.decode_meta(key, value).ok()? {
+                RawMetaValue::ChainTip(RawStoredChainTip::Confirmed(block_n)) => {
+                    Some(format!("chain tip: confirmed block {block_n}"))
+                }
+                RawMetaValue::ChainTip(RawStoredChainTip::Preconfirmed(header)) => Some(format!(
+                    "chain tip: preconfirmed block {}",
+                    header.block_number
+                )),
+                RawMetaValue::ChainInfo(info) => Some(format!(
+                    "chain id: {}, name: {}",
+                    info.chain_id, info.chain_name
+                )),
+                RawMetaValue::BlockMarker(block_n) => Some(format!("block: {block_n}")),
+                RawMetaValue::DevnetKeys(accounts) => {
+                    Some(format!("{} predeployed devnet accounts", accounts.len()))
+                }
+                RawMetaValue::RuntimeExecConfig(config) => Some(format!(
+                    "runtime exec config, protocol version {}",
+                    config.protocol_version
+                )),
+                RawMetaValue::Unknown => None,
+            },
             // Storage
             "contract_storage" => Some("contract storage key-value pair".to_string()),
             // Class-related
+            "class_compiled" => {
+                let raw = self.decoder.decode_compiled_class(key, value).ok()?;
+                Some(format!(
+                    "compiled class stored at block {}, CASM JSON: {} bytes",
+                    raw.block_number,
+                    raw.compiled_sierra.len()
+                ))
+            }
             "class_info" | "sierra_classes" | "compiled_classes" => {
                 Some(format!("class data, size: {} bytes", value.len()))
             }
@@ -256,6 +356,38 @@ impl DbReader {
                 }
             }
             // Trie-related
+            name if name.ends_with("_trie") => {
+                match self.decoder.decode_trie_node(name, key, value) {
+                    Ok(crate::TrieNode::Binary(node)) => {
+                        Some(format!("binary node, height: {}", node.height))
+                    }
+                    Ok(crate::TrieNode::Edge(node)) => Some(format!(
+                        "edge node, height: {}, path: {} bits",
+                        node.height,
+                        node.path.len()
+                    )),
+                    Err(_) => Some(format!("trie node, size: {} bytes", value.len())),
+                }
+            }
+            // Trie log: `block_n ++ 0 ++ trie key ++ kind ++ change`
+            name if name.starts_with("bonsai_") && name.ends_with("_log") && key.len() > 11 => {
+                let block_n = u64::from_be_bytes(key[..8].try_into().ok()?);
+                let change = if key[key.len() - 1] == 0 {
+                    "new"
+                } else {
+                    "old"
+                };
+                match key[key.len() - 2] {
+                    1 if value.len() == 32 => Some(format!(
+                        "block {block_n}, {change} leaf value: {}",
+                        crate::Felt::from_bytes(value).to_hex()
+                    )),
+                    _ => Some(format!(
+                        "block {block_n}, {change} trie node, size: {} bytes",
+                        value.len()
+                    )),
+                }
+            }
             name if name.contains("trie") || name.contains("bonsai") => {
                 Some(format!("trie node, size: {} bytes", value.len()))
             }
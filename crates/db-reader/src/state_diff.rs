@@ -1,7 +1,7 @@
 //! State diff reading functionality
 
 use crate::blocks::Felt;
-use crate::DbReader;
+use crate::{DbError, DbReader};
 use serde::Deserialize;
 use serde_bytes::ByteBuf;
 
@@ -65,7 +65,7 @@ pub struct ReplacedClass {
 // Raw deserialization types matching Madara's StateDiff
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawStateDiff {
+pub(crate) struct RawStateDiff {
     pub storage_diffs: Vec<RawContractStorageDiffItem>,
     #[serde(default)]
     pub old_declared_contracts: Vec<ByteBuf>,
@@ -78,74 +78,64 @@ struct RawStateDiff {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawContractStorageDiffItem {
+pub(crate) struct RawContractStorageDiffItem {
     pub address: ByteBuf,
     pub storage_entries: Vec<RawStorageEntry>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawStorageEntry {
+pub(crate) struct RawStorageEntry {
     pub key: ByteBuf,
     pub value: ByteBuf,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeclaredClassItem {
+pub(crate) struct RawDeclaredClassItem {
     pub class_hash: ByteBuf,
     pub compiled_class_hash: ByteBuf,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeployedContractItem {
+pub(crate) struct RawDeployedContractItem {
     pub address: ByteBuf,
     pub class_hash: ByteBuf,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawReplacedClassItem {
+pub(crate) struct RawReplacedClassItem {
     pub contract_address: ByteBuf,
     pub class_hash: ByteBuf,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawNonceUpdate {
+pub(crate) struct RawNonceUpdate {
     pub contract_address: ByteBuf,
     pub nonce: ByteBuf,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawMigratedClassItem {
+pub(crate) struct RawMigratedClassItem {
     pub class_hash: ByteBuf,
     pub compiled_class_hash: ByteBuf,
 }
 
 impl DbReader {
     /// Get state diff for a block
-    pub fn get_state_diff(&self, block_n: u64) -> Option<StateDiffInfo> {
-        use bincode::Options;
-
-        let block_n_u32 = u32::try_from(block_n).ok()?;
-        let cf = self.db.cf_handle("block_state_diff")?;
-        let value = self.db.get_cf(&cf, block_n_u32.to_be_bytes()).ok()??;
-
-        let opts = bincode::DefaultOptions::new();
-        let raw: RawStateDiff = match opts.deserialize(&value) {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!(
-                    "State diff deserialization error for block {}: {}",
-                    block_n, e
-                );
-                eprintln!(
-                    "Raw value length: {}, first 20 bytes: {:?}",
-                    value.len(),
-                    &value[..20.min(value.len())]
-                );
-                return None;
-            }
+    pub fn get_state_diff(&self, block_n: u64) -> Result<Option<StateDiffInfo>, DbError> {
+        let Some(cf) = self.db.cf_handle("block_state_diff") else {
+            return Ok(None);
         };
+        let Ok(block_n_u32) = u32::try_from(block_n) else {
+            return Ok(None);
+        };
+        let key = block_n_u32.to_be_bytes();
+        let Some(value) = self.db.get_cf(&cf, key)? else {
+            return Ok(None);
+        };
+
+        let raw = self.decoder.decode_state_diff(&key, &value)?;
 
-        Some(StateDiffInfo {
+        Ok(Some(StateDiffInfo {
             deployed_contracts: raw
                 .deployed_contracts
                 .iter()
@@ -193,6 +183,6 @@ impl DbReader {
                     class_hash: Felt::from_bytes(&r.class_hash).to_hex(),
                 })
                 .collect(),
-        })
+        }))
     }
 }
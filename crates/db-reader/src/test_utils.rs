@@ -0,0 +1,43 @@
+//! Temporary databases for unit tests
+
+use crate::{DbReader, DB};
+use rocksdb::Options;
+use tempfile::TempDir;
+
+/// Writes `(cf, key, value)` entries to a fresh database and opens a reader on it.
+/// Only the column families written to are created.
+pub(crate) fn open_db(writes: &[(&str, Vec<u8>, Vec<u8>)]) -> (TempDir, DbReader) {
+    open_db_with_cfs(&[], writes)
+}
+
+/// Like [`open_db`], but also creates the (possibly empty) column families in `cfs`
+pub(crate) fn open_db_with_cfs(
+    cfs: &[&str],
+    writes: &[(&str, Vec<u8>, Vec<u8>)],
+) -> (TempDir, DbReader) {
+    let dir = TempDir::new().unwrap();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cfs: Vec<&str> = cfs
+            .iter()
+            .copied()
+            .chain(writes.iter().map(|(cf, _, _)| *cf))
+            .collect();
+        cfs.sort_unstable();
+        cfs.dedup();
+        let db = DB::open_cf(&opts, dir.path(), cfs).unwrap();
+        for (cf_name, key, value) in writes {
+            let cf = db.cf_handle(cf_name).unwrap();
+            db.put_cf(&cf, key, value).unwrap();
+        }
+    }
+    let reader = DbReader::open(dir.path()).unwrap();
+    (dir, reader)
+}
+
+/// A `block_info` entry that puts the chain tip at `block_n`
+pub(crate) fn chain_tip(block_n: u32) -> (&'static str, Vec<u8>, Vec<u8>) {
+    ("block_info", block_n.to_be_bytes().to_vec(), vec![])
+}
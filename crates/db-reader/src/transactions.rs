@@ -1,7 +1,8 @@
 //! Transaction reading functionality
 
 use crate::blocks::Felt;
-use crate::DbReader;
+use crate::{DbError, DbReader, DecodeError};
+use rocksdb::{Direction, IteratorMode};
 use serde::Deserialize;
 use serde_bytes::ByteBuf;
 
@@ -47,7 +48,8 @@ pub enum ExecutionStatus {
 pub struct TransactionSummary {
     pub tx_hash: String,
     pub tx_type: TransactionType,
-    pub status: ExecutionStatus,
+    /// `None` when the receipt could not be decoded
+    pub status: Option<ExecutionStatus>,
     pub block_number: u64,
     pub tx_index: usize,
 }
@@ -70,6 +72,106 @@ pub struct TransactionDetail {
     pub signature: Vec<String>,
     pub nonce: Option<String>,
     pub version: Option<String>,
+    /// Variant-specific transaction fields (`None` when the transaction could not be decoded)
+    pub body: Option<TransactionBody>,
+    /// Receipt execution resources (`None` when the transaction could not be decoded)
+    pub execution_resources: Option<ExecutionResources>,
+}
+
+/// Cairo VM resources and gas consumed by a transaction
+#[derive(Debug, Clone)]
+pub struct ExecutionResources {
+    pub steps: u64,
+    pub memory_holes: u64,
+    pub range_check_builtin_applications: u64,
+    pub pedersen_builtin_applications: u64,
+    pub poseidon_builtin_applications: u64,
+    pub ec_op_builtin_applications: u64,
+    pub ecdsa_builtin_applications: u64,
+    pub bitwise_builtin_applications: u64,
+    pub keccak_builtin_applications: u64,
+    pub segment_arena_builtin: u64,
+    /// Gas spent on data availability
+    pub data_availability: GasVector,
+    /// Total gas consumed, including data availability
+    pub total_gas_consumed: GasVector,
+}
+
+/// Gas amounts per resource
+#[derive(Debug, Clone)]
+pub struct GasVector {
+    pub l1_gas: u128,
+    pub l1_data_gas: u128,
+    pub l2_gas: u128,
+}
+
+/// Variant-specific transaction fields
+#[derive(Debug, Clone)]
+pub enum TransactionBody {
+    Invoke {
+        /// V0 and V1 only
+        max_fee: Option<String>,
+        /// V0 only
+        entry_point_selector: Option<String>,
+        /// V3 only
+        v3: Option<V3Fields>,
+        account_deployment_data: Vec<String>,
+    },
+    L1Handler {
+        contract_address: String,
+        entry_point_selector: String,
+    },
+    Declare {
+        class_hash: String,
+        /// V2 and V3 only
+        compiled_class_hash: Option<String>,
+        /// V0 to V2 only
+        max_fee: Option<String>,
+        /// V3 only
+        v3: Option<V3Fields>,
+        account_deployment_data: Vec<String>,
+    },
+    Deploy {
+        class_hash: String,
+        contract_address_salt: String,
+        constructor_calldata: Vec<String>,
+    },
+    DeployAccount {
+        class_hash: String,
+        contract_address_salt: String,
+        constructor_calldata: Vec<String>,
+        /// V1 only
+        max_fee: Option<String>,
+        /// V3 only
+        v3: Option<V3Fields>,
+    },
+}
+
+/// Fee market fields shared by all V3 transactions
+#[derive(Debug, Clone)]
+pub struct V3Fields {
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: u64,
+    pub paymaster_data: Vec<String>,
+    /// "L1" or "L2"
+    pub nonce_data_availability_mode: String,
+    /// "L1" or "L2"
+    pub fee_data_availability_mode: String,
+}
+
+/// Per-resource bounds of a V3 transaction
+#[derive(Debug, Clone)]
+pub struct ResourceBoundsMapping {
+    pub l1_gas: ResourceBounds,
+    pub l2_gas: ResourceBounds,
+    pub l1_data_gas: Option<ResourceBounds>,
+}
+
+/// Maximum amount and price the sender is willing to pay for one resource
+#[derive(Debug, Clone)]
+pub struct ResourceBounds {
+    pub max_amount: u64,
+    pub max_price_per_unit: u128,
 }
 
 /// Event information
@@ -92,14 +194,14 @@ pub struct MessageInfo {
 // This matches madara's serialization format
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawTransactionWithReceipt {
+pub(crate) struct RawTransactionWithReceipt {
     pub transaction: RawTransaction,
     pub receipt: RawTransactionReceipt,
 }
 
 // Transaction is an enum with 5 variants
 #[derive(Debug, Clone, Deserialize)]
-enum RawTransaction {
+pub(crate) enum RawTransaction {
     Invoke(RawInvokeTransaction),
     L1Handler(RawL1HandlerTransaction),
     Declare(RawDeclareTransaction),
@@ -109,14 +211,14 @@ enum RawTransaction {
 
 // Invoke transaction is also an enum with multiple versions
 #[derive(Debug, Clone, Deserialize)]
-enum RawInvokeTransaction {
+pub(crate) enum RawInvokeTransaction {
     V0(RawInvokeTransactionV0),
     V1(RawInvokeTransactionV1),
     V3(RawInvokeTransactionV3),
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawInvokeTransactionV0 {
+pub(crate) struct RawInvokeTransactionV0 {
     pub max_fee: ByteBuf,
     pub signature: Vec<ByteBuf>,
     pub contract_address: ByteBuf,
@@ -125,7 +227,7 @@ struct RawInvokeTransactionV0 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawInvokeTransactionV1 {
+pub(crate) struct RawInvokeTransactionV1 {
     pub sender_address: ByteBuf,
     pub calldata: Vec<ByteBuf>,
     pub max_fee: ByteBuf,
@@ -134,7 +236,7 @@ struct RawInvokeTransactionV1 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawInvokeTransactionV3 {
+pub(crate) struct RawInvokeTransactionV3 {
     pub sender_address: ByteBuf,
     pub calldata: Vec<ByteBuf>,
     pub signature: Vec<ByteBuf>,
@@ -148,20 +250,35 @@ struct RawInvokeTransactionV3 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawResourceBoundsMapping {
+pub(crate) struct RawResourceBoundsMapping {
     pub l1_gas: RawResourceBounds,
     pub l2_gas: RawResourceBounds,
     pub l1_data_gas: Option<RawResourceBounds>,
 }
 
+// Madara serializes both fields as "0x"-prefixed hex strings (`u64_as_hex`, `u128_as_hex`)
 #[derive(Debug, Clone, Deserialize)]
-struct RawResourceBounds {
+pub(crate) struct RawResourceBounds {
+    #[serde(deserialize_with = "deserialize_hex_u64")]
     pub max_amount: u64,
+    #[serde(deserialize_with = "deserialize_hex_u128")]
     pub max_price_per_unit: u128,
 }
 
+fn deserialize_hex_u64<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    u64::from_str_radix(hex.trim_start_matches("0x"), 16).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_hex_u128<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u128, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    u128::from_str_radix(hex.trim_start_matches("0x"), 16).map_err(serde::de::Error::custom)
+}
+
 #[derive(Debug, Clone, Deserialize)]
-struct RawL1HandlerTransaction {
+pub(crate) struct RawL1HandlerTransaction {
     pub version: ByteBuf,
     pub nonce: u64,
     pub contract_address: ByteBuf,
@@ -170,7 +287,7 @@ struct RawL1HandlerTransaction {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-enum RawDeclareTransaction {
+pub(crate) enum RawDeclareTransaction {
     V0(RawDeclareTransactionV0),
     V1(RawDeclareTransactionV1),
     V2(RawDeclareTransactionV2),
@@ -178,7 +295,7 @@ enum RawDeclareTransaction {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeclareTransactionV0 {
+pub(crate) struct RawDeclareTransactionV0 {
     pub sender_address: ByteBuf,
     pub max_fee: ByteBuf,
     pub signature: Vec<ByteBuf>,
@@ -186,7 +303,7 @@ struct RawDeclareTransactionV0 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeclareTransactionV1 {
+pub(crate) struct RawDeclareTransactionV1 {
     pub sender_address: ByteBuf,
     pub max_fee: ByteBuf,
     pub signature: Vec<ByteBuf>,
@@ -195,7 +312,7 @@ struct RawDeclareTransactionV1 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeclareTransactionV2 {
+pub(crate) struct RawDeclareTransactionV2 {
     pub sender_address: ByteBuf,
     pub compiled_class_hash: ByteBuf,
     pub max_fee: ByteBuf,
@@ -205,7 +322,7 @@ struct RawDeclareTransactionV2 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeclareTransactionV3 {
+pub(crate) struct RawDeclareTransactionV3 {
     pub sender_address: ByteBuf,
     pub compiled_class_hash: ByteBuf,
     pub signature: Vec<ByteBuf>,
@@ -220,7 +337,7 @@ struct RawDeclareTransactionV3 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeployTransaction {
+pub(crate) struct RawDeployTransaction {
     pub version: ByteBuf,
     pub class_hash: ByteBuf,
     pub contract_address_salt: ByteBuf,
@@ -228,13 +345,13 @@ struct RawDeployTransaction {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-enum RawDeployAccountTransaction {
+pub(crate) enum RawDeployAccountTransaction {
     V1(RawDeployAccountTransactionV1),
     V3(RawDeployAccountTransactionV3),
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeployAccountTransactionV1 {
+pub(crate) struct RawDeployAccountTransactionV1 {
     pub max_fee: ByteBuf,
     pub signature: Vec<ByteBuf>,
     pub nonce: ByteBuf,
@@ -244,7 +361,7 @@ struct RawDeployAccountTransactionV1 {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeployAccountTransactionV3 {
+pub(crate) struct RawDeployAccountTransactionV3 {
     pub signature: Vec<ByteBuf>,
     pub nonce: ByteBuf,
     pub contract_address_salt: ByteBuf,
@@ -259,7 +376,7 @@ struct RawDeployAccountTransactionV3 {
 
 // Receipt types
 #[derive(Debug, Clone, Deserialize)]
-enum RawTransactionReceipt {
+pub(crate) enum RawTransactionReceipt {
     Invoke(RawInvokeTransactionReceipt),
     L1Handler(RawL1HandlerTransactionReceipt),
     Declare(RawDeclareTransactionReceipt),
@@ -268,7 +385,7 @@ enum RawTransactionReceipt {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawInvokeTransactionReceipt {
+pub(crate) struct RawInvokeTransactionReceipt {
     pub transaction_hash: ByteBuf,
     pub actual_fee: RawFeePayment,
     pub messages_sent: Vec<RawMsgToL1>,
@@ -278,7 +395,7 @@ struct RawInvokeTransactionReceipt {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawL1HandlerTransactionReceipt {
+pub(crate) struct RawL1HandlerTransactionReceipt {
     pub message_hash: [u8; 32],
     pub transaction_hash: ByteBuf,
     pub actual_fee: RawFeePayment,
@@ -289,7 +406,7 @@ struct RawL1HandlerTransactionReceipt {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeclareTransactionReceipt {
+pub(crate) struct RawDeclareTransactionReceipt {
     pub transaction_hash: ByteBuf,
     pub actual_fee: RawFeePayment,
     pub messages_sent: Vec<RawMsgToL1>,
@@ -299,7 +416,7 @@ struct RawDeclareTransactionReceipt {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeployTransactionReceipt {
+pub(crate) struct RawDeployTransactionReceipt {
     pub transaction_hash: ByteBuf,
     pub actual_fee: RawFeePayment,
     pub messages_sent: Vec<RawMsgToL1>,
@@ -310,7 +427,7 @@ struct RawDeployTransactionReceipt {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawDeployAccountTransactionReceipt {
+pub(crate) struct RawDeployAccountTransactionReceipt {
     pub transaction_hash: ByteBuf,
     pub actual_fee: RawFeePayment,
     pub messages_sent: Vec<RawMsgToL1>,
@@ -321,33 +438,33 @@ struct RawDeployAccountTransactionReceipt {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawFeePayment {
+pub(crate) struct RawFeePayment {
     pub amount: ByteBuf,
     pub unit: RawPriceUnit,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-enum RawPriceUnit {
+pub(crate) enum RawPriceUnit {
     Wei,
     Fri,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawMsgToL1 {
+pub(crate) struct RawMsgToL1 {
     pub from_address: ByteBuf,
     pub to_address: ByteBuf,
     pub payload: Vec<ByteBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawEvent {
+pub(crate) struct RawEvent {
     pub from_address: ByteBuf,
     pub keys: Vec<ByteBuf>,
     pub data: Vec<ByteBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawExecutionResources {
+pub(crate) struct RawExecutionResources {
     pub steps: u64,
     pub memory_holes: u64,
     pub range_check_builtin_applications: u64,
@@ -363,18 +480,91 @@ struct RawExecutionResources {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawGasVector {
+pub(crate) struct RawGasVector {
     pub l1_gas: u128,
     pub l1_data_gas: u128,
     pub l2_gas: u128,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-enum RawExecutionResult {
+pub(crate) enum RawExecutionResult {
     Succeeded,
     Reverted { reason: String },
 }
 
+impl From<&RawResourceBounds> for ResourceBounds {
+    fn from(raw: &RawResourceBounds) -> Self {
+        Self {
+            max_amount: raw.max_amount,
+            max_price_per_unit: raw.max_price_per_unit,
+        }
+    }
+}
+
+impl From<&RawResourceBoundsMapping> for ResourceBoundsMapping {
+    fn from(raw: &RawResourceBoundsMapping) -> Self {
+        Self {
+            l1_gas: (&raw.l1_gas).into(),
+            l2_gas: (&raw.l2_gas).into(),
+            l1_data_gas: raw.l1_data_gas.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl From<&RawGasVector> for GasVector {
+    fn from(raw: &RawGasVector) -> Self {
+        Self {
+            l1_gas: raw.l1_gas,
+            l1_data_gas: raw.l1_data_gas,
+            l2_gas: raw.l2_gas,
+        }
+    }
+}
+
+impl From<&RawExecutionResources> for ExecutionResources {
+    fn from(raw: &RawExecutionResources) -> Self {
+        Self {
+            steps: raw.steps,
+            memory_holes: raw.memory_holes,
+            range_check_builtin_applications: raw.range_check_builtin_applications,
+            pedersen_builtin_applications: raw.pedersen_builtin_applications,
+            poseidon_builtin_applications: raw.poseidon_builtin_applications,
+            ec_op_builtin_applications: raw.ec_op_builtin_applications,
+            ecdsa_builtin_applications: raw.ecdsa_builtin_applications,
+            bitwise_builtin_applications: raw.bitwise_builtin_applications,
+            keccak_builtin_applications: raw.keccak_builtin_applications,
+            segment_arena_builtin: raw.segment_arena_builtin,
+            data_availability: (&raw.data_availability).into(),
+            total_gas_consumed: (&raw.total_gas_consumed).into(),
+        }
+    }
+}
+
+// DataAvailabilityMode is a unit enum, serialized as its variant index
+fn da_mode_name(mode: u32) -> String {
+    match mode {
+        0 => "L1".to_string(),
+        1 => "L2".to_string(),
+        other => format!("UNKNOWN({other})"),
+    }
+}
+
+fn felts_to_hex(felts: &[ByteBuf]) -> Vec<String> {
+    felts.iter().map(|f| Felt::from_bytes(f).to_hex()).collect()
+}
+
+// Variant order of madara's Transaction enum
+fn transaction_type_from_tag(tag: u8) -> Option<TransactionType> {
+    match tag {
+        0 => Some(TransactionType::Invoke),
+        1 => Some(TransactionType::L1Handler),
+        2 => Some(TransactionType::Declare),
+        3 => Some(TransactionType::Deploy),
+        4 => Some(TransactionType::DeployAccount),
+        _ => None,
+    }
+}
+
 // Helper to make transaction column key (same as madara)
 fn make_transaction_column_key(block_n: u32, tx_index: u16) -> [u8; 6] {
     let mut key = [0u8; 6];
@@ -385,52 +575,113 @@ fn make_transaction_column_key(block_n: u32, tx_index: u16) -> [u8; 6] {
 
 impl DbReader {
     /// Get transaction by block number and index
-    fn get_raw_transaction(
+    pub(crate) fn get_raw_transaction(
         &self,
         block_n: u64,
         tx_index: u64,
-    ) -> Option<RawTransactionWithReceipt> {
-        use bincode::Options;
-
-        let cf = self.db.cf_handle("block_transactions")?;
-        let block_n_u32 = u32::try_from(block_n).ok()?;
-        let tx_index_u16 = u16::try_from(tx_index).ok()?;
+    ) -> Result<Option<RawTransactionWithReceipt>, DbError> {
+        let Some(cf) = self.db.cf_handle("block_transactions") else {
+            return Ok(None);
+        };
+        let (Ok(block_n_u32), Ok(tx_index_u16)) = (u32::try_from(block_n), u16::try_from(tx_index))
+        else {
+            return Ok(None);
+        };
         let key = make_transaction_column_key(block_n_u32, tx_index_u16);
-        let value = self.db.get_cf(&cf, key).ok()??;
+        let Some(value) = self.db.get_cf(&cf, key)? else {
+            return Ok(None);
+        };
 
-        let opts = bincode::DefaultOptions::new();
-        match opts.deserialize::<RawTransactionWithReceipt>(&value) {
-            Ok(tx) => Some(tx),
-            Err(e) => {
-                eprintln!("Failed to deserialize tx {}/{}: {}", block_n, tx_index, e);
-                eprintln!("First 100 bytes: {:?}", &value[..100.min(value.len())]);
-                None
+        Ok(Some(self.decoder.decode_transaction(&key, &value)?))
+    }
+
+    /// Decode every transaction of a block, keeping the ones that fail to decode as errors
+    pub(crate) fn get_raw_block_transactions(
+        &self,
+        block_n: u64,
+    ) -> Vec<(usize, Result<RawTransactionWithReceipt, DecodeError>)> {
+        let Ok(block_n_u32) = u32::try_from(block_n) else {
+            return vec![];
+        };
+        let Some(cf) = self.db.cf_handle("block_transactions") else {
+            return vec![];
+        };
+
+        let prefix = block_n_u32.to_be_bytes();
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&prefix, Direction::Forward));
+
+        let mut txs = Vec::new();
+        for (key, value) in iter.flatten() {
+            if key.len() != 6 || key[..4] != prefix {
+                break;
             }
+            let tx_index = u16::from_be_bytes([key[4], key[5]]) as usize;
+            txs.push((tx_index, self.decoder.decode_transaction(&key, &value)));
         }
+        txs
     }
 
-    /// Get transactions for a block using tx_hashes from block_info
-    /// (simplified - no full transaction deserialization for now)
+    /// Get transactions for a block with a single range scan over `block_transactions`
     pub fn get_block_transactions(&self, block_n: u64) -> Vec<TransactionSummary> {
-        // Get the block info which has tx_hashes
-        let block = match self.get_block_detail(block_n) {
-            Some(b) => b,
+        let Ok(block_n_u32) = u32::try_from(block_n) else {
+            return vec![];
+        };
+        let cf = match self.db.cf_handle("block_transactions") {
+            Some(cf) => cf,
             None => return vec![],
         };
 
-        // Create transaction summaries from tx_hashes
-        block
-            .tx_hashes
-            .into_iter()
-            .enumerate()
-            .map(|(tx_index, tx_hash)| TransactionSummary {
-                tx_hash,
-                tx_type: TransactionType::Invoke, // Default type - we'd need full deserialization to know
-                status: ExecutionStatus::Succeeded, // Default - we'd need full deserialization
-                block_number: block_n,
-                tx_index,
-            })
-            .collect()
+        let prefix = block_n_u32.to_be_bytes();
+        // Only needed when an entry fails to decode and we have to recover its hash
+        let mut tx_hashes: Option<Vec<String>> = None;
+        let mut summaries = Vec::new();
+
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&prefix, Direction::Forward));
+
+        for (key, value) in iter.flatten() {
+            if key.len() != 6 || key[..4] != prefix {
+                break;
+            }
+            let tx_index = u16::from_be_bytes([key[4], key[5]]) as usize;
+
+            match self.decoder.decode_transaction(&key, &value) {
+                Ok(raw) => summaries.push(raw.to_summary(block_n, tx_index)),
+                Err(_) => {
+                    // The detail view reports the decode error. The transaction enum tag is
+                    // the first field, so the type survives a failed receipt decode.
+                    let Some(tx_type) = value
+                        .first()
+                        .and_then(|&tag| transaction_type_from_tag(tag))
+                    else {
+                        continue;
+                    };
+                    let hashes = tx_hashes.get_or_insert_with(|| {
+                        self.get_block_detail(block_n)
+                            .ok()
+                            .flatten()
+                            .map(|b| b.tx_hashes)
+                            .unwrap_or_default()
+                    });
+                    let Some(tx_hash) = hashes.get(tx_index).cloned() else {
+                        continue;
+                    };
+
+                    summaries.push(TransactionSummary {
+                        tx_hash,
+                        tx_type,
+                        status: None,
+                        block_number: block_n,
+                        tx_index,
+                    });
+                }
+            }
+        }
+
+        summaries
     }
 
     /// Find transaction by hash
@@ -458,80 +709,43 @@ impl DbReader {
     }
 
     /// Get transaction detail
-    /// Note: Full deserialization is complex, so we return minimal info for now
-    pub fn get_transaction_detail(&self, block_n: u64, tx_index: u64) -> Option<TransactionDetail> {
-        // Try full deserialization first
-        if let Some(raw_tx) = self.get_raw_transaction(block_n, tx_index) {
-            return Some(raw_tx.to_detail(block_n, tx_index as usize));
-        }
-
-        // Fallback: return minimal info from block's tx_hashes
-        let block = self.get_block_detail(block_n)?;
-        let tx_hash = block.tx_hashes.get(tx_index as usize)?.clone();
-
-        Some(TransactionDetail {
-            tx_hash,
-            tx_type: TransactionType::Invoke, // Unknown - full deserialization failed
-            status: ExecutionStatus::Succeeded, // Unknown
-            block_number: block_n,
-            tx_index: tx_index as usize,
-            actual_fee: "0x0".to_string(),
-            fee_unit: "UNKNOWN".to_string(),
-            events: vec![],
-            messages_sent: vec![],
-            sender_address: None,
-            calldata: vec![],
-            signature: vec![],
-            nonce: None,
-            version: None,
-        })
-    }
-
-    /// Internal method to get raw block (re-exported from blocks module)
-    fn get_raw_block_internal(
+    pub fn get_transaction_detail(
         &self,
         block_n: u64,
-    ) -> Option<super::blocks::RawMadaraBlockInfoInternal> {
-        use bincode::Options;
-
-        let cf = self.db.cf_handle("block_info")?;
-        let block_n_u32 = u32::try_from(block_n).ok()?;
-        let value = self.db.get_cf(&cf, block_n_u32.to_be_bytes()).ok()??;
-
-        let opts = bincode::DefaultOptions::new();
-        match opts.deserialize::<super::blocks::RawMadaraBlockInfoInternal>(&value) {
-            Ok(block) => Some(block),
-            Err(e) => {
-                eprintln!("Failed to deserialize block_info for {}: {}", block_n, e);
-                None
-            }
-        }
+        tx_index: u64,
+    ) -> Result<Option<TransactionDetail>, DbError> {
+        Ok(self
+            .get_raw_transaction(block_n, tx_index)?
+            .map(|raw_tx| raw_tx.to_detail(block_n, tx_index as usize)))
     }
 }
 
 impl RawTransactionWithReceipt {
     fn to_summary(&self, block_number: u64, tx_index: usize) -> TransactionSummary {
         let tx_hash = self.get_tx_hash();
-        let tx_type = self.get_tx_type();
+        let tx_type = self.transaction.get_tx_type();
         let status = self.get_status();
 
         TransactionSummary {
             tx_hash,
             tx_type,
-            status,
+            status: Some(status),
             block_number,
             tx_index,
         }
     }
 
-    fn to_detail(&self, block_number: u64, tx_index: usize) -> TransactionDetail {
+    pub(crate) fn to_detail(&self, block_number: u64, tx_index: usize) -> TransactionDetail {
         let tx_hash = self.get_tx_hash();
-        let tx_type = self.get_tx_type();
+        let tx_type = self.transaction.get_tx_type();
         let status = self.get_status();
         let (actual_fee, fee_unit) = self.get_fee();
         let events = self.get_events();
         let messages_sent = self.get_messages();
-        let (sender_address, calldata, signature, nonce, version) = self.get_tx_fields();
+        let (sender_address, calldata, signature, nonce, version) =
+            self.transaction.get_tx_fields();
+        let body = self.transaction.get_body();
+        let execution_resources = self.get_execution_resources();
 
         TransactionDetail {
             tx_hash,
@@ -548,52 +762,54 @@ impl RawTransactionWithReceipt {
             signature,
             nonce,
             version,
+            body: Some(body),
+            execution_resources: Some(execution_resources),
         }
     }
 
-    fn get_tx_hash(&self) -> String {
-        let bytes = match &self.receipt {
+    pub(crate) fn raw_tx_hash(&self) -> &ByteBuf {
+        match &self.receipt {
             RawTransactionReceipt::Invoke(r) => &r.transaction_hash,
             RawTransactionReceipt::L1Handler(r) => &r.transaction_hash,
             RawTransactionReceipt::Declare(r) => &r.transaction_hash,
             RawTransactionReceipt::Deploy(r) => &r.transaction_hash,
             RawTransactionReceipt::DeployAccount(r) => &r.transaction_hash,
-        };
-        Felt::from_bytes(bytes).to_hex()
+        }
     }
 
-    fn get_tx_type(&self) -> TransactionType {
-        match &self.transaction {
-            RawTransaction::Invoke(_) => TransactionType::Invoke,
-            RawTransaction::L1Handler(_) => TransactionType::L1Handler,
-            RawTransaction::Declare(_) => TransactionType::Declare,
-            RawTransaction::Deploy(_) => TransactionType::Deploy,
-            RawTransaction::DeployAccount(_) => TransactionType::DeployAccount,
-        }
+    pub(crate) fn get_tx_hash(&self) -> String {
+        Felt::from_bytes(self.raw_tx_hash()).to_hex()
     }
 
-    fn get_status(&self) -> ExecutionStatus {
-        let result = match &self.receipt {
+    pub(crate) fn raw_execution_result(&self) -> &RawExecutionResult {
+        match &self.receipt {
             RawTransactionReceipt::Invoke(r) => &r.execution_result,
             RawTransactionReceipt::L1Handler(r) => &r.execution_result,
             RawTransactionReceipt::Declare(r) => &r.execution_result,
             RawTransactionReceipt::Deploy(r) => &r.execution_result,
             RawTransactionReceipt::DeployAccount(r) => &r.execution_result,
-        };
-        match result {
+        }
+    }
+
+    fn get_status(&self) -> ExecutionStatus {
+        match self.raw_execution_result() {
             RawExecutionResult::Succeeded => ExecutionStatus::Succeeded,
             RawExecutionResult::Reverted { reason } => ExecutionStatus::Reverted(reason.clone()),
         }
     }
 
-    fn get_fee(&self) -> (String, String) {
-        let fee = match &self.receipt {
+    pub(crate) fn raw_actual_fee(&self) -> &RawFeePayment {
+        match &self.receipt {
             RawTransactionReceipt::Invoke(r) => &r.actual_fee,
             RawTransactionReceipt::L1Handler(r) => &r.actual_fee,
             RawTransactionReceipt::Declare(r) => &r.actual_fee,
             RawTransactionReceipt::Deploy(r) => &r.actual_fee,
             RawTransactionReceipt::DeployAccount(r) => &r.actual_fee,
-        };
+        }
+    }
+
+    fn get_fee(&self) -> (String, String) {
+        let fee = self.raw_actual_fee();
         let amount = Felt::from_bytes(&fee.amount).to_hex();
         let unit = match fee.unit {
             RawPriceUnit::Wei => "WEI".to_string(),
@@ -602,15 +818,32 @@ impl RawTransactionWithReceipt {
         (amount, unit)
     }
 
-    fn get_events(&self) -> Vec<EventInfo> {
-        let events = match &self.receipt {
+    pub(crate) fn raw_execution_resources(&self) -> &RawExecutionResources {
+        match &self.receipt {
+            RawTransactionReceipt::Invoke(r) => &r.execution_resources,
+            RawTransactionReceipt::L1Handler(r) => &r.execution_resources,
+            RawTransactionReceipt::Declare(r) => &r.execution_resources,
+            RawTransactionReceipt::Deploy(r) => &r.execution_resources,
+            RawTransactionReceipt::DeployAccount(r) => &r.execution_resources,
+        }
+    }
+
+    fn get_execution_resources(&self) -> ExecutionResources {
+        self.raw_execution_resources().into()
+    }
+
+    pub(crate) fn raw_events(&self) -> &[RawEvent] {
+        match &self.receipt {
             RawTransactionReceipt::Invoke(r) => &r.events,
             RawTransactionReceipt::L1Handler(r) => &r.events,
             RawTransactionReceipt::Declare(r) => &r.events,
             RawTransactionReceipt::Deploy(r) => &r.events,
             RawTransactionReceipt::DeployAccount(r) => &r.events,
-        };
-        events
+        }
+    }
+
+    fn get_events(&self) -> Vec<EventInfo> {
+        self.raw_events()
             .iter()
             .map(|e| EventInfo {
                 from_address: Felt::from_bytes(&e.from_address).to_hex(),
@@ -628,15 +861,18 @@ impl RawTransactionWithReceipt {
             .collect()
     }
 
-    fn get_messages(&self) -> Vec<MessageInfo> {
-        let messages = match &self.receipt {
+    pub(crate) fn raw_messages(&self) -> &[RawMsgToL1] {
+        match &self.receipt {
             RawTransactionReceipt::Invoke(r) => &r.messages_sent,
             RawTransactionReceipt::L1Handler(r) => &r.messages_sent,
             RawTransactionReceipt::Declare(r) => &r.messages_sent,
             RawTransactionReceipt::Deploy(r) => &r.messages_sent,
             RawTransactionReceipt::DeployAccount(r) => &r.messages_sent,
-        };
-        messages
+        }
+    }
+
+    fn get_messages(&self) -> Vec<MessageInfo> {
+        self.raw_messages()
             .iter()
             .map(|m| MessageInfo {
                 from_address: Felt::from_bytes(&m.from_address).to_hex(),
@@ -649,8 +885,147 @@ impl RawTransactionWithReceipt {
             })
             .collect()
     }
+}
+
+impl RawTransaction {
+    pub(crate) fn get_tx_type(&self) -> TransactionType {
+        match self {
+            RawTransaction::Invoke(_) => TransactionType::Invoke,
+            RawTransaction::L1Handler(_) => TransactionType::L1Handler,
+            RawTransaction::Declare(_) => TransactionType::Declare,
+            RawTransaction::Deploy(_) => TransactionType::Deploy,
+            RawTransaction::DeployAccount(_) => TransactionType::DeployAccount,
+        }
+    }
+
+    /// L1 handler and deploy transactions carry no signature
+    pub(crate) fn raw_signature(&self) -> &[ByteBuf] {
+        match self {
+            RawTransaction::Invoke(RawInvokeTransaction::V0(t)) => &t.signature,
+            RawTransaction::Invoke(RawInvokeTransaction::V1(t)) => &t.signature,
+            RawTransaction::Invoke(RawInvokeTransaction::V3(t)) => &t.signature,
+            RawTransaction::Declare(RawDeclareTransaction::V0(t)) => &t.signature,
+            RawTransaction::Declare(RawDeclareTransaction::V1(t)) => &t.signature,
+            RawTransaction::Declare(RawDeclareTransaction::V2(t)) => &t.signature,
+            RawTransaction::Declare(RawDeclareTransaction::V3(t)) => &t.signature,
+            RawTransaction::DeployAccount(RawDeployAccountTransaction::V1(t)) => &t.signature,
+            RawTransaction::DeployAccount(RawDeployAccountTransaction::V3(t)) => &t.signature,
+            RawTransaction::L1Handler(_) | RawTransaction::Deploy(_) => &[],
+        }
+    }
+
+    pub(crate) fn get_body(&self) -> TransactionBody {
+        let v3_fields = |resource_bounds: &RawResourceBoundsMapping,
+                         tip: u64,
+                         paymaster_data: &[ByteBuf],
+                         nonce_da_mode: u32,
+                         fee_da_mode: u32| V3Fields {
+            resource_bounds: resource_bounds.into(),
+            tip,
+            paymaster_data: felts_to_hex(paymaster_data),
+            nonce_data_availability_mode: da_mode_name(nonce_da_mode),
+            fee_data_availability_mode: da_mode_name(fee_da_mode),
+        };
+        let hex = |f: &ByteBuf| Felt::from_bytes(f).to_hex();
+
+        match self {
+            RawTransaction::Invoke(tx) => match tx {
+                RawInvokeTransaction::V0(t) => TransactionBody::Invoke {
+                    max_fee: Some(hex(&t.max_fee)),
+                    entry_point_selector: Some(hex(&t.entry_point_selector)),
+                    v3: None,
+                    account_deployment_data: vec![],
+                },
+                RawInvokeTransaction::V1(t) => TransactionBody::Invoke {
+                    max_fee: Some(hex(&t.max_fee)),
+                    entry_point_selector: None,
+                    v3: None,
+                    account_deployment_data: vec![],
+                },
+                RawInvokeTransaction::V3(t) => TransactionBody::Invoke {
+                    max_fee: None,
+                    entry_point_selector: None,
+                    v3: Some(v3_fields(
+                        &t.resource_bounds,
+                        t.tip,
+                        &t.paymaster_data,
+                        t.nonce_data_availability_mode,
+                        t.fee_data_availability_mode,
+                    )),
+                    account_deployment_data: felts_to_hex(&t.account_deployment_data),
+                },
+            },
+            RawTransaction::L1Handler(t) => TransactionBody::L1Handler {
+                contract_address: hex(&t.contract_address),
+                entry_point_selector: hex(&t.entry_point_selector),
+            },
+            RawTransaction::Declare(tx) => match tx {
+                RawDeclareTransaction::V0(t) => TransactionBody::Declare {
+                    class_hash: hex(&t.class_hash),
+                    compiled_class_hash: None,
+                    max_fee: Some(hex(&t.max_fee)),
+                    v3: None,
+                    account_deployment_data: vec![],
+                },
+                RawDeclareTransaction::V1(t) => TransactionBody::Declare {
+                    class_hash: hex(&t.class_hash),
+                    compiled_class_hash: None,
+                    max_fee: Some(hex(&t.max_fee)),
+                    v3: None,
+                    account_deployment_data: vec![],
+                },
+                RawDeclareTransaction::V2(t) => TransactionBody::Declare {
+                    class_hash: hex(&t.class_hash),
+                    compiled_class_hash: Some(hex(&t.compiled_class_hash)),
+                    max_fee: Some(hex(&t.max_fee)),
+                    v3: None,
+                    account_deployment_data: vec![],
+                },
+                RawDeclareTransaction::V3(t) => TransactionBody::Declare {
+                    class_hash: hex(&t.class_hash),
+                    compiled_class_hash: Some(hex(&t.compiled_class_hash)),
+                    max_fee: None,
+                    v3: Some(v3_fields(
+                        &t.resource_bounds,
+                        t.tip,
+                        &t.paymaster_data,
+                        t.nonce_data_availability_mode,
+                        t.fee_data_availability_mode,
+                    )),
+                    account_deployment_data: felts_to_hex(&t.account_deployment_data),
+                },
+            },
+            RawTransaction::Deploy(t) => TransactionBody::Deploy {
+                class_hash: hex(&t.class_hash),
+                contract_address_salt: hex(&t.contract_address_salt),
+                constructor_calldata: felts_to_hex(&t.constructor_calldata),
+            },
+            RawTransaction::DeployAccount(tx) => match tx {
+                RawDeployAccountTransaction::V1(t) => TransactionBody::DeployAccount {
+                    class_hash: hex(&t.class_hash),
+                    contract_address_salt: hex(&t.contract_address_salt),
+                    constructor_calldata: felts_to_hex(&t.constructor_calldata),
+                    max_fee: Some(hex(&t.max_fee)),
+                    v3: None,
+                },
+                RawDeployAccountTransaction::V3(t) => TransactionBody::DeployAccount {
+                    class_hash: hex(&t.class_hash),
+                    contract_address_salt: hex(&t.contract_address_salt),
+                    constructor_calldata: felts_to_hex(&t.constructor_calldata),
+                    max_fee: None,
+                    v3: Some(v3_fields(
+                        &t.resource_bounds,
+                        t.tip,
+                        &t.paymaster_data,
+                        t.nonce_data_availability_mode,
+                        t.fee_data_availability_mode,
+                    )),
+                },
+            },
+        }
+    }
 
-    fn get_tx_fields(
+    pub(crate) fn get_tx_fields(
         &self,
     ) -> (
         Option<String>,
@@ -659,7 +1034,7 @@ impl RawTransactionWithReceipt {
         Option<String>,
         Option<String>,
     ) {
-        match &self.transaction {
+        match self {
             RawTransaction::Invoke(tx) => match tx {
                 RawInvokeTransaction::V0(t) => (
                     Some(Felt::from_bytes(&t.contract_address).to_hex()),
@@ -794,3 +1169,132 @@ impl RawTransactionWithReceipt {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{BLOCK_INFO, DECLARE_V3, L1_HANDLER};
+    use crate::test_utils::open_db;
+
+    fn decode(value: &str) -> RawTransactionWithReceipt {
+        use bincode::Options;
+        bincode::DefaultOptions::new()
+            .deserialize(&hex::decode(value).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn decodes_v3_resource_bounds_from_hex_strings() {
+        let detail = decode(DECLARE_V3).to_detail(4, 0);
+        let Some(TransactionBody::Declare {
+            class_hash,
+            compiled_class_hash,
+            max_fee,
+            v3: Some(v3),
+            ..
+        }) = detail.body
+        else {
+            panic!("expected a declare V3 body");
+        };
+        assert_eq!(
+            class_hash,
+            "0x03813488a19a9361784e5f46a9fa82f007ca680c3e02d2678fb1d806115a9f8a"
+        );
+        assert_eq!(
+            compiled_class_hash.as_deref(),
+            Some("0x06ad580fc695566497e5e4a22fccf3d1f7f680b32aa29457843a7144397a525b")
+        );
+        assert_eq!(max_fee, None);
+
+        let bounds = v3.resource_bounds;
+        // "0x100000" and "0x1000000000"
+        assert_eq!(bounds.l1_gas.max_amount, 0x100000);
+        assert_eq!(bounds.l1_gas.max_price_per_unit, 0x10_0000_0000);
+        assert_eq!(bounds.l2_gas.max_amount, 0x2_fa64_db00);
+        assert_eq!(bounds.l1_data_gas.unwrap().max_amount, 0x100000);
+        assert_eq!(v3.tip, 0);
+        assert!(v3.paymaster_data.is_empty());
+        assert_eq!(v3.nonce_data_availability_mode, "L1");
+        assert_eq!(v3.fee_data_availability_mode, "L1");
+    }
+
+    #[test]
+    fn decodes_l1_handler_body() {
+        let detail = decode(L1_HANDLER).to_detail(4, 1);
+        let Some(TransactionBody::L1Handler {
+            contract_address,
+            entry_point_selector,
+        }) = detail.body
+        else {
+            panic!("expected an L1 handler body");
+        };
+        assert_eq!(contract_address, "0x1234");
+        assert_eq!(entry_point_selector, "0xabcd");
+        assert_eq!(detail.calldata, ["0xeeee", "0x01", "0x02"]);
+        assert_eq!(detail.nonce.as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn lists_one_block_and_falls_back_to_the_tag() {
+        let entry = |block_n: u32, tx_index: u16, value: Vec<u8>| {
+            (
+                "block_transactions",
+                make_transaction_column_key(block_n, tx_index).to_vec(),
+                value,
+            )
+        };
+        // Same handler with its execution result switched to `Reverted { reason: "oops" }`
+        let reverted = format!("{}01046f6f7073", &L1_HANDLER[..L1_HANDLER.len() - 2]);
+        // The declare with the end of its receipt cut off
+        let corrupt = hex::decode(DECLARE_V3).unwrap()[..260].to_vec();
+        let (_dir, reader) = open_db(&[
+            (
+                "block_info",
+                4u32.to_be_bytes().to_vec(),
+                hex::decode(BLOCK_INFO).unwrap(),
+            ),
+            entry(3, 0, hex::decode(L1_HANDLER).unwrap()),
+            entry(4, 0, corrupt),
+            entry(4, 1, hex::decode(reverted).unwrap()),
+            entry(5, 0, hex::decode(DECLARE_V3).unwrap()),
+        ]);
+
+        let txs = reader.get_block_transactions(4);
+        let summary: Vec<_> = txs
+            .iter()
+            .map(|tx| (tx.block_number, tx.tx_index, tx.tx_type, tx.status.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (4, 0, TransactionType::Declare, None),
+                (
+                    4,
+                    1,
+                    TransactionType::L1Handler,
+                    Some(ExecutionStatus::Reverted("oops".to_string()))
+                ),
+            ]
+        );
+        // The hash of the undecodable entry comes from the block header
+        assert_eq!(
+            txs[0].tx_hash,
+            "0x068dc917fe3e96612b4547f85853c3021c49059c9ca5b49a8d7b5425765ab6fe"
+        );
+
+        let txs = reader.get_block_transactions(5);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].status, Some(ExecutionStatus::Succeeded));
+        assert!(reader.get_block_transactions(6).is_empty());
+    }
+
+    #[test]
+    fn maps_transaction_tags() {
+        assert_eq!(transaction_type_from_tag(0), Some(TransactionType::Invoke));
+        assert_eq!(
+            transaction_type_from_tag(4),
+            Some(TransactionType::DeployAccount)
+        );
+        assert_eq!(transaction_type_from_tag(5), None);
+    }
+}
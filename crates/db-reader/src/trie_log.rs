@@ -0,0 +1,431 @@
+//! Bonsai trie change logs (`bonsai_*_log`).
+//!
+//! For every committed block, bonsai records each trie entry it rewrote so the tries can be
+//! reverted later. Keys are `block_n (u64 BE) ++ 0x00 ++ trie key ++ kind ++ change`, where the
+//! trie key is a `_trie` node key for kind `0` and a `_flat` leaf key for kind `1`, and change
+//! `0` holds the value after the block and `1` the value before it. Values are stored as-is:
+//! an encoded node, or a 32-byte felt for leaves. Inserted entries have no old value and
+//! removed ones no new value.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use rocksdb::{Direction, IteratorMode};
+use starknet_types_core::felt::Felt as StarkFelt;
+
+use crate::audit::felt_key;
+use crate::proofs::class_leaf_hash;
+use crate::tries::{CLASS_IDENTIFIER, CONTRACT_IDENTIFIER, TRIE_HEIGHT};
+use crate::{DbError, DbReader, DecodeError, Felt, Trie};
+
+/// A trie leaf rewritten by a block
+#[derive(Debug, Clone)]
+pub struct TrieLeafChange {
+    pub trie: Trie,
+    /// Contract owning the storage trie, for storage leaves
+    pub contract_address: Option<String>,
+    pub key: String,
+    /// `None` when the block inserted the leaf
+    pub old_value: Option<String>,
+    /// `None` when the block removed the leaf
+    pub new_value: Option<String>,
+}
+
+/// A leaf where the trie log and `block_state_diff` disagree
+#[derive(Debug, Clone)]
+pub struct TrieLogMismatch {
+    pub trie: Trie,
+    pub contract_address: Option<String>,
+    pub key: String,
+    pub reason: String,
+}
+
+/// Trie changes a block recorded in the bonsai logs
+#[derive(Debug, Clone)]
+pub struct BlockTrieChanges {
+    pub block_number: u64,
+    pub leaves: Vec<TrieLeafChange>,
+    /// Internal nodes rewritten across all three tries
+    pub nodes_changed: usize,
+    /// `false` when no log exists for the block: it changed nothing, or the log was pruned
+    pub has_log: bool,
+    /// Always empty without a log
+    pub mismatches: Vec<TrieLogMismatch>,
+}
+
+/// Old and new value of one leaf, keyed by `(trie, identifier, key)`
+type LeafLog = BTreeMap<(Trie, Vec<u8>, [u8; 32]), (Option<[u8; 32]>, Option<[u8; 32]>)>;
+
+fn identifier_len(trie: Trie) -> usize {
+    match trie {
+        Trie::Contract => CONTRACT_IDENTIFIER.len(),
+        Trie::ContractStorage => 32,
+        Trie::Class => CLASS_IDENTIFIER.len(),
+    }
+}
+
+fn felt_hex(bytes: &[u8]) -> String {
+    Felt::from_bytes(bytes).to_hex()
+}
+
+impl DbReader {
+    /// Read one trie's log entries for `block_n` into `leaves`, returning how many node
+    /// entries it held
+    fn read_trie_log(
+        &self,
+        trie: Trie,
+        block_n: u64,
+        leaves: &mut LeafLog,
+    ) -> Result<usize, DbError> {
+        let Some(cf) = self.db.cf_handle(trie.log_cf()) else {
+            return Ok(0);
+        };
+        let mut prefix = block_n.to_be_bytes().to_vec();
+        prefix.push(0);
+
+        let mut nodes = HashSet::new();
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&prefix, Direction::Forward));
+        for item in iter {
+            let (key, value) = item?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            let error = |offset: usize, expected_type: &str, message: String| DecodeError {
+                cf: trie.log_cf().to_string(),
+                key: hex::encode(&key),
+                offset,
+                expected_type: expected_type.to_string(),
+                message,
+            };
+
+            let entry_key = &key[prefix.len()..];
+            if entry_key.len() < 2 {
+                return Err(error(key.len(), "TrieLogKey", "key too short".to_string()).into());
+            }
+            let (trie_key, suffix) = entry_key.split_at(entry_key.len() - 2);
+            let (kind, change) = (suffix[0], suffix[1]);
+            match kind {
+                0 => {
+                    nodes.insert(trie_key.to_vec());
+                }
+                1 => {
+                    let id_len = identifier_len(trie);
+                    if trie_key.len() != id_len + 33 || trie_key[id_len] != TRIE_HEIGHT as u8 {
+                        return Err(error(
+                            prefix.len(),
+                            "TrieLogKey",
+                            format!("{} byte leaf key", trie_key.len()),
+                        )
+                        .into());
+                    }
+                    let leaf_value: [u8; 32] = value.as_ref().try_into().map_err(|_| {
+                        error(0, "Felt", format!("{} byte leaf value", value.len()))
+                    })?;
+                    let leaf_key = trie_key[id_len + 1..].try_into().expect("32-byte key");
+                    let entry = leaves
+                        .entry((trie, trie_key[..id_len].to_vec(), leaf_key))
+                        .or_default();
+                    match change {
+                        0 => entry.1 = Some(leaf_value),
+                        _ => entry.0 = Some(leaf_value),
+                    }
+                }
+                other => {
+                    return Err(error(
+                        key.len() - 2,
+                        "TrieLogKey",
+                        format!("unknown entry kind {other}"),
+                    )
+                    .into())
+                }
+            }
+        }
+        Ok(nodes.len())
+    }
+
+    /// Decode the trie log entries of a block and check them against its state diff
+    pub fn get_trie_changes(&self, block_n: u64) -> Result<BlockTrieChanges, DbError> {
+        let mut leaf_log = LeafLog::new();
+        let mut nodes_changed = 0;
+        for trie in [Trie::Contract, Trie::ContractStorage, Trie::Class] {
+            nodes_changed += self.read_trie_log(trie, block_n, &mut leaf_log)?;
+        }
+        let has_log = nodes_changed > 0 || !leaf_log.is_empty();
+        let mismatches = if has_log {
+            self.trie_log_mismatches(block_n, &leaf_log)?
+        } else {
+            Vec::new()
+        };
+
+        let leaves = leaf_log
+            .into_iter()
+            .map(
+                |((trie, identifier, key), (old_value, new_value))| TrieLeafChange {
+                    trie,
+                    contract_address: (trie == Trie::ContractStorage)
+                        .then(|| felt_hex(&identifier)),
+                    key: felt_hex(&key),
+                    old_value: old_value.map(|v| felt_hex(&v)),
+                    new_value: new_value.map(|v| felt_hex(&v)),
+                },
+            )
+            .collect();
+
+        Ok(BlockTrieChanges {
+            block_number: block_n,
+            leaves,
+            nodes_changed,
+            has_log,
+            mismatches,
+        })
+    }
+
+    /// Compare the leaves a block rewrote with what its state diff says it changed.
+    ///
+    /// Storage and class leaves are compared by value; contract leaves are hashes over state
+    /// the diff only partly holds, so only the set of contracts is compared.
+    fn trie_log_mismatches(
+        &self,
+        block_n: u64,
+        leaf_log: &LeafLog,
+    ) -> Result<Vec<TrieLogMismatch>, DbError> {
+        let diff = self.get_raw_state_diff(block_n)?;
+        let zero = [0u8; 32];
+        let mut mismatches = Vec::new();
+        let mut mismatch = |trie: Trie, identifier: &[u8], key: &[u8; 32], reason: String| {
+            mismatches.push(TrieLogMismatch {
+                trie,
+                contract_address: (trie == Trie::ContractStorage).then(|| felt_hex(identifier)),
+                key: felt_hex(key),
+                reason,
+            })
+        };
+        let logged = |trie: Trie, identifier: &[u8], key: &[u8; 32]| {
+            leaf_log.get(&(trie, identifier.to_vec(), *key))
+        };
+
+        // Storage: every write shows up as the leaf's new value, a zero write as a removal
+        let mut written = BTreeSet::new();
+        for item in &diff.storage_diffs {
+            let address = felt_key(&item.address);
+            for entry in &item.storage_entries {
+                let key = felt_key(&entry.key);
+                let value = felt_key(&entry.value);
+                written.insert((address.to_vec(), key));
+                let expected = (value != zero).then_some(value);
+                match logged(Trie::ContractStorage, &address, &key) {
+                    Some((_, new_value)) if *new_value == expected => {}
+                    Some((_, new_value)) => mismatch(
+                        Trie::ContractStorage,
+                        &address,
+                        &key,
+                        format!(
+                            "state diff writes {}, trie log has {}",
+                            felt_hex(&value),
+                            new_value.map_or("a removal".to_string(), |v| felt_hex(&v))
+                        ),
+                    ),
+                    None => {
+                        // Rewriting the value a slot already held leaves the trie untouched
+                        let previous = block_n
+                            .checked_sub(1)
+                            .and_then(|parent| {
+                                self.get_storage_at(&felt_hex(&address), &felt_hex(&key), parent)
+                            })
+                            .map(|entry| entry.value)
+                            .unwrap_or_else(|| felt_hex(&zero));
+                        if previous != felt_hex(&value) {
+                            mismatch(
+                                Trie::ContractStorage,
+                                &address,
+                                &key,
+                                format!(
+                                    "state diff writes {}, missing from the trie log",
+                                    felt_hex(&value)
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        // Classes: each declaration sets the leaf to the hash of its compiled class hash
+        let mut declared = BTreeSet::new();
+        for (class_hash, compiled) in diff
+            .declared_classes
+            .iter()
+            .map(|c| (&c.class_hash, &c.compiled_class_hash))
+            .chain(
+                diff.migrated_compiled_classes
+                    .iter()
+                    .map(|c| (&c.class_hash, &c.compiled_class_hash)),
+            )
+        {
+            let class_hash = felt_key(class_hash);
+            declared.insert(class_hash);
+            let leaf = class_leaf_hash(&StarkFelt::from_bytes_be_slice(compiled)).to_bytes_be();
+            match logged(Trie::Class, CLASS_IDENTIFIER, &class_hash) {
+                Some((_, Some(new_value))) if *new_value == leaf => {}
+                Some((_, new_value)) => mismatch(
+                    Trie::Class,
+                    CLASS_IDENTIFIER,
+                    &class_hash,
+                    format!(
+                        "state diff implies leaf {}, trie log has {}",
+                        felt_hex(&leaf),
+                        new_value.map_or("a removal".to_string(), |v| felt_hex(&v))
+                    ),
+                ),
+                None => mismatch(
+                    Trie::Class,
+                    CLASS_IDENTIFIER,
+                    &class_hash,
+                    "declared in the state diff, missing from the trie log".to_string(),
+                ),
+            }
+        }
+
+        // Contracts: any storage, nonce or class change rewrites the contract's leaf
+        let touched: BTreeSet<[u8; 32]> = diff
+            .storage_diffs
+            .iter()
+            .map(|s| felt_key(&s.address))
+            .chain(diff.deployed_contracts.iter().map(|d| felt_key(&d.address)))
+            .chain(
+                diff.replaced_classes
+                    .iter()
+                    .map(|r| felt_key(&r.contract_address)),
+            )
+            .chain(diff.nonces.iter().map(|n| felt_key(&n.contract_address)))
+            .collect();
+        for address in &touched {
+            if logged(Trie::Contract, CONTRACT_IDENTIFIER, address).is_none() {
+                mismatch(
+                    Trie::Contract,
+                    CONTRACT_IDENTIFIER,
+                    address,
+                    "changed by the state diff, leaf missing from the trie log".to_string(),
+                );
+            }
+        }
+
+        for (trie, identifier, key) in leaf_log.keys() {
+            let trie = *trie;
+            let in_diff = match trie {
+                Trie::Contract => touched.contains(key),
+                Trie::ContractStorage => written.contains(&(identifier.clone(), *key)),
+                Trie::Class => declared.contains(key),
+            };
+            if !in_diff {
+                mismatch(
+                    trie,
+                    identifier,
+                    key,
+                    "changed in the trie log, absent from the state diff".to_string(),
+                );
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::open_db;
+    use crate::tries::flat_db_key;
+    use serde_bytes::ByteBuf;
+
+    fn felt(last: u8) -> [u8; 32] {
+        let mut felt = [0u8; 32];
+        felt[31] = last;
+        felt
+    }
+
+    fn log_key(block_n: u64, trie_key: &[u8], kind: u8, change: u8) -> Vec<u8> {
+        let mut key = block_n.to_be_bytes().to_vec();
+        key.push(0);
+        key.extend_from_slice(trie_key);
+        key.extend([kind, change]);
+        key
+    }
+
+    /// bincode `StateDiff` with a single storage write
+    fn storage_diff(address: [u8; 32], key: [u8; 32], value: [u8; 32]) -> Vec<u8> {
+        use bincode::Options;
+        let buf = |b: [u8; 32]| ByteBuf::from(b.to_vec());
+        let diff = (
+            vec![(buf(address), vec![(buf(key), buf(value))])],
+            Vec::<ByteBuf>::new(),
+            Vec::<(ByteBuf, ByteBuf)>::new(),
+            Vec::<(ByteBuf, ByteBuf)>::new(),
+            Vec::<(ByteBuf, ByteBuf)>::new(),
+            Vec::<(ByteBuf, ByteBuf)>::new(),
+            Vec::<(ByteBuf, ByteBuf)>::new(),
+        );
+        bincode::DefaultOptions::new().serialize(&diff).unwrap()
+    }
+
+    #[test]
+    fn decodes_leaf_changes_and_flags_disagreement() {
+        let address = felt(0x12);
+        let storage_log = |block_n, key: &[u8; 32], change, value: &[u8]| {
+            (
+                "bonsai_contract_storage_log",
+                log_key(block_n, &flat_db_key(&address, key), 1, change),
+                value.to_vec(),
+            )
+        };
+        let contract_log = |block_n, value: u8| {
+            (
+                "bonsai_contract_log",
+                log_key(block_n, &flat_db_key(CONTRACT_IDENTIFIER, &address), 1, 0),
+                felt(value).to_vec(),
+            )
+        };
+        let (_dir, reader) = open_db(&[
+            // Block 1 writes 0x5 = 0x2a and the log agrees
+            (
+                "block_state_diff",
+                1u32.to_be_bytes().to_vec(),
+                storage_diff(address, felt(5), felt(0x2a)),
+            ),
+            storage_log(1, &felt(5), 0, &felt(0x2a)),
+            storage_log(1, &felt(5), 1, &felt(0x07)),
+            (
+                "bonsai_contract_storage_log",
+                log_key(1, &[0u8; 33], 0, 0),
+                vec![0],
+            ),
+            contract_log(1, 0x99),
+            // Block 2 writes 0x6 but the log changed 0x7 instead
+            (
+                "block_state_diff",
+                2u32.to_be_bytes().to_vec(),
+                storage_diff(address, felt(6), felt(1)),
+            ),
+            storage_log(2, &felt(7), 0, &felt(1)),
+            contract_log(2, 0x98),
+        ]);
+
+        let changes = reader.get_trie_changes(1).unwrap();
+        assert!(changes.has_log);
+        assert_eq!(changes.nodes_changed, 1);
+        assert!(changes.mismatches.is_empty(), "{:?}", changes.mismatches);
+        let storage = &changes.leaves[1];
+        assert_eq!(storage.trie, Trie::ContractStorage);
+        assert_eq!(storage.contract_address.as_deref(), Some("0x12"));
+        assert_eq!(storage.key, "0x05");
+        assert_eq!(storage.old_value.as_deref(), Some("0x07"));
+        assert_eq!(storage.new_value.as_deref(), Some("0x2a"));
+
+        let changes = reader.get_trie_changes(2).unwrap();
+        let keys: Vec<_> = changes.mismatches.iter().map(|m| m.key.as_str()).collect();
+        assert_eq!(keys, ["0x06", "0x07"]);
+
+        assert!(!reader.get_trie_changes(3).unwrap().has_log);
+    }
+}
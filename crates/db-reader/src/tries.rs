@@ -0,0 +1,592 @@
+//! Bonsai trie nodes and root-to-leaf path walks.
+//!
+//! Madara commits to global state with three bonsai tries (contract, contract storage and
+//! class). Each keeps its internal nodes in a `_trie` column family keyed by
+//! `identifier ++ path_len ++ packed_path_bits`, and its leaf values in a `_flat` column
+//! family keyed by `identifier ++ 251 ++ key` (the full 32-byte key behind its bit length).
+//! The identifier is `0xcontract` / `0xclass` for the global tries and the contract address
+//! for storage tries.
+//!
+//! Node values are SCALE-encoded (`parity-scale-codec`):
+//! - `Node`: `0` = binary, `1` = edge
+//! - `BinaryNode`: `hash: Option<Felt>`, `height: u64` (LE), `left`, `right`
+//! - `EdgeNode`: `hash: Option<Felt>`, `height: u64` (LE), `path`, `child`
+//! - `NodeHandle`: `0` = `Hash(Felt)`, `1` = `InMemory(u64)`
+//! - `Path`: bit length as `u8`, then the bits packed MSB-first
+//! - `Felt`: 32 bytes big-endian
+//!
+//! Reference (upstream): `bonsai-trie/src/trie/{merkle_node,path}.rs`.
+
+use crate::{parse_felt_hex, DbError, DbReader, DecodeError};
+
+/// Keys are 251-bit field elements, so every leaf sits at this depth
+pub const TRIE_HEIGHT: usize = 251;
+
+pub(crate) const CONTRACT_IDENTIFIER: &[u8] = b"0xcontract";
+pub(crate) const CLASS_IDENTIFIER: &[u8] = b"0xclass";
+
+/// One of Madara's bonsai tries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Trie {
+    /// Contract address -> contract state hash (Pedersen)
+    Contract,
+    /// (contract address, storage key) -> storage value (Pedersen), one trie per contract
+    ContractStorage,
+    /// Class hash -> class commitment (Poseidon)
+    Class,
+}
+
+impl Trie {
+    /// Parse the name used in API paths: `contract`, `storage` or `class`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "contract" => Some(Trie::Contract),
+            "storage" | "contract-storage" | "contract_storage" => Some(Trie::ContractStorage),
+            "class" => Some(Trie::Class),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Trie::Contract => "contract",
+            Trie::ContractStorage => "storage",
+            Trie::Class => "class",
+        }
+    }
+
+    /// Column family holding internal nodes
+    pub fn trie_cf(&self) -> &'static str {
+        match self {
+            Trie::Contract => "bonsai_contract_trie",
+            Trie::ContractStorage => "bonsai_contract_storage_trie",
+            Trie::Class => "bonsai_class_trie",
+        }
+    }
+
+    /// Column family holding leaf values
+    pub fn flat_cf(&self) -> &'static str {
+        match self {
+            Trie::Contract => "bonsai_contract_flat",
+            Trie::ContractStorage => "bonsai_contract_storage_flat",
+            Trie::Class => "bonsai_class_flat",
+        }
+    }
+
+    /// Column family holding per-commit change logs
+    pub fn log_cf(&self) -> &'static str {
+        match self {
+            Trie::Contract => "bonsai_contract_log",
+            Trie::ContractStorage => "bonsai_contract_storage_log",
+            Trie::Class => "bonsai_class_log",
+        }
+    }
+}
+
+/// Reference from a node to its child
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeHandle {
+    /// Hash of a committed child (or the leaf value at the bottom of the trie)
+    Hash(String),
+    /// Child not yet committed; should not appear on disk
+    InMemory(u64),
+}
+
+impl std::fmt::Display for NodeHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeHandle::Hash(hash) => f.write_str(hash),
+            NodeHandle::InMemory(id) => write!(f, "in-memory #{id}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryNode {
+    pub hash: Option<String>,
+    pub height: u64,
+    pub left: NodeHandle,
+    pub right: NodeHandle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeNode {
+    pub hash: Option<String>,
+    pub height: u64,
+    /// Compressed path segment below this node
+    pub path: Vec<bool>,
+    pub child: NodeHandle,
+}
+
+/// Decoded internal trie node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieNode {
+    Binary(BinaryNode),
+    Edge(EdgeNode),
+}
+
+impl TrieNode {
+    pub fn hash(&self) -> Option<&str> {
+        match self {
+            TrieNode::Binary(node) => node.hash.as_deref(),
+            TrieNode::Edge(node) => node.hash.as_deref(),
+        }
+    }
+}
+
+/// A node visited on the way from the root to a key
+#[derive(Debug, Clone)]
+pub struct TriePathNode {
+    /// Path of this node from the root
+    pub path: Vec<bool>,
+    pub node: TrieNode,
+    /// Branch taken below a binary node (`true` = right)
+    pub took_right: Option<bool>,
+}
+
+/// Where a walk towards a key stopped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriePathEnd {
+    /// Reached the key; the handle holds the leaf value committed in the trie
+    Leaf(NodeHandle),
+    /// An edge leads away from the key at this depth, so the key is not in the trie
+    Diverged { depth: usize },
+    /// The node at this path is referenced by its parent but missing from the trie column
+    MissingNode { path: Vec<bool> },
+    /// No root node
+    EmptyTrie,
+}
+
+/// Result of walking a trie from the root towards a key
+#[derive(Debug, Clone)]
+pub struct TriePath {
+    pub trie: Trie,
+    /// Hex-encoded trie identifier
+    pub identifier: String,
+    pub key: String,
+    pub nodes: Vec<TriePathNode>,
+    pub end: TriePathEnd,
+    /// Value stored in the flat column for this key, if any
+    pub flat_value: Option<String>,
+}
+
+impl TriePath {
+    /// Root hash as recorded on the root node
+    pub fn root_hash(&self) -> Option<&str> {
+        self.nodes.first().and_then(|n| n.node.hash())
+    }
+}
+
+/// Render a bit path as a `0`/`1` string
+pub fn bits_to_string(bits: &[bool]) -> String {
+    bits.iter().map(|&b| if b { '1' } else { '0' }).collect()
+}
+
+/// The 251 bits of a key, MSB first (the top 5 bits of the 32-byte felt are dropped)
+pub(crate) fn key_bits(key: &[u8; 32]) -> Vec<bool> {
+    let all: Vec<bool> = key
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| byte & (1 << i) != 0))
+        .collect();
+    all[256 - TRIE_HEIGHT..].to_vec()
+}
+
+/// `identifier ++ path_len ++ packed bits`, the key bonsai stores a node under
+pub(crate) fn node_db_key(identifier: &[u8], path: &[bool]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(identifier.len() + 1 + path.len().div_ceil(8));
+    key.extend_from_slice(identifier);
+    key.push(path.len() as u8);
+    for chunk in path.chunks(8) {
+        let mut byte = 0u8;
+        for (i, &bit) in chunk.iter().enumerate() {
+            if bit {
+                byte |= 1 << (7 - i);
+            }
+        }
+        key.push(byte);
+    }
+    key
+}
+
+/// `identifier ++ 251 ++ key`, the key bonsai stores a leaf value under in the flat column
+pub(crate) fn flat_db_key(identifier: &[u8], key: &[u8; 32]) -> Vec<u8> {
+    let mut flat_key = Vec::with_capacity(identifier.len() + 33);
+    flat_key.extend_from_slice(identifier);
+    flat_key.push(TRIE_HEIGHT as u8);
+    flat_key.extend_from_slice(key);
+    flat_key
+}
+
+/// Cursor over a SCALE-encoded value that remembers how far it got
+struct ScaleReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ScaleReader<'a> {
+    fn take(&mut self, n: usize, what: &str) -> Result<&'a [u8], String> {
+        let end = self.offset + n;
+        if end > self.data.len() {
+            return Err(format!(
+                "unexpected end of input reading {what} ({n} bytes, {} left)",
+                self.data.len() - self.offset
+            ));
+        }
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self, what: &str) -> Result<u8, String> {
+        Ok(self.take(1, what)?[0])
+    }
+
+    fn u64(&mut self, what: &str) -> Result<u64, String> {
+        let bytes = self.take(8, what)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn felt(&mut self, what: &str) -> Result<String, String> {
+        Ok(format!("0x{}", hex::encode(self.take(32, what)?)))
+    }
+
+    fn option_felt(&mut self, what: &str) -> Result<Option<String>, String> {
+        match self.u8(what)? {
+            0 => Ok(None),
+            1 => Ok(Some(self.felt(what)?)),
+            tag => Err(format!("invalid Option tag {tag} for {what}")),
+        }
+    }
+
+    fn handle(&mut self, what: &str) -> Result<NodeHandle, String> {
+        match self.u8(what)? {
+            0 => Ok(NodeHandle::Hash(self.felt(what)?)),
+            1 => Ok(NodeHandle::InMemory(self.u64(what)?)),
+            tag => Err(format!("invalid NodeHandle tag {tag} for {what}")),
+        }
+    }
+
+    fn path(&mut self) -> Result<Vec<bool>, String> {
+        let len = self.u8("path length")? as usize;
+        if len == 0 || len > TRIE_HEIGHT {
+            return Err(format!("invalid edge path length {len}"));
+        }
+        let bytes = self.take(len.div_ceil(8), "path bits")?;
+        Ok((0..len)
+            .map(|i| bytes[i / 8] & (1 << (7 - i % 8)) != 0)
+            .collect())
+    }
+}
+
+/// Decode a SCALE-encoded bonsai node read from `cf` at `key`
+pub(crate) fn decode_node(cf: &str, key: &[u8], value: &[u8]) -> Result<TrieNode, DecodeError> {
+    let mut reader = ScaleReader {
+        data: value,
+        offset: 0,
+    };
+
+    let decoded = (|| match reader.u8("node tag")? {
+        0 => Ok(TrieNode::Binary(BinaryNode {
+            hash: reader.option_felt("hash")?,
+            height: reader.u64("height")?,
+            left: reader.handle("left child")?,
+            right: reader.handle("right child")?,
+        })),
+        1 => Ok(TrieNode::Edge(EdgeNode {
+            hash: reader.option_felt("hash")?,
+            height: reader.u64("height")?,
+            path: reader.path()?,
+            child: reader.handle("child")?,
+        })),
+        tag => Err(format!("invalid Node tag {tag}")),
+    })();
+
+    let error = |offset: usize, message: String| DecodeError {
+        cf: cf.to_string(),
+        key: hex::encode(key),
+        offset,
+        expected_type: "Node".to_string(),
+        message,
+    };
+
+    let node = decoded.map_err(|message| error(reader.offset, message))?;
+    if reader.offset != value.len() {
+        return Err(error(
+            reader.offset,
+            format!("{} trailing bytes", value.len() - reader.offset),
+        ));
+    }
+    Ok(node)
+}
+
+impl DbReader {
+    /// Walk `trie` from its root towards `key`, returning every node on the way.
+    ///
+    /// `contract` selects the per-contract trie and is required for
+    /// [`Trie::ContractStorage`]; it is ignored otherwise.
+    pub fn get_trie_path(
+        &self,
+        trie: Trie,
+        contract: Option<&str>,
+        key: &str,
+    ) -> Result<TriePath, DbError> {
+        let identifier = trie_identifier(trie, contract)?;
+        let key_bytes = parse_trie_key(key)?;
+        let (nodes, end) = self.walk_trie(trie, &identifier, &key_bytes)?;
+
+        let flat_value = match self.db.cf_handle(trie.flat_cf()) {
+            Some(flat_cf) => self
+                .db
+                .get_cf(&flat_cf, flat_db_key(&identifier, &key_bytes))?
+                .map(|value| format!("0x{}", hex::encode(value))),
+            None => None,
+        };
+
+        Ok(TriePath {
+            trie,
+            identifier: hex::encode(&identifier),
+            key: format!("0x{}", hex::encode(key_bytes)),
+            nodes,
+            end,
+            flat_value,
+        })
+    }
+
+    /// Follow `key` down the trie stored under `identifier`, collecting the nodes on the way
+    pub(crate) fn walk_trie(
+        &self,
+        trie: Trie,
+        identifier: &[u8],
+        key: &[u8; 32],
+    ) -> Result<(Vec<TriePathNode>, TriePathEnd), DbError> {
+        let bits = key_bits(key);
+
+        let trie_cf = self.db.cf_handle(trie.trie_cf()).ok_or_else(|| {
+            DbError::InvalidInput(format!("column family {} not found", trie.trie_cf()))
+        })?;
+
+        let mut nodes = Vec::new();
+        let mut path: Vec<bool> = Vec::new();
+        let end = loop {
+            let node_key = node_db_key(identifier, &path);
+            let Some(value) = self.db.get_cf(&trie_cf, &node_key)? else {
+                break if path.is_empty() {
+                    TriePathEnd::EmptyTrie
+                } else {
+                    TriePathEnd::MissingNode { path: path.clone() }
+                };
+            };
+            let node = self
+                .decoder
+                .decode_trie_node(trie.trie_cf(), &node_key, &value)?;
+
+            let depth = path.len();
+            match &node {
+                TrieNode::Binary(binary) => {
+                    let right = bits[depth];
+                    let child = if right { &binary.right } else { &binary.left }.clone();
+                    nodes.push(TriePathNode {
+                        path: path.clone(),
+                        node,
+                        took_right: Some(right),
+                    });
+                    path.push(right);
+                    if path.len() == TRIE_HEIGHT {
+                        break TriePathEnd::Leaf(child);
+                    }
+                }
+                TrieNode::Edge(edge) => {
+                    let common = edge
+                        .path
+                        .iter()
+                        .zip(&bits[depth..])
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    let child = edge.child.clone();
+                    let follows_key = common == edge.path.len();
+                    path.extend_from_slice(&edge.path);
+                    nodes.push(TriePathNode {
+                        path: path[..depth].to_vec(),
+                        node,
+                        took_right: None,
+                    });
+                    if !follows_key {
+                        break TriePathEnd::Diverged {
+                            depth: depth + common,
+                        };
+                    }
+                    if path.len() >= TRIE_HEIGHT {
+                        break TriePathEnd::Leaf(child);
+                    }
+                }
+            }
+        };
+
+        Ok((nodes, end))
+    }
+}
+
+/// Identifier a trie's keys are prefixed with; storage tries need the contract address
+pub(crate) fn trie_identifier(trie: Trie, contract: Option<&str>) -> Result<Vec<u8>, DbError> {
+    match trie {
+        Trie::Contract => Ok(CONTRACT_IDENTIFIER.to_vec()),
+        Trie::Class => Ok(CLASS_IDENTIFIER.to_vec()),
+        Trie::ContractStorage => {
+            let contract = contract.ok_or_else(|| {
+                DbError::InvalidInput("storage tries need a contract address".to_string())
+            })?;
+            parse_felt_hex(contract)
+                .map(|address| address.to_vec())
+                .ok_or_else(|| {
+                    DbError::InvalidInput(format!("invalid contract address: {contract}"))
+                })
+        }
+    }
+}
+
+/// Parse a hex key and check it fits in the trie height
+pub(crate) fn parse_trie_key(key: &str) -> Result<[u8; 32], DbError> {
+    let key_bytes =
+        parse_felt_hex(key).ok_or_else(|| DbError::InvalidInput(format!("invalid key: {key}")))?;
+    if key_bytes[0] & 0xf8 != 0 {
+        return Err(DbError::InvalidInput(format!(
+            "key does not fit in {TRIE_HEIGHT} bits: {key}"
+        )));
+    }
+    Ok(key_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felt(byte: u8) -> Vec<u8> {
+        let mut f = vec![0u8; 32];
+        f[31] = byte;
+        f
+    }
+
+    #[test]
+    fn node_key_packs_path_msb_first() {
+        assert_eq!(node_db_key(b"id", &[]), b"id\x00".to_vec());
+        assert_eq!(
+            node_db_key(
+                b"",
+                &[true, false, true, true, false, false, false, false, true]
+            ),
+            vec![9, 0b1011_0000, 0b1000_0000]
+        );
+    }
+
+    #[test]
+    fn decodes_binary_and_edge_nodes() {
+        let mut binary = vec![0, 1];
+        binary.extend(felt(7));
+        binary.extend(3u64.to_le_bytes());
+        binary.push(0);
+        binary.extend(felt(1));
+        binary.push(0);
+        binary.extend(felt(2));
+        let node = decode_node("bonsai_contract_trie", b"k", &binary).unwrap();
+        let TrieNode::Binary(node) = node else {
+            panic!("expected binary node")
+        };
+        assert_eq!(node.height, 3);
+        assert_eq!(
+            node.hash.as_deref(),
+            Some(format!("0x{}", hex::encode(felt(7))).as_str())
+        );
+        assert_eq!(
+            node.right,
+            NodeHandle::Hash(format!("0x{}", hex::encode(felt(2))))
+        );
+
+        let mut edge = vec![1, 0];
+        edge.extend(0u64.to_le_bytes());
+        edge.extend([3, 0b1010_0000]);
+        edge.push(0);
+        edge.extend(felt(9));
+        let TrieNode::Edge(node) = decode_node("bonsai_contract_trie", b"k", &edge).unwrap() else {
+            panic!("expected edge node")
+        };
+        assert_eq!(node.path, vec![true, false, true]);
+        assert_eq!(node.hash, None);
+    }
+
+    #[test]
+    fn walks_single_edge_trie_to_leaf_and_detects_divergence() {
+        use crate::test_utils::open_db;
+
+        let mut key = [0u8; 32];
+        key[31] = 5;
+        let bits = key_bits(&key);
+
+        // Root is one edge covering the whole key
+        let mut root = vec![1, 1];
+        root.extend(felt(0xaa));
+        root.extend(0u64.to_le_bytes());
+        root.extend(&node_db_key(&[], &bits));
+        root.push(0);
+        root.extend(felt(0x42));
+
+        let (_dir, reader) = open_db(&[
+            (
+                "bonsai_class_trie",
+                node_db_key(CLASS_IDENTIFIER, &[]),
+                root,
+            ),
+            (
+                "bonsai_class_flat",
+                flat_db_key(CLASS_IDENTIFIER, &key),
+                felt(0x42),
+            ),
+        ]);
+
+        let path = reader.get_trie_path(Trie::Class, None, "0x5").unwrap();
+        assert_eq!(path.nodes.len(), 1);
+        assert_eq!(
+            path.root_hash(),
+            Some(format!("0x{}", hex::encode(felt(0xaa))).as_str())
+        );
+        assert_eq!(
+            path.end,
+            TriePathEnd::Leaf(NodeHandle::Hash(format!("0x{}", hex::encode(felt(0x42)))))
+        );
+        assert_eq!(
+            path.flat_value,
+            Some(format!("0x{}", hex::encode(felt(0x42))))
+        );
+
+        let path = reader.get_trie_path(Trie::Class, None, "0x7").unwrap();
+        assert_eq!(path.end, TriePathEnd::Diverged { depth: 249 });
+    }
+
+    #[test]
+    fn decodes_edge_node_written_by_madara() {
+        // `bonsai_class_trie` value under `0xclass ++ [1, 0x80]` in a Madara v9 DB
+        let value = hex::decode(
+            "010101961c16b1f4ffdd3ad5cdadd1516cb6ba0e8bc3d4fe66032124bab5dcbb0fcb0100000000000000\
+             0500\
+             00010f59348717429b26c2b6c9e623d00ea227ab39738dc949ffad15aefaecd675",
+        )
+        .unwrap();
+        let TrieNode::Edge(node) = decode_node("bonsai_class_trie", b"k", &value).unwrap() else {
+            panic!("expected edge node")
+        };
+        assert_eq!(node.height, 1);
+        assert_eq!(node.path, vec![false; 5]);
+        assert_eq!(
+            node.child,
+            NodeHandle::Hash(
+                "0x010f59348717429b26c2b6c9e623d00ea227ab39738dc949ffad15aefaecd675".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn truncated_node_reports_offset() {
+        let err = decode_node("bonsai_class_trie", b"k", &[0, 1, 0xaa]).unwrap_err();
+        assert_eq!(err.expected_type, "Node");
+        assert_eq!(err.offset, 2);
+    }
+}
@@ -0,0 +1,546 @@
+//! Block hash and commitment verification.
+//!
+//! Recomputes the commitments a block header carries from the block's transactions, receipts
+//! and state diff, and the block hash from the header, with the hashing rules of the block's
+//! `protocol_version`:
+//! - before 0.13.2: Pedersen commitment trees and block hash; there is no receipt or state
+//!   diff commitment. Signatures are part of every transaction leaf from 0.11.1, of Invoke
+//!   leaves only before that
+//! - 0.13.2: Poseidon trees, receipt and state diff commitments, and a block hash tagged
+//!   `STARKNET_BLOCK_HASH0` over the L1 gas prices
+//! - 0.13.4 and later: `STARKNET_BLOCK_HASH1`, with all six gas prices folded into one hash
+//!
+//! Commitment trees are height-64 Merkle-Patricia tries keyed by the transaction index, or the
+//! event index across the whole block. Blocks before 0.7 hashed in the chain id; their block
+//! hash is not checked.
+
+use sha3::{Digest, Keccak256};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use crate::audit::subtree_hash;
+use crate::blocks::{RawL1DataAvailabilityMode, RawMadaraBlockInfo};
+use crate::proofs::felt_hex;
+use crate::state_diff::RawStateDiff;
+use crate::transactions::{RawExecutionResult, RawTransaction, RawTransactionWithReceipt};
+use crate::{DbError, DbReader};
+
+/// Hashing rules a block was produced under, picked from its protocol version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockHashRules {
+    /// Before 0.7: the block hash covers the chain id and is not checked
+    PreV0_7,
+    /// 0.7 up to 0.13.1
+    Pedersen,
+    /// 0.13.2 and 0.13.3
+    PoseidonV0,
+    /// 0.13.4 and later
+    PoseidonV1,
+}
+
+impl BlockHashRules {
+    fn for_version(version: [u8; 4]) -> Self {
+        if version < [0, 7, 0, 0] {
+            BlockHashRules::PreV0_7
+        } else if version < [0, 13, 2, 0] {
+            BlockHashRules::Pedersen
+        } else if version < [0, 13, 4, 0] {
+            BlockHashRules::PoseidonV0
+        } else {
+            BlockHashRules::PoseidonV1
+        }
+    }
+
+    fn uses_poseidon(self) -> bool {
+        matches!(
+            self,
+            BlockHashRules::PoseidonV0 | BlockHashRules::PoseidonV1
+        )
+    }
+}
+
+impl std::fmt::Display for BlockHashRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockHashRules::PreV0_7 => write!(f, "pre-0.7"),
+            BlockHashRules::Pedersen => write!(f, "0.7 (Pedersen)"),
+            BlockHashRules::PoseidonV0 => write!(f, "0.13.2 (Poseidon)"),
+            BlockHashRules::PoseidonV1 => write!(f, "0.13.4 (Poseidon, gas prices hash)"),
+        }
+    }
+}
+
+/// A recomputed value next to the one stored in `block_info`
+#[derive(Debug, Clone)]
+pub struct HashCheck {
+    pub expected: String,
+    pub computed: String,
+}
+
+impl HashCheck {
+    fn new(expected: &[u8], computed: Felt) -> Self {
+        Self {
+            expected: felt_hex(&Felt::from_bytes_be_slice(expected)),
+            computed: felt_hex(&computed),
+        }
+    }
+
+    pub fn matches(&self) -> bool {
+        self.expected == self.computed
+    }
+}
+
+/// Result of recomputing a block's commitments and hash
+#[derive(Debug, Clone)]
+pub struct BlockVerification {
+    pub block_number: u64,
+    pub protocol_version: String,
+    pub rules: BlockHashRules,
+    pub transaction_commitment: HashCheck,
+    pub event_commitment: HashCheck,
+    /// `None` when the header has none (before 0.13.2)
+    pub receipt_commitment: Option<HashCheck>,
+    /// `None` when the header has none (before 0.13.2)
+    pub state_diff_commitment: Option<HashCheck>,
+    /// Hash of the header as stored, so a wrong commitment does not show up here as well.
+    /// `None` before 0.7.
+    pub block_hash: Option<HashCheck>,
+}
+
+impl BlockVerification {
+    /// The checks that were made, by name
+    pub fn checks(&self) -> Vec<(&'static str, &HashCheck)> {
+        let mut checks = vec![
+            ("transaction_commitment", &self.transaction_commitment),
+            ("event_commitment", &self.event_commitment),
+        ];
+        if let Some(check) = &self.receipt_commitment {
+            checks.push(("receipt_commitment", check));
+        }
+        if let Some(check) = &self.state_diff_commitment {
+            checks.push(("state_diff_commitment", check));
+        }
+        if let Some(check) = &self.block_hash {
+            checks.push(("block_hash", check));
+        }
+        checks
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.checks().iter().all(|(_, check)| check.matches())
+    }
+}
+
+fn felt(bytes: &[u8]) -> Felt {
+    Felt::from_bytes_be_slice(bytes)
+}
+
+fn felts<'a>(items: impl IntoIterator<Item = &'a serde_bytes::ByteBuf>) -> Vec<Felt> {
+    items.into_iter().map(|b| felt(b)).collect()
+}
+
+fn len_felt(len: usize) -> Felt {
+    Felt::from(len as u64)
+}
+
+/// `sn_keccak`: Keccak-256 truncated to 250 bits
+fn starknet_keccak(data: &[u8]) -> Felt {
+    let mut hash: [u8; 32] = Keccak256::digest(data).into();
+    hash[0] &= 0x03;
+    Felt::from_bytes_be(&hash)
+}
+
+/// Root of a height-64 commitment tree holding `leaves` at indices 0, 1, 2, ...
+fn commitment_root(leaves: Vec<Felt>, poseidon: bool) -> Felt {
+    if leaves.is_empty() {
+        return Felt::ZERO;
+    }
+    let leaves: Vec<(Vec<bool>, Felt)> = leaves
+        .into_iter()
+        .enumerate()
+        .map(|(i, leaf)| {
+            let bits = (0..64)
+                .rev()
+                .map(|bit| (i as u64 >> bit) & 1 == 1)
+                .collect();
+            (bits, leaf)
+        })
+        .collect();
+    if poseidon {
+        subtree_hash(&|a, b| Poseidon::hash(a, b), &leaves, 0)
+    } else {
+        subtree_hash(&|a, b| Pedersen::hash(a, b), &leaves, 0)
+    }
+}
+
+fn transaction_leaf(
+    tx: &RawTransactionWithReceipt,
+    rules: BlockHashRules,
+    version: [u8; 4],
+) -> Felt {
+    let tx_hash = felt(tx.raw_tx_hash());
+    let signature = felts(tx.transaction.raw_signature());
+    if rules.uses_poseidon() {
+        let signature = if signature.is_empty() {
+            vec![Felt::ZERO]
+        } else {
+            signature
+        };
+        let elements: Vec<Felt> = std::iter::once(tx_hash).chain(signature).collect();
+        return Poseidon::hash_array(&elements);
+    }
+    let signed = version >= [0, 11, 1, 0] || matches!(tx.transaction, RawTransaction::Invoke(_));
+    let signature_hash = if signed {
+        Pedersen::hash_array(&signature)
+    } else {
+        Pedersen::hash_array(&[])
+    };
+    Pedersen::hash(&tx_hash, &signature_hash)
+}
+
+fn event_leaves(tx: &RawTransactionWithReceipt, rules: BlockHashRules) -> Vec<Felt> {
+    let tx_hash = felt(tx.raw_tx_hash());
+    tx.raw_events()
+        .iter()
+        .map(|event| {
+            let keys = felts(&event.keys);
+            let data = felts(&event.data);
+            if rules.uses_poseidon() {
+                let mut elements = vec![felt(&event.from_address), tx_hash, len_felt(keys.len())];
+                elements.extend(keys);
+                elements.push(len_felt(data.len()));
+                elements.extend(data);
+                Poseidon::hash_array(&elements)
+            } else {
+                Pedersen::hash_array(&[
+                    felt(&event.from_address),
+                    Pedersen::hash_array(&keys),
+                    Pedersen::hash_array(&data),
+                ])
+            }
+        })
+        .collect()
+}
+
+/// `poseidon(tx_hash, fee, messages_hash, sn_keccak(revert_reason), 0, l1_gas, l1_data_gas)`;
+/// L2 gas is committed as zero
+fn receipt_leaf(tx: &RawTransactionWithReceipt) -> Felt {
+    let mut messages = vec![len_felt(tx.raw_messages().len())];
+    for message in tx.raw_messages() {
+        messages.push(felt(&message.from_address));
+        messages.push(felt(&message.to_address));
+        messages.push(len_felt(message.payload.len()));
+        messages.extend(felts(&message.payload));
+    }
+    let revert_reason = match tx.raw_execution_result() {
+        RawExecutionResult::Succeeded => Felt::ZERO,
+        RawExecutionResult::Reverted { reason } => starknet_keccak(reason.as_bytes()),
+    };
+    let gas = &tx.raw_execution_resources().total_gas_consumed;
+
+    Poseidon::hash_array(&[
+        felt(tx.raw_tx_hash()),
+        felt(&tx.raw_actual_fee().amount),
+        Poseidon::hash_array(&messages),
+        revert_reason,
+        Felt::ZERO,
+        Felt::from(gas.l1_gas),
+        Felt::from(gas.l1_data_gas),
+    ])
+}
+
+/// `poseidon("STARKNET_STATE_DIFF0", ...)` over the sorted state diff
+fn state_diff_commitment(diff: &RawStateDiff) -> Felt {
+    let pairs = |items: Vec<(Felt, Felt)>| -> Vec<Felt> {
+        let mut items = items;
+        items.sort_by_key(|(key, _)| *key);
+        std::iter::once(len_felt(items.len()))
+            .chain(items.into_iter().flat_map(|(key, value)| [key, value]))
+            .collect()
+    };
+
+    let updated_contracts = diff
+        .deployed_contracts
+        .iter()
+        .map(|c| (felt(&c.address), felt(&c.class_hash)))
+        .chain(
+            diff.replaced_classes
+                .iter()
+                .map(|c| (felt(&c.contract_address), felt(&c.class_hash))),
+        )
+        .collect();
+    // Compiled class hash migrations update the same class hash -> compiled class hash map
+    let declared_classes = diff
+        .declared_classes
+        .iter()
+        .map(|c| (felt(&c.class_hash), felt(&c.compiled_class_hash)))
+        .chain(
+            diff.migrated_compiled_classes
+                .iter()
+                .map(|c| (felt(&c.class_hash), felt(&c.compiled_class_hash))),
+        )
+        .collect();
+    let mut deprecated_declared_classes = felts(&diff.old_declared_contracts);
+    deprecated_declared_classes.sort();
+
+    let mut storage_diffs: Vec<(Felt, Vec<(Felt, Felt)>)> = diff
+        .storage_diffs
+        .iter()
+        .map(|d| {
+            let mut entries: Vec<(Felt, Felt)> = d
+                .storage_entries
+                .iter()
+                .map(|e| (felt(&e.key), felt(&e.value)))
+                .collect();
+            entries.sort_by_key(|(key, _)| *key);
+            (felt(&d.address), entries)
+        })
+        .collect();
+    storage_diffs.sort_by_key(|(address, _)| *address);
+
+    let mut elements = vec![Felt::from_bytes_be_slice(b"STARKNET_STATE_DIFF0")];
+    elements.extend(pairs(updated_contracts));
+    elements.extend(pairs(declared_classes));
+    elements.push(len_felt(deprecated_declared_classes.len()));
+    elements.extend(deprecated_declared_classes);
+    // Data availability placeholders
+    elements.extend([Felt::ONE, Felt::ZERO]);
+    elements.push(len_felt(storage_diffs.len()));
+    for (address, entries) in storage_diffs {
+        elements.push(address);
+        elements.push(len_felt(entries.len()));
+        elements.extend(entries.into_iter().flat_map(|(key, value)| [key, value]));
+    }
+    elements.extend(pairs(
+        diff.nonces
+            .iter()
+            .map(|n| (felt(&n.contract_address), felt(&n.nonce)))
+            .collect(),
+    ));
+    Poseidon::hash_array(&elements)
+}
+
+/// Transaction, event and state diff counts and the L1 DA mode packed into one felt
+fn concat_counts(
+    transaction_count: u64,
+    event_count: u64,
+    state_diff_length: u64,
+    l1_da_mode: &RawL1DataAvailabilityMode,
+) -> Felt {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&transaction_count.to_be_bytes());
+    bytes[8..16].copy_from_slice(&event_count.to_be_bytes());
+    bytes[16..24].copy_from_slice(&state_diff_length.to_be_bytes());
+    if matches!(l1_da_mode, RawL1DataAvailabilityMode::Blob) {
+        bytes[24] = 0x80;
+    }
+    Felt::from_bytes_be(&bytes)
+}
+
+fn block_hash(block: &RawMadaraBlockInfo, rules: BlockHashRules) -> Option<Felt> {
+    let header = &block.header;
+    let common = [
+        Felt::from(header.block_number),
+        felt(&header.global_state_root),
+        felt(&header.sequencer_address),
+        Felt::from(header.block_timestamp),
+    ];
+    let optional =
+        |value: &Option<serde_bytes::ByteBuf>| value.as_ref().map_or(Felt::ZERO, |b| felt(b));
+    let gas = &header.gas_prices;
+    let protocol_version =
+        Felt::from_bytes_be_slice(header.protocol_version.to_string().as_bytes());
+
+    let elements: Vec<Felt> = match rules {
+        BlockHashRules::PreV0_7 => return None,
+        BlockHashRules::Pedersen => {
+            return Some(Pedersen::hash_array(
+                &common
+                    .into_iter()
+                    .chain([
+                        Felt::from(header.transaction_count),
+                        felt(&header.transaction_commitment),
+                        Felt::from(header.event_count),
+                        felt(&header.event_commitment),
+                        Felt::ZERO,
+                        Felt::ZERO,
+                        felt(&header.parent_block_hash),
+                    ])
+                    .collect::<Vec<_>>(),
+            ))
+        }
+        BlockHashRules::PoseidonV0 | BlockHashRules::PoseidonV1 => {
+            let (tag, gas_prices): (&[u8], Vec<Felt>) = if rules == BlockHashRules::PoseidonV0 {
+                (
+                    b"STARKNET_BLOCK_HASH0",
+                    vec![
+                        Felt::from(gas.eth_l1_gas_price),
+                        Felt::from(gas.strk_l1_gas_price),
+                        Felt::from(gas.eth_l1_data_gas_price),
+                        Felt::from(gas.strk_l1_data_gas_price),
+                    ],
+                )
+            } else {
+                (
+                    b"STARKNET_BLOCK_HASH1",
+                    vec![Poseidon::hash_array(&[
+                        Felt::from_bytes_be_slice(b"STARKNET_GAS_PRICES0"),
+                        Felt::from(gas.eth_l1_gas_price),
+                        Felt::from(gas.strk_l1_gas_price),
+                        Felt::from(gas.eth_l1_data_gas_price),
+                        Felt::from(gas.strk_l1_data_gas_price),
+                        Felt::from(gas.eth_l2_gas_price),
+                        Felt::from(gas.strk_l2_gas_price),
+                    ])],
+                )
+            };
+            std::iter::once(Felt::from_bytes_be_slice(tag))
+                .chain(common)
+                .chain([
+                    concat_counts(
+                        header.transaction_count,
+                        header.event_count,
+                        header.state_diff_length.unwrap_or(0),
+                        &header.l1_da_mode,
+                    ),
+                    optional(&header.state_diff_commitment),
+                    felt(&header.transaction_commitment),
+                    felt(&header.event_commitment),
+                    optional(&header.receipt_commitment),
+                ])
+                .chain(gas_prices)
+                .chain([
+                    protocol_version,
+                    Felt::ZERO,
+                    felt(&header.parent_block_hash),
+                ])
+                .collect()
+        }
+    };
+    Some(Poseidon::hash_array(&elements))
+}
+
+/// Check a block against its transactions, in index order, and its state diff, which is only
+/// read when the header commits to it
+pub(crate) fn verify_block(
+    block: &RawMadaraBlockInfo,
+    transactions: &[RawTransactionWithReceipt],
+    state_diff: Option<&RawStateDiff>,
+) -> BlockVerification {
+    let header = &block.header;
+    let version = header.protocol_version.0;
+    let rules = BlockHashRules::for_version(version);
+
+    let transaction_leaves = transactions
+        .iter()
+        .map(|tx| transaction_leaf(tx, rules, version))
+        .collect();
+    let event_leaves = transactions
+        .iter()
+        .flat_map(|tx| event_leaves(tx, rules))
+        .collect();
+
+    BlockVerification {
+        block_number: header.block_number,
+        protocol_version: header.protocol_version.to_string(),
+        rules,
+        transaction_commitment: HashCheck::new(
+            &header.transaction_commitment,
+            commitment_root(transaction_leaves, rules.uses_poseidon()),
+        ),
+        event_commitment: HashCheck::new(
+            &header.event_commitment,
+            commitment_root(event_leaves, rules.uses_poseidon()),
+        ),
+        receipt_commitment: header.receipt_commitment.as_ref().map(|expected| {
+            HashCheck::new(
+                expected,
+                commitment_root(transactions.iter().map(receipt_leaf).collect(), true),
+            )
+        }),
+        state_diff_commitment: header
+            .state_diff_commitment
+            .as_ref()
+            .zip(state_diff)
+            .map(|(expected, diff)| HashCheck::new(expected, state_diff_commitment(diff))),
+        block_hash: block_hash(block, rules).map(|hash| HashCheck::new(&block.block_hash, hash)),
+    }
+}
+
+impl DbReader {
+    /// Recompute a block's commitments and hash. `Ok(None)` when the block does not exist;
+    /// a transaction or state diff that fails to decode is an error.
+    pub fn verify_block(&self, block_n: u64) -> Result<Option<BlockVerification>, DbError> {
+        let Some(block) = self.get_raw_block(block_n)? else {
+            return Ok(None);
+        };
+        let transactions = self
+            .get_raw_block_transactions(block_n)
+            .into_iter()
+            .map(|(_, tx)| tx)
+            .collect::<Result<Vec<_>, _>>()?;
+        let state_diff = match block.header.state_diff_commitment {
+            Some(_) => Some(self.get_raw_state_diff(block_n)?),
+            None => None,
+        };
+        Ok(Some(verify_block(
+            &block,
+            &transactions,
+            state_diff.as_ref(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::decoder_for_version;
+    use crate::fixtures::{BLOCK_INFO, DECLARE_V3, STATE_DIFF};
+
+    fn devnet_block() -> (
+        RawMadaraBlockInfo,
+        Vec<RawTransactionWithReceipt>,
+        RawStateDiff,
+    ) {
+        let decoder = decoder_for_version(Some(9));
+        let key = 4u32.to_be_bytes();
+        let bytes = |value: &str| hex::decode(value).unwrap();
+        (
+            decoder.decode_block_info(&key, &bytes(BLOCK_INFO)).unwrap(),
+            vec![decoder
+                .decode_transaction(&[0, 0, 0, 4, 0, 0], &bytes(DECLARE_V3))
+                .unwrap()],
+            decoder.decode_state_diff(&key, &bytes(STATE_DIFF)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn recomputes_a_devnet_block() {
+        let (block, transactions, diff) = devnet_block();
+        let verification = verify_block(&block, &transactions, Some(&diff));
+        assert_eq!(verification.rules, BlockHashRules::PoseidonV1);
+        assert_eq!(verification.checks().len(), 5);
+        assert!(verification.is_valid(), "{verification:?}");
+    }
+
+    #[test]
+    fn missing_transaction_only_breaks_body_commitments() {
+        let (block, _, diff) = devnet_block();
+        let verification = verify_block(&block, &[], Some(&diff));
+        assert!(!verification.transaction_commitment.matches());
+        assert!(!verification.event_commitment.matches());
+        assert!(!verification.receipt_commitment.unwrap().matches());
+        assert!(verification.state_diff_commitment.unwrap().matches());
+        // The block hash covers the header as stored
+        assert!(verification.block_hash.unwrap().matches());
+    }
+
+    #[test]
+    fn packs_counts_and_da_mode() {
+        let packed = concat_counts(1, 2, 3, &RawL1DataAvailabilityMode::Blob).to_bytes_be();
+        assert_eq!(packed[7], 1);
+        assert_eq!(packed[15], 2);
+        assert_eq!(packed[23], 3);
+        assert_eq!(packed[24..], [0x80, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
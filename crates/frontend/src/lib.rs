@@ -1,13 +1,20 @@
 use leptos::prelude::*;
 use std::sync::OnceLock;
 use visualizer_types::{
-    BlockDetail, BlockListResponse, BlockSummary, ClassListResponse, ClassResponse,
-    ColumnFamilyInfo, ColumnFamilyListResponse, ColumnFamilySchemaInfo, ContractListResponse,
-    ContractResponse, ContractStorageResponse, FilteredTransactionsResponse, IndexStatusResponse,
-    IndexedTransactionInfo, KeyListResponse, QueryRequest, QueryResult, RawKeyValueResponse,
-    SchemaCategoriesResponse, SchemaCategoryInfo, SchemaColumnFamiliesResponse, SearchResponse,
-    StateDiffResponse, StatsResponse, TableInfo, TableListResponse, TransactionDetail,
-    TransactionListResponse, TransactionSummary,
+    BlockDetail, BlockListResponse, BlockSummary, BlockVerificationResponse, BouncerWeightsInfo,
+    BouncerWeightsResponse, CasmEntryPointInfo, ClassDefinitionInfo, ClassListResponse,
+    ClassResponse, ColumnFamilyInfo, ColumnFamilyListResponse, ColumnFamilySchemaInfo,
+    CompiledClassResponse, ContractClassHistoryResponse, ContractListResponse, ContractResponse,
+    ContractStorageResponse, DecodeErrorResponse, DecodedArgInfo, DecodedCallInfo, EntryPointInfo,
+    ExecutionResources, FilteredTransactionsResponse, IndexStatusResponse, IndexedTransactionInfo,
+    KeyListResponse, L1ToL2MessageInfo, L1ToL2MessageListResponse, L2ToL1MessageInfo,
+    L2ToL1MessageListResponse, MempoolAccountInfo, MempoolResponse, MempoolTransactionInfo,
+    NodeMetaResponse, PendingBlockResponse, PendingTransactionInfo, QueryRequest, QueryResult,
+    RawKeyValueResponse, SchemaCategoriesResponse, SchemaCategoryInfo,
+    SchemaColumnFamiliesResponse, SearchResponse, StateDiffResponse, StatsResponse,
+    StorageHistoryResponse, TableInfo, TableListResponse, TransactionBody, TransactionDetail,
+    TransactionListResponse, TransactionSummary, TrieChangesResponse, TrieNodeInfo,
+    TriePathResponse, V3TransactionFields,
 };
 use wasm_bindgen::prelude::*;
 
@@ -107,14 +114,53 @@ async fn fetch_blocks(offset: u64, limit: u64) -> Result<BlockListResponse, Stri
     .map_err(|e| e.to_string())
 }
 
+/// Parses a detail response, turning a 422 decode error into a readable message
+async fn parse_detail_response<T: serde::de::DeserializeOwned>(
+    resp: gloo_net::http::Response,
+) -> Result<T, String> {
+    if resp.status() == 422 {
+        let err: DecodeErrorResponse = resp.json().await.map_err(|e| e.to_string())?;
+        return Err(format!(
+            "This value exists in {} but failed to decode as {} at byte offset {} (key 0x{}): {}",
+            err.cf, err.expected_type, err.offset, err.key, err.message
+        ));
+    }
+    if !resp.ok() {
+        return Err(resp.text().await.map_err(|e| e.to_string())?);
+    }
+    resp.json().await.map_err(|e| e.to_string())
+}
+
 async fn fetch_block(block_number: u64) -> Result<BlockDetail, String> {
-    gloo_net::http::Request::get(&format!("{}/api/blocks/{block_number}", get_api_base()))
+    let resp =
+        gloo_net::http::Request::get(&format!("{}/api/blocks/{block_number}", get_api_base()))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
+/// `None` when the chain tip is a closed block
+async fn fetch_pending_block() -> Result<Option<PendingBlockResponse>, String> {
+    let resp = gloo_net::http::Request::get(&format!("{}/api/blocks/pending", get_api_base()))
         .send()
         .await
-        .map_err(|e| e.to_string())?
-        .json()
-        .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    if resp.status() == 404 {
+        return Ok(None);
+    }
+    parse_detail_response(resp).await.map(Some)
+}
+
+async fn fetch_block_verification(block_number: u64) -> Result<BlockVerificationResponse, String> {
+    let resp = gloo_net::http::Request::get(&format!(
+        "{}/api/blocks/{block_number}/verify",
+        get_api_base()
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
 }
 
 async fn fetch_block_transactions(block_number: u64) -> Result<TransactionListResponse, String> {
@@ -134,16 +180,14 @@ async fn fetch_transaction(
     block_number: u64,
     tx_index: usize,
 ) -> Result<TransactionDetail, String> {
-    gloo_net::http::Request::get(&format!(
+    let resp = gloo_net::http::Request::get(&format!(
         "{}/api/blocks/{block_number}/transactions/{tx_index}",
         get_api_base()
     ))
     .send()
     .await
-    .map_err(|e| e.to_string())?
-    .json()
-    .await
-    .map_err(|e| e.to_string())
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
 }
 
 async fn fetch_contracts(limit: usize) -> Result<ContractListResponse, String> {
@@ -182,6 +226,38 @@ async fn fetch_contract_storage(
     .map_err(|e| e.to_string())
 }
 
+async fn fetch_contract_class_history(
+    address: String,
+) -> Result<ContractClassHistoryResponse, String> {
+    gloo_net::http::Request::get(&format!(
+        "{}/api/contracts/{address}/class-history",
+        get_api_base()
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?
+    .json()
+    .await
+    .map_err(|e| e.to_string())
+}
+
+async fn fetch_storage_history(
+    address: String,
+    key: String,
+    limit: usize,
+) -> Result<StorageHistoryResponse, String> {
+    gloo_net::http::Request::get(&format!(
+        "{}/api/contracts/{address}/storage/{key}/history?limit={limit}",
+        get_api_base()
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?
+    .json()
+    .await
+    .map_err(|e| e.to_string())
+}
+
 async fn fetch_classes(limit: usize) -> Result<ClassListResponse, String> {
     gloo_net::http::Request::get(&format!("{}/api/classes?limit={limit}", get_api_base()))
         .send()
@@ -193,26 +269,45 @@ async fn fetch_classes(limit: usize) -> Result<ClassListResponse, String> {
 }
 
 async fn fetch_class(class_hash: String) -> Result<ClassResponse, String> {
-    gloo_net::http::Request::get(&format!("{}/api/classes/{class_hash}", get_api_base()))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?
-        .json()
-        .await
-        .map_err(|e| e.to_string())
+    let resp =
+        gloo_net::http::Request::get(&format!("{}/api/classes/{class_hash}", get_api_base()))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
+async fn fetch_class_compiled(class_hash: String) -> Result<CompiledClassResponse, String> {
+    let resp = gloo_net::http::Request::get(&format!(
+        "{}/api/classes/{class_hash}/compiled",
+        get_api_base()
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
 }
 
 async fn fetch_state_diff(block_number: u64) -> Result<StateDiffResponse, String> {
-    gloo_net::http::Request::get(&format!(
+    let resp = gloo_net::http::Request::get(&format!(
         "{}/api/blocks/{block_number}/state-diff",
         get_api_base()
     ))
     .send()
     .await
-    .map_err(|e| e.to_string())?
-    .json()
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
+async fn fetch_trie_changes(block_number: u64) -> Result<TrieChangesResponse, String> {
+    let resp = gloo_net::http::Request::get(&format!(
+        "{}/api/blocks/{block_number}/trie-changes",
+        get_api_base()
+    ))
+    .send()
     .await
-    .map_err(|e| e.to_string())
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
 }
 
 async fn fetch_search(query: String) -> Result<SearchResponse, String> {
@@ -229,6 +324,26 @@ async fn fetch_search(query: String) -> Result<SearchResponse, String> {
     .map_err(|e| e.to_string())
 }
 
+async fn fetch_trie_path(
+    trie: String,
+    key: String,
+    contract: String,
+) -> Result<TriePathResponse, String> {
+    let mut url = format!(
+        "{}/api/tries/{trie}/path/{}",
+        get_api_base(),
+        urlencoding::encode(&key)
+    );
+    if !contract.is_empty() {
+        url.push_str(&format!("?contract={}", urlencoding::encode(&contract)));
+    }
+    let resp = gloo_net::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
 async fn fetch_index_status() -> Result<IndexStatusResponse, String> {
     gloo_net::http::Request::get(&format!("{}/api/index/status", get_api_base()))
         .send()
@@ -264,6 +379,80 @@ async fn fetch_filtered_transactions(
         .map_err(|e| e.to_string())
 }
 
+async fn fetch_mempool(sort: String, limit: usize) -> Result<MempoolResponse, String> {
+    let resp = gloo_net::http::Request::get(&format!(
+        "{}/api/mempool?sort={sort}&limit={limit}",
+        get_api_base()
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
+async fn fetch_node_meta() -> Result<NodeMetaResponse, String> {
+    let resp = gloo_net::http::Request::get(&format!("{}/api/meta", get_api_base()))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
+async fn fetch_l1_to_l2_messages(
+    from_nonce: u64,
+    limit: usize,
+) -> Result<L1ToL2MessageListResponse, String> {
+    let resp = gloo_net::http::Request::get(&format!(
+        "{}/api/messages/l1-to-l2?from_nonce={from_nonce}&limit={limit}",
+        get_api_base()
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
+async fn fetch_l2_to_l1_messages(
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    limit: usize,
+) -> Result<L2ToL1MessageListResponse, String> {
+    let mut url = format!("{}/api/messages/l2-to-l1?limit={limit}", get_api_base());
+    if let Some(from) = from_block {
+        url.push_str(&format!("&from_block={from}"));
+    }
+    if let Some(to) = to_block {
+        url.push_str(&format!("&to_block={to}"));
+    }
+    let resp = gloo_net::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
+async fn fetch_bouncer_weights(
+    from: Option<u64>,
+    to: Option<u64>,
+) -> Result<BouncerWeightsResponse, String> {
+    let mut params = Vec::new();
+    if let Some(from) = from {
+        params.push(format!("from={from}"));
+    }
+    if let Some(to) = to {
+        params.push(format!("to={to}"));
+    }
+    let resp = gloo_net::http::Request::get(&format!(
+        "{}/api/bouncer-weights?{}",
+        get_api_base(),
+        params.join("&")
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+    parse_detail_response(resp).await
+}
+
 // Schema API functions
 
 async fn fetch_cf_schema(cf_name: &str) -> Result<ColumnFamilySchemaInfo, String> {
@@ -447,6 +636,7 @@ fn ExportButton(data: String, filename: String) -> impl IntoView {
 enum Page {
     BlockList,
     BlockDetail { block_number: u64 },
+    PendingBlock,
     TransactionDetail { block_number: u64, tx_index: usize },
     StateDiff { block_number: u64 },
     ContractList,
@@ -459,6 +649,11 @@ enum Page {
     Schema,
     SchemaDetail { cf_name: String },
     SqlConsole,
+    Tries,
+    Mempool,
+    Messaging,
+    BlockUtilization,
+    NodeStatus,
 }
 
 #[component]
@@ -477,7 +672,27 @@ fn BlockRow(block: BlockSummary, on_click: impl Fn(u64) + 'static) -> impl IntoV
 }
 
 #[component]
-fn BlockList(on_select: impl Fn(u64) + Clone + Send + 'static) -> impl IntoView {
+fn PendingBlockRow(block: PendingBlockResponse, on_click: impl Fn() + 'static) -> impl IntoView {
+    view! {
+        <tr
+            class="bg-amber-900/20 hover:bg-amber-900/30 cursor-pointer transition-colors"
+            on:click=move |_| on_click()
+        >
+            <td class="px-4 py-3 text-amber-300 font-mono font-semibold">
+                {"#"}{block.header.block_number}
+                <span class="ml-2 px-2 py-0.5 text-xs rounded bg-amber-700 text-amber-100">"pending"</span>
+            </td>
+            <td class="px-4 py-3 text-xs sm:text-sm text-amber-200/80">"Preconfirmed, not closed yet"</td>
+            <td class="px-4 py-3 text-center text-slate-200">{block.transactions.len()}</td>
+        </tr>
+    }
+}
+
+#[component]
+fn BlockList(
+    on_select: impl Fn(u64) + Clone + Send + 'static,
+    on_pending_select: impl Fn() + Clone + Send + 'static,
+) -> impl IntoView {
     let (offset, set_offset) = signal(0u64);
     let limit = 20u64;
 
@@ -485,6 +700,7 @@ fn BlockList(on_select: impl Fn(u64) + Clone + Send + 'static) -> impl IntoView
         let offset = offset.get();
         async move { fetch_blocks(offset, limit).await }
     });
+    let pending = LocalResource::new(fetch_pending_block);
 
     view! {
         <div class="space-y-4">
@@ -497,6 +713,7 @@ fn BlockList(on_select: impl Fn(u64) + Clone + Send + 'static) -> impl IntoView
             <Suspense fallback=move || view! { <p class="text-gray-400">"Loading blocks..."</p> }>
                 {move || {
                     let on_select = on_select.clone();
+                    let on_pending_select = on_pending_select.clone();
                     blocks.get().map(|result| {
                         match result.as_ref() {
                             Ok(data) => {
@@ -504,6 +721,11 @@ fn BlockList(on_select: impl Fn(u64) + Clone + Send + 'static) -> impl IntoView
                                 let total = data.total;
                                 let has_prev = offset.get() > 0;
                                 let has_next = offset.get() + limit < total;
+                                // Only on the first page, above the latest closed block
+                                let pending_block = pending
+                                    .get()
+                                    .and_then(|r| r.as_ref().ok().cloned().flatten())
+                                    .filter(|_| offset.get() == 0);
 
                                 view! {
                                     <div>
@@ -517,6 +739,9 @@ fn BlockList(on_select: impl Fn(u64) + Clone + Send + 'static) -> impl IntoView
                                                 </tr>
                                             </thead>
                                             <tbody class="divide-y divide-slate-800">
+                                                {pending_block.map(|block| view! {
+                                                    <PendingBlockRow block=block on_click=on_pending_select.clone() />
+                                                })}
                                                 {blocks_data.into_iter().map(|block| {
                                                     let on_select = on_select.clone();
                                                     view! { <BlockRow block=block on_click=move |n| on_select(n) /> }
@@ -564,10 +789,10 @@ fn TransactionRow(
 ) -> impl IntoView {
     let block_number = tx.block_number;
     let tx_index = tx.tx_index;
-    let status_class = if tx.status == "SUCCEEDED" {
-        "text-green-400"
-    } else {
-        "text-red-400"
+    let status_class = match tx.status.as_str() {
+        "SUCCEEDED" => "text-green-400",
+        "REVERTED" => "text-red-400",
+        _ => "text-gray-500",
     };
 
     view! {
@@ -585,6 +810,52 @@ fn TransactionRow(
     }
 }
 
+/// Whether the stored commitments and block hash match the ones recomputed by the API
+#[component]
+fn VerificationBadge(block_number: u64) -> impl IntoView {
+    let verification =
+        LocalResource::new(move || async move { fetch_block_verification(block_number).await });
+
+    view! {
+        <Suspense fallback=move || view! { <span class="text-xs text-gray-500">"verifying..."</span> }>
+            {move || {
+                verification.get().map(|result| match result.as_ref() {
+                    Ok(v) if v.valid => {
+                        let title = format!("{} checks match ({} rules)", v.checks.len(), v.hash_rules);
+                        view! {
+                            <span class="px-2 py-0.5 text-xs rounded bg-green-800 text-green-200" title=title>
+                                "verified ✓"
+                            </span>
+                        }.into_any()
+                    }
+                    Ok(v) => {
+                        let title = v
+                            .checks
+                            .iter()
+                            .filter(|c| !c.matches)
+                            .map(|c| format!("{}: expected {}, computed {}", c.name, c.expected, c.computed))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        view! {
+                            <span class="px-2 py-0.5 text-xs rounded bg-red-800 text-red-200" title=title>
+                                "✗ mismatch"
+                            </span>
+                        }.into_any()
+                    }
+                    Err(e) => {
+                        let title = e.clone();
+                        view! {
+                            <span class="px-2 py-0.5 text-xs rounded bg-gray-700 text-gray-300" title=title>
+                                "unverifiable"
+                            </span>
+                        }.into_any()
+                    }
+                })
+            }}
+        </Suspense>
+    }
+}
+
 #[component]
 fn BlockDetailView(
     block_number: u64,
@@ -625,13 +896,31 @@ fn BlockDetailView(
                                 let tx_count = b.transaction_count;
                                 let event_count = b.event_count;
                                 let gas_used = b.l2_gas_used;
+                                let protocol_version = b.protocol_version.clone();
+                                let l1_da_mode = b.l1_da_mode.clone();
+                                let state_diff_length = b.state_diff_length;
+                                let commitments = vec![
+                                    ("Transaction Commitment", Some(b.transaction_commitment.clone())),
+                                    ("Event Commitment", Some(b.event_commitment.clone())),
+                                    ("State Diff Commitment", b.state_diff_commitment.clone()),
+                                    ("Receipt Commitment", b.receipt_commitment.clone()),
+                                ];
+                                let gp = &b.gas_prices;
+                                let gas_prices = vec![
+                                    ("L1 Gas", gp.eth_l1_gas_price, gp.strk_l1_gas_price),
+                                    ("L1 Data Gas", gp.eth_l1_data_gas_price, gp.strk_l1_data_gas_price),
+                                    ("L2 Gas", gp.eth_l2_gas_price, gp.strk_l2_gas_price),
+                                ];
                                 let export_data = serde_json::to_string_pretty(&*b).unwrap_or_default();
                                 let export_filename = format!("block_{}.json", block_num);
 
                                 view! {
                                     <div>
                                         <div class="flex justify-between items-center mb-4">
-                                            <h2 class="text-2xl font-bold">"Block #"{block_num}</h2>
+                                            <div class="flex items-center gap-3">
+                                                <h2 class="text-2xl font-bold">"Block #"{block_num}</h2>
+                                                <VerificationBadge block_number=block_num />
+                                            </div>
                                             <ExportButton data=export_data filename=export_filename />
                                         </div>
                                         <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
@@ -675,7 +964,59 @@ fn BlockDetailView(
                                                 <p class="text-gray-400">"L2 Gas Used"</p>
                                                 <p>{gas_used}</p>
                                             </div>
+                                            <div>
+                                                <p class="text-gray-400">"Protocol Version"</p>
+                                                <p>{protocol_version}</p>
+                                            </div>
+                                            <div>
+                                                <p class="text-gray-400">"L1 DA Mode"</p>
+                                                <p>{l1_da_mode}</p>
+                                            </div>
+                                            <div>
+                                                <p class="text-gray-400">"State Diff Length"</p>
+                                                <p>{state_diff_length.map(|n| n.to_string()).unwrap_or_else(|| "-".to_string())}</p>
+                                            </div>
+                                        </div>
+                                        <h3 class="text-lg font-semibold mt-6 mb-2">"Commitments"</h3>
+                                        <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                                            {commitments.into_iter().map(|(label, value)| {
+                                                view! {
+                                                    <div>
+                                                        <p class="text-gray-400">{label}</p>
+                                                        {match value {
+                                                            Some(v) => view! {
+                                                                <div class="flex items-center">
+                                                                    <p class="font-mono text-sm break-all">{v.clone()}</p>
+                                                                    <CopyButton text=v />
+                                                                </div>
+                                                            }.into_any(),
+                                                            None => view! { <p class="text-gray-500">"-"</p> }.into_any(),
+                                                        }}
+                                                    </div>
+                                                }
+                                            }).collect::<Vec<_>>()}
                                         </div>
+                                        <h3 class="text-lg font-semibold mt-6 mb-2">"Gas Prices"</h3>
+                                        <table class="w-full text-left text-sm">
+                                            <thead class="text-gray-400">
+                                                <tr>
+                                                    <th class="px-4 py-2">"Resource"</th>
+                                                    <th class="px-4 py-2">"ETH (wei)"</th>
+                                                    <th class="px-4 py-2">"STRK (fri)"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {gas_prices.into_iter().map(|(resource, eth, strk)| {
+                                                    view! {
+                                                        <tr class="border-t border-gray-700">
+                                                            <td class="px-4 py-2 text-gray-300">{resource}</td>
+                                                            <td class="px-4 py-2 font-mono">{eth.to_string()}</td>
+                                                            <td class="px-4 py-2 font-mono">{strk.to_string()}</td>
+                                                        </tr>
+                                                    }
+                                                }).collect::<Vec<_>>()}
+                                            </tbody>
+                                        </table>
                                         <div class="mt-4">
                                             <button
                                                 class="px-4 py-2 bg-yellow-600 hover:bg-yellow-700 rounded text-white"
@@ -742,73 +1083,453 @@ fn BlockDetailView(
 }
 
 #[component]
-fn TransactionDetailView(
-    block_number: u64,
-    tx_index: usize,
-    on_back: impl Fn() + 'static,
-) -> impl IntoView {
-    let tx =
-        LocalResource::new(move || async move { fetch_transaction(block_number, tx_index).await });
+fn TxField(label: &'static str, value: String) -> impl IntoView {
+    view! {
+        <div>
+            <p class="text-gray-400">{label}</p>
+            <p class="font-mono text-sm break-all">{value}</p>
+        </div>
+    }
+}
 
+#[component]
+fn FeltList(title: &'static str, items: Vec<String>) -> impl IntoView {
+    if items.is_empty() {
+        return ().into_any();
+    }
     view! {
-        <div class="bg-gray-800 rounded-lg p-6">
-            <button
-                class="mb-4 text-blue-400 hover:underline"
-                on:click=move |_| on_back()
-            >
-                "< Back to block"
-            </button>
+        <div class="mb-4">
+            <h3 class="text-lg font-semibold mb-2">{title}" ("{items.len()}" items)"</h3>
+            <div class="bg-gray-900 rounded p-3 max-h-48 overflow-y-auto">
+                {items.into_iter().enumerate().map(|(i, item)| {
+                    view! {
+                        <p class="font-mono text-xs text-gray-300 break-all">
+                            <span class="text-gray-500">{i}": "</span>
+                            {item}
+                        </p>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+        </div>
+    }
+    .into_any()
+}
 
-            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading transaction..."</p> }>
-                {move || {
-                    tx.get().map(|result| {
-                        match result.as_ref() {
-                            Ok(t) => {
-                                let tx_hash = t.tx_hash.clone();
-                                let tx_hash_copy = t.tx_hash.clone();
-                                let tx_type = t.tx_type.clone();
-                                let status = t.status.clone();
-                                let revert_reason = t.revert_reason.clone();
-                                let block_num = t.block_number;
-                                let idx = t.tx_index;
-                                let actual_fee = t.actual_fee.clone();
-                                let fee_unit = t.fee_unit.clone();
-                                let sender = t.sender_address.clone();
-                                let sender_copy = t.sender_address.clone();
-                                let nonce = t.nonce.clone();
-                                let version = t.version.clone();
-                                let calldata = t.calldata.clone();
-                                let signature = t.signature.clone();
-                                let events = t.events.clone();
-                                let export_data = serde_json::to_string_pretty(&*t).unwrap_or_default();
-                                let export_filename = format!("tx_{}.json", truncate_hash(&t.tx_hash));
+#[component]
+fn V3FieldsView(v3: V3TransactionFields) -> impl IntoView {
+    let bounds = v3.resource_bounds;
+    let mut rows = vec![("L1 Gas", bounds.l1_gas), ("L2 Gas", bounds.l2_gas)];
+    if let Some(l1_data_gas) = bounds.l1_data_gas {
+        rows.push(("L1 Data Gas", l1_data_gas));
+    }
 
-                                let status_class = if status == "SUCCEEDED" {
-                                    "text-green-400"
-                                } else {
-                                    "text-red-400"
-                                };
+    view! {
+        <div class="mb-4">
+            <h3 class="text-lg font-semibold mb-2">"Resource Bounds"</h3>
+            <table class="w-full text-left text-sm mb-4">
+                <thead class="text-gray-400">
+                    <tr>
+                        <th class="px-4 py-2">"Resource"</th>
+                        <th class="px-4 py-2">"Max Amount"</th>
+                        <th class="px-4 py-2">"Max Price / Unit"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {rows.into_iter().map(|(resource, b)| {
+                        view! {
+                            <tr class="border-t border-gray-700">
+                                <td class="px-4 py-2 text-gray-300">{resource}</td>
+                                <td class="px-4 py-2 font-mono">{b.max_amount}</td>
+                                <td class="px-4 py-2 font-mono">{b.max_price_per_unit.to_string()}</td>
+                            </tr>
+                        }
+                    }).collect::<Vec<_>>()}
+                </tbody>
+            </table>
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-4 mb-4">
+                <TxField label="Tip" value=v3.tip.to_string() />
+                <TxField label="Nonce DA Mode" value=v3.nonce_data_availability_mode />
+                <TxField label="Fee DA Mode" value=v3.fee_data_availability_mode />
+            </div>
+            <FeltList title="Paymaster Data" items=v3.paymaster_data />
+        </div>
+    }
+}
 
-                                view! {
-                                    <div>
-                                        <div class="flex justify-between items-center mb-4">
-                                            <h2 class="text-2xl font-bold">"Transaction"</h2>
-                                            <ExportButton data=export_data filename=export_filename />
-                                        </div>
-                                        <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
-                                            <div class="col-span-1 md:col-span-2">
-                                                <p class="text-gray-400">"Transaction Hash"</p>
-                                                <div class="flex items-center flex-wrap">
-                                                    <p class="font-mono text-sm break-all text-blue-400">{tx_hash}</p>
-                                                    <CopyButton text=tx_hash_copy />
-                                                </div>
-                                            </div>
-                                            <div>
-                                                <p class="text-gray-400">"Block"</p>
-                                                <p class="text-blue-400 font-semibold">{"#"}{block_num}</p>
-                                            </div>
-                                            <div>
-                                                <p class="text-gray-400">"Index"</p>
+#[component]
+fn ExecutionResourcesView(resources: ExecutionResources) -> impl IntoView {
+    let builtins = [
+        ("Range Check", resources.range_check_builtin_applications),
+        ("Pedersen", resources.pedersen_builtin_applications),
+        ("Poseidon", resources.poseidon_builtin_applications),
+        ("EC Op", resources.ec_op_builtin_applications),
+        ("ECDSA", resources.ecdsa_builtin_applications),
+        ("Bitwise", resources.bitwise_builtin_applications),
+        ("Keccak", resources.keccak_builtin_applications),
+        ("Segment Arena", resources.segment_arena_builtin),
+    ];
+    let total = resources.total_gas_consumed;
+    let da = resources.data_availability;
+    let gas_rows = [
+        ("L1 Gas", total.l1_gas, da.l1_gas),
+        ("L1 Data Gas", total.l1_data_gas, da.l1_data_gas),
+        ("L2 Gas", total.l2_gas, da.l2_gas),
+    ];
+
+    view! {
+        <div class="mb-6">
+            <h3 class="text-lg font-semibold mb-2">"Execution Resources"</h3>
+            <div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-4">
+                <div>
+                    <p class="text-gray-400">"Steps"</p>
+                    <p class="text-blue-400 font-semibold">{resources.steps}</p>
+                </div>
+                <div>
+                    <p class="text-gray-400">"Memory Holes"</p>
+                    <p>{resources.memory_holes}</p>
+                </div>
+                {builtins.into_iter().map(|(name, count)| {
+                    view! {
+                        <div>
+                            <p class="text-gray-400">{name}</p>
+                            <p class={if count > 0 { "" } else { "text-gray-500" }}>{count}</p>
+                        </div>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+            <table class="w-full text-left text-sm">
+                <thead class="text-gray-400">
+                    <tr>
+                        <th class="px-4 py-2">"Resource"</th>
+                        <th class="px-4 py-2">"Total Consumed"</th>
+                        <th class="px-4 py-2">"Data Availability"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {gas_rows.into_iter().map(|(resource, total, da)| {
+                        view! {
+                            <tr class="border-t border-gray-700">
+                                <td class="px-4 py-2 text-gray-300">{resource}</td>
+                                <td class="px-4 py-2 font-mono">{total.to_string()}</td>
+                                <td class="px-4 py-2 font-mono">{da.to_string()}</td>
+                            </tr>
+                        }
+                    }).collect::<Vec<_>>()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Variant-specific transaction fields, one layout per transaction type
+#[component]
+fn TransactionBodyView(body: TransactionBody) -> impl IntoView {
+    let opt_field = |label: &'static str, value: Option<String>| {
+        value.map(|value| view! { <TxField label=label value=value /> })
+    };
+    let v3_view = |v3: Option<V3TransactionFields>| v3.map(|v3| view! { <V3FieldsView v3=v3 /> });
+
+    match body {
+        TransactionBody::Invoke {
+            max_fee,
+            entry_point_selector,
+            v3,
+            account_deployment_data,
+        } => view! {
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+                {opt_field("Max Fee", max_fee)}
+                {opt_field("Entry Point Selector", entry_point_selector)}
+            </div>
+            {v3_view(v3)}
+            <FeltList title="Account Deployment Data" items=account_deployment_data />
+        }
+        .into_any(),
+        TransactionBody::L1Handler {
+            contract_address,
+            entry_point_selector,
+        } => view! {
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+                <TxField label="Contract Address" value=contract_address />
+                <TxField label="Entry Point Selector" value=entry_point_selector />
+            </div>
+        }
+        .into_any(),
+        TransactionBody::Declare {
+            class_hash,
+            compiled_class_hash,
+            max_fee,
+            v3,
+            account_deployment_data,
+        } => view! {
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+                <TxField label="Class Hash" value=class_hash />
+                {opt_field("Compiled Class Hash", compiled_class_hash)}
+                {opt_field("Max Fee", max_fee)}
+            </div>
+            {v3_view(v3)}
+            <FeltList title="Account Deployment Data" items=account_deployment_data />
+        }
+        .into_any(),
+        TransactionBody::Deploy {
+            class_hash,
+            contract_address_salt,
+            constructor_calldata,
+        } => view! {
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+                <TxField label="Class Hash" value=class_hash />
+                <TxField label="Contract Address Salt" value=contract_address_salt />
+            </div>
+            <FeltList title="Constructor Calldata" items=constructor_calldata />
+        }
+        .into_any(),
+        TransactionBody::DeployAccount {
+            class_hash,
+            contract_address_salt,
+            constructor_calldata,
+            max_fee,
+            v3,
+        } => view! {
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+                <TxField label="Class Hash" value=class_hash />
+                <TxField label="Contract Address Salt" value=contract_address_salt />
+                {opt_field("Max Fee", max_fee)}
+            </div>
+            {v3_view(v3)}
+            <FeltList title="Constructor Calldata" items=constructor_calldata />
+        }
+        .into_any(),
+    }
+}
+
+#[component]
+fn PendingTransactionRow(tx: PendingTransactionInfo) -> impl IntoView {
+    let status_class = match tx.transaction.status.as_str() {
+        "SUCCEEDED" => "text-green-400",
+        "REVERTED" => "text-red-400",
+        _ => "text-gray-500",
+    };
+    let storage_writes: usize = tx
+        .state_diff
+        .storage_diffs
+        .iter()
+        .map(|d| d.storage_entries.len())
+        .sum();
+    let nonce = tx
+        .transaction
+        .nonce
+        .clone()
+        .unwrap_or_else(|| "-".to_string());
+    let sender = tx.transaction.sender_address.clone().unwrap_or_default();
+    let state_diff = tx.state_diff.clone();
+
+    view! {
+        <tr class="border-b border-gray-700 align-top">
+            <td class="px-4 py-3 text-gray-400">{tx.transaction.tx_index}</td>
+            <td class="px-4 py-3 font-mono text-sm text-blue-400" title=tx.transaction.tx_hash.clone()>{truncate_hash(&tx.transaction.tx_hash)}</td>
+            <td class="px-4 py-3">
+                <span class="px-2 py-1 text-xs rounded bg-gray-700">{tx.transaction.tx_type.clone()}</span>
+            </td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=sender.clone()>{truncate_hash(&sender)}</td>
+            <td class="px-4 py-3 font-mono text-sm">{nonce}</td>
+            <td class="px-4 py-3 text-sm text-gray-400">{format_millis(tx.arrived_at)}</td>
+            <td class="px-4 py-3">
+                <details>
+                    <summary class={format!("cursor-pointer {status_class}")}>
+                        {tx.transaction.status.clone()}", "{storage_writes}" storage writes"
+                    </summary>
+                    <div class="mt-2 space-y-1 text-xs">
+                        {tx.transaction.revert_reason.clone().map(|reason| view! {
+                            <pre class="p-2 bg-gray-900 rounded text-red-300 whitespace-pre-wrap">{reason}</pre>
+                        })}
+                        <p class="text-gray-400">"Fee: "{tx.transaction.actual_fee.clone()}" "{tx.transaction.fee_unit.clone()}</p>
+                        <p class="text-gray-400">"Events: "{tx.transaction.events.len()}</p>
+                        {state_diff.nonces.into_iter().map(|n| view! {
+                            <p class="font-mono text-gray-300">"nonce "{truncate_hash(&n.contract_address)}" -> "{n.nonce}</p>
+                        }).collect::<Vec<_>>()}
+                        {state_diff.storage_diffs.into_iter().flat_map(|d| {
+                            let address = d.address;
+                            d.storage_entries.into_iter().map(move |e| view! {
+                                <p class="font-mono text-gray-300" title=format!("{}[{}] = {}", address, e.key, e.value)>
+                                    {truncate_hash(&address)}"["{truncate_hash(&e.key)}"] = "{truncate_hash(&e.value)}
+                                </p>
+                            })
+                        }).collect::<Vec<_>>()}
+                        {state_diff.deployed_contracts.into_iter().map(|d| view! {
+                            <p class="font-mono text-gray-300">"deployed "{truncate_hash(&d.address)}" class "{truncate_hash(&d.class_hash)}</p>
+                        }).collect::<Vec<_>>()}
+                        {tx.declared_class_hash.clone().map(|hash| view! {
+                            <p class="font-mono text-gray-300">"declared "{truncate_hash(&hash)}</p>
+                        })}
+                    </div>
+                </details>
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn PendingBlockView(on_back: impl Fn() + 'static) -> impl IntoView {
+    let pending = LocalResource::new(fetch_pending_block);
+
+    view! {
+        <div class="bg-gray-800 rounded-lg p-6">
+            <button
+                class="mb-4 text-blue-400 hover:underline"
+                on:click=move |_| on_back()
+            >
+                "< Back to blocks"
+            </button>
+
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading pending block..."</p> }>
+                {move || {
+                    pending.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(Some(block)) => {
+                                let header = block.header.clone();
+                                let export_data = serde_json::to_string_pretty(block).unwrap_or_default();
+                                let export_filename = format!("pending_block_{}.json", header.block_number);
+                                let undecodable = block.undecodable_transactions;
+                                view! {
+                                    <div>
+                                        <div class="flex justify-between items-center mb-4">
+                                            <h2 class="text-2xl font-bold">
+                                                "Block #"{header.block_number}
+                                                <span class="ml-3 px-2 py-1 text-sm rounded bg-amber-700 text-amber-100 align-middle">"pending"</span>
+                                            </h2>
+                                            <ExportButton data=export_data filename=export_filename />
+                                        </div>
+                                        <p class="text-gray-400 text-sm mb-4">
+                                            "Transactions the sequencer had executed into this block when the database was written. The block has no hash or commitments until it is closed."
+                                        </p>
+                                        <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+                                            <div>
+                                                <p class="text-gray-400 text-sm">"Sequencer"</p>
+                                                <p class="font-mono break-all">{header.sequencer_address}</p>
+                                            </div>
+                                            <div>
+                                                <p class="text-gray-400 text-sm">"Timestamp"</p>
+                                                <p class="font-mono">{header.timestamp}</p>
+                                            </div>
+                                            <div>
+                                                <p class="text-gray-400 text-sm">"Protocol version"</p>
+                                                <p class="font-mono">{header.protocol_version}</p>
+                                            </div>
+                                            <div>
+                                                <p class="text-gray-400 text-sm">"L1 DA mode"</p>
+                                                <p class="font-mono">{header.l1_da_mode}</p>
+                                            </div>
+                                        </div>
+                                        {(undecodable > 0).then(|| view! {
+                                            <p class="text-amber-400 text-sm mb-2">{undecodable}" preconfirmed entries failed to decode; see the raw browser"</p>
+                                        })}
+                                        <table class="w-full text-left">
+                                            <thead class="text-gray-400 text-sm">
+                                                <tr>
+                                                    <th class="px-4 py-2">"#"</th>
+                                                    <th class="px-4 py-2">"Tx Hash"</th>
+                                                    <th class="px-4 py-2">"Type"</th>
+                                                    <th class="px-4 py-2">"Sender"</th>
+                                                    <th class="px-4 py-2">"Nonce"</th>
+                                                    <th class="px-4 py-2">"Arrived"</th>
+                                                    <th class="px-4 py-2">"Result"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {block.transactions.clone().into_iter().map(|tx| {
+                                                    view! { <PendingTransactionRow tx=tx /> }
+                                                }).collect::<Vec<_>>()}
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }.into_any()
+                            }
+                            Ok(None) => view! {
+                                <p class="text-gray-500">"No block is under construction: the chain tip is a closed block"</p>
+                            }.into_any(),
+                            Err(e) => view! {
+                                <p class="text-red-400">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn TransactionDetailView(
+    block_number: u64,
+    tx_index: usize,
+    on_back: impl Fn() + 'static,
+) -> impl IntoView {
+    let tx =
+        LocalResource::new(move || async move { fetch_transaction(block_number, tx_index).await });
+
+    view! {
+        <div class="bg-gray-800 rounded-lg p-6">
+            <button
+                class="mb-4 text-blue-400 hover:underline"
+                on:click=move |_| on_back()
+            >
+                "< Back to block"
+            </button>
+
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading transaction..."</p> }>
+                {move || {
+                    tx.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(t) => {
+                                let tx_hash = t.tx_hash.clone();
+                                let tx_hash_copy = t.tx_hash.clone();
+                                let tx_type = t.tx_type.clone();
+                                let status = t.status.clone();
+                                let revert_reason = t.revert_reason.clone();
+                                let block_num = t.block_number;
+                                let idx = t.tx_index;
+                                let actual_fee = t.actual_fee.clone();
+                                let fee_unit = t.fee_unit.clone();
+                                let sender = t.sender_address.clone();
+                                let sender_copy = t.sender_address.clone();
+                                let nonce = t.nonce.clone();
+                                let version = t.version.clone();
+                                let calldata = t.calldata.clone();
+                                let signature = t.signature.clone();
+                                let events = t.events.clone();
+                                let decoded = t.decoded.clone().unwrap_or_default();
+                                let decoded_calls = decoded.calls;
+                                let decoded_events = decoded.events;
+                                let body = t.body.clone();
+                                let execution_resources = t.execution_resources.clone();
+                                let export_data = serde_json::to_string_pretty(&*t).unwrap_or_default();
+                                let export_filename = format!("tx_{}.json", truncate_hash(&t.tx_hash));
+
+                                let status_class = if status == "SUCCEEDED" {
+                                    "text-green-400"
+                                } else {
+                                    "text-red-400"
+                                };
+
+                                view! {
+                                    <div>
+                                        <div class="flex justify-between items-center mb-4">
+                                            <h2 class="text-2xl font-bold">"Transaction"</h2>
+                                            <ExportButton data=export_data filename=export_filename />
+                                        </div>
+                                        <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+                                            <div class="col-span-1 md:col-span-2">
+                                                <p class="text-gray-400">"Transaction Hash"</p>
+                                                <div class="flex items-center flex-wrap">
+                                                    <p class="font-mono text-sm break-all text-blue-400">{tx_hash}</p>
+                                                    <CopyButton text=tx_hash_copy />
+                                                </div>
+                                            </div>
+                                            <div>
+                                                <p class="text-gray-400">"Block"</p>
+                                                <p class="text-blue-400 font-semibold">{"#"}{block_num}</p>
+                                            </div>
+                                            <div>
+                                                <p class="text-gray-400">"Index"</p>
                                                 <p>{idx}</p>
                                             </div>
                                             <div>
@@ -855,6 +1576,19 @@ fn TransactionDetailView(
                                             })}
                                         </div>
 
+                                        {body.map(|body| view! { <TransactionBodyView body=body /> })}
+
+                                        {execution_resources.map(|resources| view! { <ExecutionResourcesView resources=resources /> })}
+
+                                        {(!decoded_calls.is_empty()).then(|| view! {
+                                            <div class="mb-4">
+                                                <h3 class="text-lg font-semibold mb-2">"Calls ("{decoded_calls.len()}")"</h3>
+                                                <div class="space-y-2">
+                                                    {decoded_calls.into_iter().map(|call| view! { <DecodedCallView call=call /> }).collect::<Vec<_>>()}
+                                                </div>
+                                            </div>
+                                        })}
+
                                         {if !calldata.is_empty() {
                                             view! {
                                                 <div class="mb-4">
@@ -901,10 +1635,14 @@ fn TransactionDetailView(
                                                     <h3 class="text-lg font-semibold mb-2">"Events ("{events.len()}")"</h3>
                                                     <div class="space-y-2">
                                                         {events.into_iter().enumerate().map(|(i, evt)| {
+                                                            let decoded_event = decoded_events.iter().find(|d| d.index == i).cloned();
                                                             view! {
                                                                 <div class="bg-gray-900 rounded p-3">
                                                                     <p class="text-sm font-semibold text-purple-400 mb-1">
                                                                         "Event "{i + 1}
+                                                                        {decoded_event.as_ref().map(|d| view! {
+                                                                            <span class="ml-2 font-mono text-gray-200">{short_abi_name(&d.name)}</span>
+                                                                        })}
                                                                     </p>
                                                                     <p class="text-xs text-gray-400">
                                                                         "From: "
@@ -913,6 +1651,7 @@ fn TransactionDetailView(
                                                                     <p class="text-xs text-gray-400">
                                                                         "Keys: "{evt.keys.len()}" | Data: "{evt.data.len()}
                                                                     </p>
+                                                                    {decoded_event.map(|d| view! { <DecodedArgList args=d.fields /> })}
                                                                 </div>
                                                             }
                                                         }).collect::<Vec<_>>()}
@@ -936,6 +1675,61 @@ fn TransactionDetailView(
     }
 }
 
+/// Last path segment of an ABI name, e.g. `Transfer` for `erc20::ERC20Component::Transfer`
+fn short_abi_name(name: &str) -> String {
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+/// Decoded values are JSON; show strings without quotes
+fn decoded_value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[component]
+fn DecodedCallView(call: DecodedCallInfo) -> impl IntoView {
+    let title = call
+        .function
+        .clone()
+        .unwrap_or_else(|| truncate_hash(&call.selector));
+    let calldata_len = call.calldata.len();
+
+    view! {
+        <div class="bg-gray-900 rounded p-3">
+            <p class="text-sm font-semibold text-green-400 mb-1">
+                <span class="font-mono">{title}</span>
+                <span class="text-gray-400 font-normal">" on "</span>
+                <span class="font-mono text-gray-300 font-normal">{truncate_hash(&call.to)}</span>
+            </p>
+            {match call.args {
+                Some(args) => view! { <DecodedArgList args=args /> }.into_any(),
+                None => view! {
+                    <p class="text-xs text-gray-500">
+                        {calldata_len}" calldata items, not decoded with the target's ABI"
+                    </p>
+                }.into_any(),
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn DecodedArgList(args: Vec<DecodedArgInfo>) -> impl IntoView {
+    view! {
+        <div class="mt-1 space-y-1">
+            {args.into_iter().map(|arg| view! {
+                <p class="font-mono text-xs break-all">
+                    <span class="text-gray-300">{arg.name}</span>
+                    <span class="text-gray-500">": "{short_abi_name(&arg.type_name)}" = "</span>
+                    <span class="text-gray-100">{decoded_value_text(&arg.value)}</span>
+                </p>
+            }).collect::<Vec<_>>()}
+        </div>
+    }
+}
+
 // Contract components
 
 #[component]
@@ -1021,6 +1815,7 @@ fn ContractList(on_select: impl Fn(String) + Clone + Send + 'static) -> impl Int
 fn ContractDetailView(address: String, on_back: impl Fn() + 'static) -> impl IntoView {
     let addr = address.clone();
     let addr2 = address.clone();
+    let addr3 = address.clone();
     let contract = LocalResource::new(move || {
         let addr = addr.clone();
         async move { fetch_contract(addr).await }
@@ -1029,6 +1824,11 @@ fn ContractDetailView(address: String, on_back: impl Fn() + 'static) -> impl Int
         let addr = addr2.clone();
         async move { fetch_contract_storage(addr, 50).await }
     });
+    let class_history = LocalResource::new(move || {
+        let addr = addr3.clone();
+        async move { fetch_contract_class_history(addr).await }
+    });
+    let (history_key, set_history_key) = signal::<Option<String>>(None);
 
     view! {
         <div class="bg-gray-800 rounded-lg p-6">
@@ -1085,6 +1885,34 @@ fn ContractDetailView(address: String, on_back: impl Fn() + 'static) -> impl Int
                 }}
             </Suspense>
 
+            <Suspense fallback=|| ()>
+                {move || {
+                    class_history.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(data) if data.entries.len() > 1 => {
+                                let entries = data.entries.clone();
+                                view! {
+                                    <div class="mt-2">
+                                        <h3 class="text-lg font-semibold mb-2">"Class History"</h3>
+                                        <div class="bg-gray-900 rounded p-3">
+                                            {entries.into_iter().map(|entry| {
+                                                view! {
+                                                    <div class="flex gap-4 py-1 text-sm">
+                                                        <span class="text-gray-400 w-28 shrink-0">"Block "{entry.block_number}</span>
+                                                        <span class="font-mono text-xs text-purple-400 break-all">{entry.class_hash}</span>
+                                                    </div>
+                                                }
+                                            }).collect::<Vec<_>>()}
+                                        </div>
+                                    </div>
+                                }.into_any()
+                            },
+                            _ => ().into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+
             <div class="mt-6">
                 <h3 class="text-lg font-semibold mb-4">"Storage"</h3>
                 <Suspense fallback=move || view! { <p class="text-gray-400">"Loading storage..."</p> }>
@@ -1101,14 +1929,26 @@ fn ContractDetailView(address: String, on_back: impl Fn() + 'static) -> impl Int
                                         view! {
                                             <div class="bg-gray-900 rounded p-3 max-h-96 overflow-y-auto">
                                                 {entries.into_iter().map(|entry| {
+                                                    let key = entry.key.clone();
                                                     view! {
                                                         <div class="border-b border-gray-700 py-2">
-                                                            <p class="font-mono text-xs text-gray-400 break-all">
-                                                                "Key: "{entry.key}
-                                                            </p>
+                                                            <div class="flex justify-between items-start gap-2">
+                                                                <p class="font-mono text-xs text-gray-400 break-all">
+                                                                    "Key: "{entry.key}
+                                                                </p>
+                                                                <button
+                                                                    class="text-xs text-blue-400 hover:underline shrink-0"
+                                                                    on:click=move |_| set_history_key.set(Some(key.clone()))
+                                                                >
+                                                                    "History"
+                                                                </button>
+                                                            </div>
                                                             <p class="font-mono text-xs text-gray-300 break-all">
                                                                 "Value: "{entry.value}
                                                             </p>
+                                                            {entry.block_number.map(|n| view! {
+                                                                <p class="text-xs text-gray-500">"Written at block "{n}</p>
+                                                            })}
                                                         </div>
                                                     }
                                                 }).collect::<Vec<_>>()}
@@ -1124,12 +1964,79 @@ fn ContractDetailView(address: String, on_back: impl Fn() + 'static) -> impl Int
                     }}
                 </Suspense>
             </div>
+
+            {move || {
+                let address = address.clone();
+                history_key.get().map(|key| view! {
+                    <StorageHistoryPanel
+                        address=address
+                        storage_key=key
+                        on_close=move || set_history_key.set(None)
+                    />
+                })
+            }}
         </div>
     }
 }
 
 // Class components
 
+#[component]
+fn StorageHistoryPanel(
+    address: String,
+    storage_key: String,
+    on_close: impl Fn() + 'static,
+) -> impl IntoView {
+    let key = storage_key.clone();
+    let history = LocalResource::new(move || {
+        let address = address.clone();
+        let key = key.clone();
+        async move { fetch_storage_history(address, key, 100).await }
+    });
+
+    view! {
+        <div class="mt-6 bg-gray-900 rounded p-4">
+            <div class="flex justify-between items-center mb-3">
+                <h3 class="text-lg font-semibold">"Slot History"</h3>
+                <button class="text-sm text-gray-400 hover:text-white" on:click=move |_| on_close()>
+                    "Close"
+                </button>
+            </div>
+            <p class="font-mono text-xs text-gray-400 break-all mb-4">{storage_key}</p>
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading history..."</p> }>
+                {move || {
+                    history.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(data) if data.entries.is_empty() => view! {
+                                <p class="text-gray-500">"No versions found"</p>
+                            }.into_any(),
+                            Ok(data) => {
+                                let entries = data.entries.clone();
+                                view! {
+                                    <ol class="border-l-2 border-gray-700 ml-2 max-h-96 overflow-y-auto">
+                                        {entries.into_iter().map(|entry| {
+                                            view! {
+                                                <li class="relative pl-4 pb-3">
+                                                    <span class="absolute -left-[5px] top-1.5 w-2 h-2 rounded-full bg-blue-400"></span>
+                                                    <p class="text-sm text-blue-400">"Block "{entry.block_number}</p>
+                                                    <p class="font-mono text-xs text-gray-300 break-all">{entry.value}</p>
+                                                </li>
+                                            }
+                                        }).collect::<Vec<_>>()}
+                                    </ol>
+                                }.into_any()
+                            },
+                            Err(e) => view! {
+                                <p class="text-red-400">"Error loading history: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
 #[component]
 fn ClassRow(class: ClassResponse, on_click: impl Fn(String) + 'static) -> impl IntoView {
     let class_hash = class.class_hash.clone();
@@ -1231,6 +2138,8 @@ fn ClassDetailView(class_hash: String, on_back: impl Fn() + 'static) -> impl Int
                                 let hash = c.class_hash.clone();
                                 let class_type = c.class_type.clone();
                                 let compiled_hash = c.compiled_class_hash.clone();
+                                let definition = c.definition.clone();
+                                let hash_for_program = hash.clone();
 
                                 let type_color = match class_type.as_str() {
                                     "SIERRA" => "text-green-400",
@@ -1257,6 +2166,7 @@ fn ClassDetailView(class_hash: String, on_back: impl Fn() + 'static) -> impl Int
                                                 </p>
                                             </div>
                                         </div>
+                                        {definition.map(|d| view! { <ClassDefinitionView class_hash=hash_for_program definition=d /> })}
                                     </div>
                                 }.into_any()
                             },
@@ -1271,12 +2181,301 @@ fn ClassDetailView(class_hash: String, on_back: impl Fn() + 'static) -> impl Int
     }
 }
 
-// State Diff View
+#[component]
+fn ClassDefinitionView(class_hash: String, definition: ClassDefinitionInfo) -> impl IntoView {
+    let is_sierra = definition.sierra_version.is_some();
+    let program_label = if is_sierra {
+        format!("{} felts", definition.program_length)
+    } else {
+        format!("{} bytes (compressed)", definition.program_length)
+    };
+    let or_dash = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+    let abi = definition
+        .abi
+        .as_ref()
+        .map(|abi| serde_json::to_string_pretty(abi).unwrap_or_default());
+    let entry_points = definition.entry_points;
+    let legacy_program = definition.legacy_program;
+    let legacy_program_error = definition.legacy_program_error;
+    let compiled_class_hash_check = definition.compiled_class_hash_check;
+    let program_url = format!("{}/api/classes/{class_hash}/program", get_api_base());
+    let (show_casm, set_show_casm) = signal(false);
+    let casm_hash = class_hash.clone();
+
+    view! {
+        <div class="mt-6">
+            <div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-6">
+                <TxField label="Declared At Block" value=definition.declared_at.to_string() />
+                <TxField label="Program Size" value=program_label />
+                <TxField label="Contract Class Version" value=or_dash(definition.contract_class_version) />
+                <TxField label="Sierra Version" value=or_dash(definition.sierra_version) />
+                <TxField label="Compiler Version" value=or_dash(definition.compiler_version) />
+            </div>
+
+            <div class="flex items-center justify-between mb-2">
+                <h3 class="text-lg font-semibold">"Program"</h3>
+                <a
+                    class="px-3 py-1 text-sm bg-gray-700 hover:bg-gray-600 rounded"
+                    href=program_url
+                    download=format!("{class_hash}-program.json")
+                >
+                    "Download program JSON"
+                </a>
+            </div>
+            {legacy_program_error.map(|e| view! {
+                <p class="text-red-400 text-sm mb-6">"Legacy program could not be read: "{e}</p>
+            })}
+            {legacy_program.map(|p| view! {
+                <div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-6">
+                    <TxField label="Cairo Compiler" value=or_dash(p.compiler_version) />
+                    <TxField label="Builtins" value=p.builtins.join(", ") />
+                    <TxField label="Data Length" value=p.data_length.to_string() />
+                    <TxField label="Hints" value=p.hints_count.to_string() />
+                    <TxField label="Identifiers" value=p.identifiers_count.to_string() />
+                    <TxField label="Main Scope" value=or_dash(p.main_scope) />
+                    <TxField label="Decompressed Size" value=format!("{} bytes", p.decompressed_size) />
+                    <div class="col-span-2 md:col-span-4">
+                        <TxField label="Prime" value=p.prime />
+                    </div>
+                </div>
+            })}
+
+            {compiled_class_hash_check.map(|check| {
+                let declared = check
+                    .state_diff_compiled_class_hash
+                    .unwrap_or_else(|| "not listed".to_string());
+                let casm_hash = casm_hash.clone();
+                view! {
+                    <div class="mb-6">
+                        <div class="flex items-center justify-between mb-2">
+                            <h3 class="text-lg font-semibold">"Compiled Class"</h3>
+                            <button
+                                class="px-3 py-1 text-sm bg-gray-700 hover:bg-gray-600 rounded"
+                                on:click=move |_| set_show_casm.update(|v| *v = !*v)
+                            >
+                                {move || if show_casm.get() { "Hide CASM" } else { "View CASM" }}
+                            </button>
+                        </div>
+                        {if check.matches {
+                            view! {
+                                <p class="text-green-400 text-sm">
+                                    "✓ Compiled class hash matches the state diff of block #"{check.declared_at}
+                                </p>
+                            }.into_any()
+                        } else {
+                            view! {
+                                <p class="text-red-400 text-sm">
+                                    "✗ State diff of block #"{check.declared_at}" declares compiled class hash "
+                                    <span class="font-mono break-all">{declared}</span>
+                                </p>
+                            }.into_any()
+                        }}
+                        {move || show_casm.get().then(|| view! { <CompiledClassPanel class_hash=casm_hash.clone() /> })}
+                    </div>
+                }
+            })}
+
+            <h3 class="text-lg font-semibold mb-2">"Entry Points"</h3>
+            <EntryPointTable title="Constructor" entry_points=entry_points.constructor is_sierra />
+            <EntryPointTable title="External" entry_points=entry_points.external is_sierra />
+            <EntryPointTable title="L1 Handler" entry_points=entry_points.l1_handler is_sierra />
+
+            <h3 class="text-lg font-semibold mt-6 mb-2">"ABI"</h3>
+            {match abi {
+                Some(abi) => view! {
+                    <pre class="bg-gray-900 rounded p-4 font-mono text-xs text-gray-300 whitespace-pre-wrap break-all max-h-[32rem] overflow-auto">{abi}</pre>
+                }.into_any(),
+                None => view! { <p class="text-gray-500 text-sm">"No ABI stored for this class"</p> }.into_any(),
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn CompiledClassPanel(class_hash: String) -> impl IntoView {
+    let compiled = LocalResource::new(move || {
+        let hash = class_hash.clone();
+        async move { fetch_class_compiled(hash).await }
+    });
+
+    view! {
+        <div class="mt-4 bg-gray-900 rounded p-4">
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading compiled class..."</p> }>
+                {move || {
+                    compiled.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(c) => {
+                                let c = c.clone();
+                                let hints = c
+                                    .hints
+                                    .iter()
+                                    .map(|h| {
+                                        let code = h
+                                            .hints
+                                            .iter()
+                                            .map(|hint| hint.to_string())
+                                            .collect::<Vec<_>>()
+                                            .join("\n");
+                                        (h.pc, code)
+                                    })
+                                    .collect::<Vec<_>>();
+                                view! {
+                                    <div>
+                                        <div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-4">
+                                            <div class="col-span-2 md:col-span-4">
+                                                <TxField label="Compiled Class Hash" value=c.compiled_class_hash />
+                                            </div>
+                                            <TxField label="Stored At Block" value=c.block_number.to_string() />
+                                            <TxField label="Compiler Version" value=c.compiler_version />
+                                            <TxField label="Bytecode Length" value=format!("{} felts", c.bytecode_length) />
+                                            <TxField label="Hints" value=format!("{} at {} pcs", c.hints_count, c.hints.len()) />
+                                        </div>
+                                        <CasmEntryPointTable title="Constructor" entry_points=c.entry_points.constructor />
+                                        <CasmEntryPointTable title="External" entry_points=c.entry_points.external />
+                                        <CasmEntryPointTable title="L1 Handler" entry_points=c.entry_points.l1_handler />
+                                        <p class="text-gray-400 text-sm mt-4 mb-1">"Hints"</p>
+                                        <div class="max-h-96 overflow-auto">
+                                            <table class="w-full text-left text-sm">
+                                                <thead class="text-gray-400">
+                                                    <tr>
+                                                        <th class="px-4 py-2">"PC"</th>
+                                                        <th class="px-4 py-2">"Hint"</th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {hints.into_iter().map(|(pc, code)| view! {
+                                                        <tr class="border-t border-gray-700 align-top">
+                                                            <td class="px-4 py-2 font-mono">{pc}</td>
+                                                            <td class="px-4 py-2 font-mono text-xs whitespace-pre-wrap break-all">{code}</td>
+                                                        </tr>
+                                                    }).collect::<Vec<_>>()}
+                                                </tbody>
+                                            </table>
+                                        </div>
+                                    </div>
+                                }.into_any()
+                            },
+                            Err(e) => view! {
+                                <p class="text-red-400">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn CasmEntryPointTable(
+    title: &'static str,
+    entry_points: Vec<CasmEntryPointInfo>,
+) -> impl IntoView {
+    let count = entry_points.len();
+
+    view! {
+        <div class="mb-4">
+            <p class="text-gray-400 text-sm mb-1">{title}" ("{count}")"</p>
+            {if entry_points.is_empty() {
+                view! { <p class="text-gray-500 text-sm">"None"</p> }.into_any()
+            } else {
+                view! {
+                    <table class="w-full text-left text-sm">
+                        <thead class="text-gray-400">
+                            <tr>
+                                <th class="px-4 py-2">"Selector"</th>
+                                <th class="px-4 py-2">"Offset"</th>
+                                <th class="px-4 py-2">"Builtins"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {entry_points.into_iter().map(|ep| view! {
+                                <tr class="border-t border-gray-700">
+                                    <td class="px-4 py-2 font-mono break-all">{ep.selector}</td>
+                                    <td class="px-4 py-2 font-mono">{ep.offset}</td>
+                                    <td class="px-4 py-2 text-gray-300">{ep.builtins.join(", ")}</td>
+                                </tr>
+                            }).collect::<Vec<_>>()}
+                        </tbody>
+                    </table>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn EntryPointTable(
+    title: &'static str,
+    entry_points: Vec<EntryPointInfo>,
+    is_sierra: bool,
+) -> impl IntoView {
+    let count = entry_points.len();
+    let index_label = if is_sierra {
+        "Function Index"
+    } else {
+        "Offset"
+    };
+
+    view! {
+        <div class="mb-4">
+            <p class="text-gray-400 text-sm mb-1">{title}" ("{count}")"</p>
+            {if entry_points.is_empty() {
+                view! { <p class="text-gray-500 text-sm">"None"</p> }.into_any()
+            } else {
+                view! {
+                    <table class="w-full text-left text-sm">
+                        <thead class="text-gray-400">
+                            <tr>
+                                <th class="px-4 py-2">"Selector"</th>
+                                <th class="px-4 py-2">{index_label}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {entry_points.into_iter().map(|ep| {
+                                let index = ep
+                                    .function_idx
+                                    .or(ep.offset)
+                                    .map(|i| i.to_string())
+                                    .unwrap_or_else(|| "-".to_string());
+                                view! {
+                                    <tr class="border-t border-gray-700">
+                                        <td class="px-4 py-2 font-mono break-all">{ep.selector}</td>
+                                        <td class="px-4 py-2 font-mono">{index}</td>
+                                    </tr>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </tbody>
+                    </table>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+// State Diff View
 
 #[component]
 fn StateDiffView(block_number: u64, on_back: impl Fn() + 'static) -> impl IntoView {
     let state_diff =
         LocalResource::new(move || async move { fetch_state_diff(block_number).await });
+    let trie_changes =
+        LocalResource::new(move || async move { fetch_trie_changes(block_number).await });
+    let (show_trie_changes, set_show_trie_changes) = signal(false);
+
+    let tab_class = move |active: bool| {
+        if active {
+            "px-4 py-2 text-sm rounded-t bg-gray-900 text-white"
+        } else {
+            "px-4 py-2 text-sm rounded-t text-gray-400 hover:text-white"
+        }
+    };
+    let has_mismatches = move || {
+        trie_changes
+            .get()
+            .and_then(|r| r.as_ref().ok().map(|c| !c.mismatches.is_empty()))
+            .unwrap_or(false)
+    };
 
     view! {
         <div class="bg-gray-800 rounded-lg p-6">
@@ -1289,6 +2488,31 @@ fn StateDiffView(block_number: u64, on_back: impl Fn() + 'static) -> impl IntoVi
 
             <h2 class="text-2xl font-bold mb-4">"State Diff for Block #"{block_number}</h2>
 
+            <div class="flex gap-2 mb-4 border-b border-gray-700">
+                <button
+                    class=move || tab_class(!show_trie_changes.get())
+                    on:click=move |_| set_show_trie_changes.set(false)
+                >
+                    "State Diff"
+                </button>
+                <button
+                    class=move || tab_class(show_trie_changes.get())
+                    on:click=move |_| set_show_trie_changes.set(true)
+                >
+                    "Trie Changes"
+                    {move || if has_mismatches() {
+                        view! { <span class="ml-2 text-red-400" title="Trie log disagrees with the state diff">"⚠"</span> }.into_any()
+                    } else {
+                        view! { <span></span> }.into_any()
+                    }}
+                </button>
+            </div>
+
+            <div class:hidden=move || !show_trie_changes.get()>
+                <TrieChangesPanel trie_changes=trie_changes />
+            </div>
+
+            <div class:hidden=move || show_trie_changes.get()>
             <Suspense fallback=move || view! { <p class="text-gray-400">"Loading state diff..."</p> }>
                 {move || {
                     state_diff.get().map(|result| {
@@ -1421,17 +2645,859 @@ fn StateDiffView(block_number: u64, on_back: impl Fn() + 'static) -> impl IntoVi
                                             view! { <div></div> }.into_any()
                                         }}
 
-                                        // Show empty message if no changes
-                                        {if diff.deployed_contracts.is_empty() && diff.storage_diffs.is_empty() && diff.declared_classes.is_empty() && diff.nonces.is_empty() && diff.replaced_classes.is_empty() {
-                                            view! {
-                                                <p class="text-gray-500">"No state changes in this block"</p>
-                                            }.into_any()
-                                        } else {
-                                            view! { <div></div> }.into_any()
-                                        }}
+                                        // Show empty message if no changes
+                                        {if diff.deployed_contracts.is_empty() && diff.storage_diffs.is_empty() && diff.declared_classes.is_empty() && diff.nonces.is_empty() && diff.replaced_classes.is_empty() {
+                                            view! {
+                                                <p class="text-gray-500">"No state changes in this block"</p>
+                                            }.into_any()
+                                        } else {
+                                            view! { <div></div> }.into_any()
+                                        }}
+                                    </div>
+                                }.into_any()
+                            },
+                            Err(e) => view! {
+                                <p class="text-red-400">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn TrieChangesPanel(
+    trie_changes: LocalResource<Result<TrieChangesResponse, String>>,
+) -> impl IntoView {
+    view! {
+        <Suspense fallback=move || view! { <p class="text-gray-400">"Loading trie changes..."</p> }>
+            {move || {
+                trie_changes.get().map(|result| {
+                    match result.as_ref() {
+                        Ok(changes) => {
+                            let leaves = changes.leaves.clone();
+                            let mismatches = changes.mismatches.clone();
+
+                            view! {
+                                <div class="space-y-6">
+                                    {if !changes.has_log {
+                                        view! {
+                                            <p class="text-gray-500">"No trie log for this block (no trie changes, or the log was pruned)"</p>
+                                        }.into_any()
+                                    } else {
+                                        view! {
+                                            <p class="text-gray-400 text-sm">
+                                                {leaves.len()}" leaves changed, "{changes.nodes_changed}" trie nodes rewritten"
+                                            </p>
+                                        }.into_any()
+                                    }}
+
+                                    // Disagreements with block_state_diff
+                                    {if !mismatches.is_empty() {
+                                        view! {
+                                            <div>
+                                                <h3 class="text-lg font-semibold mb-2 text-red-400">"Trie Log / State Diff Mismatches ("{mismatches.len()}")"</h3>
+                                                <div class="bg-gray-900 rounded p-3 space-y-2">
+                                                    {mismatches.into_iter().map(|m| {
+                                                        view! {
+                                                            <div class="border-b border-gray-700 pb-2 text-xs">
+                                                                <span class="px-2 py-1 rounded bg-gray-700">{m.trie}</span>
+                                                                {m.contract_address.map(|a| view! {
+                                                                    <span class="ml-2 font-mono text-blue-400">{truncate_hash(&a)}</span>
+                                                                })}
+                                                                <span class="ml-2 font-mono text-gray-400">{truncate_hash(&m.key)}</span>
+                                                                <p class="text-red-400 mt-1">{m.reason}</p>
+                                                            </div>
+                                                        }
+                                                    }).collect::<Vec<_>>()}
+                                                </div>
+                                            </div>
+                                        }.into_any()
+                                    } else {
+                                        view! { <div></div> }.into_any()
+                                    }}
+
+                                    // Leaf Changes
+                                    {if !leaves.is_empty() {
+                                        view! {
+                                            <div>
+                                                <h3 class="text-lg font-semibold mb-2 text-yellow-400">"Leaf Changes ("{leaves.len()}")"</h3>
+                                                <div class="bg-gray-900 rounded p-3 max-h-96 overflow-y-auto">
+                                                    {leaves.into_iter().map(|l| {
+                                                        view! {
+                                                            <div class="text-xs border-b border-gray-700 py-1">
+                                                                <span class="px-2 py-1 rounded bg-gray-700">{l.trie}</span>
+                                                                {l.contract_address.map(|a| view! {
+                                                                    <span class="ml-2 font-mono text-blue-400">{truncate_hash(&a)}</span>
+                                                                })}
+                                                                <span class="ml-2 font-mono text-gray-400">{truncate_hash(&l.key)}</span>
+                                                                ": "
+                                                                <span class="font-mono text-gray-500">{l.old_value.unwrap_or_else(|| "(new)".to_string())}</span>
+                                                                " → "
+                                                                <span class="font-mono text-gray-300">{l.new_value.unwrap_or_else(|| "(removed)".to_string())}</span>
+                                                            </div>
+                                                        }
+                                                    }).collect::<Vec<_>>()}
+                                                </div>
+                                            </div>
+                                        }.into_any()
+                                    } else {
+                                        view! { <div></div> }.into_any()
+                                    }}
+                                </div>
+                            }.into_any()
+                        },
+                        Err(e) => view! {
+                            <p class="text-red-400">"Error: " {e.clone()}</p>
+                        }.into_any(),
+                    }
+                })
+            }}
+        </Suspense>
+    }
+}
+
+// Advanced Filters View
+
+#[component]
+fn IndexedTransactionRow(
+    tx: IndexedTransactionInfo,
+    on_click: impl Fn((u64, u64)) + 'static,
+) -> impl IntoView {
+    let block_number = tx.block_number;
+    let tx_index = tx.tx_index;
+    let status_class = if tx.status == "SUCCEEDED" {
+        "text-green-400"
+    } else {
+        "text-red-400"
+    };
+
+    view! {
+        <tr
+            class="border-b border-gray-700 hover:bg-gray-700 cursor-pointer"
+            on:click=move |_| on_click((block_number, tx_index))
+        >
+            <td class="px-4 py-3 text-blue-400 font-mono">{"#"}{tx.block_number}</td>
+            <td class="px-4 py-3 text-gray-400">{tx.tx_index}</td>
+            <td class="px-4 py-3 font-mono text-sm text-blue-400">{truncate_hash(&tx.tx_hash)}</td>
+            <td class="px-4 py-3">
+                <span class="px-2 py-1 text-xs rounded bg-gray-700">{tx.tx_type}</span>
+            </td>
+            <td class={format!("px-4 py-3 {}", status_class)}>{tx.status.clone()}</td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-400">
+                {tx.sender_address.clone().map(|s| truncate_hash(&s)).unwrap_or_else(|| "-".to_string())}
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn AdvancedFiltersView(
+    on_tx_select: impl Fn((u64, u64)) + Clone + Send + 'static,
+) -> impl IntoView {
+    let (status_filter, set_status_filter) = signal::<Option<String>>(None);
+    let (block_from, set_block_from) = signal::<Option<u64>>(None);
+    let (block_to, set_block_to) = signal::<Option<u64>>(None);
+    let (trigger, set_trigger) = signal(0u32);
+
+    let transactions = LocalResource::new(move || {
+        let _ = trigger.get();
+        let status = status_filter.get();
+        let from = block_from.get();
+        let to = block_to.get();
+        async move { fetch_filtered_transactions(status, from, to, 100).await }
+    });
+
+    view! {
+        <div class="bg-gray-800 rounded-lg p-6">
+            <h2 class="text-2xl font-bold mb-4">"Advanced Transaction Filters"</h2>
+
+            // Filter controls
+            <div class="grid grid-cols-4 gap-4 mb-6">
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Status"</label>
+                    <select
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        on:change=move |ev| {
+                            let val = event_target_value(&ev);
+                            set_status_filter.set(if val.is_empty() { None } else { Some(val) });
+                        }
+                    >
+                        <option value="">"All"</option>
+                        <option value="SUCCEEDED">"Succeeded"</option>
+                        <option value="REVERTED">"Reverted"</option>
+                    </select>
+                </div>
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Block From"</label>
+                    <input
+                        type="number"
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        placeholder="0"
+                        on:input=move |ev| {
+                            let val = event_target_value(&ev);
+                            set_block_from.set(val.parse().ok());
+                        }
+                    />
+                </div>
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Block To"</label>
+                    <input
+                        type="number"
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        placeholder="latest"
+                        on:input=move |ev| {
+                            let val = event_target_value(&ev);
+                            set_block_to.set(val.parse().ok());
+                        }
+                    />
+                </div>
+                <div class="flex items-end">
+                    <button
+                        class="px-4 py-2 bg-blue-600 hover:bg-blue-700 rounded text-sm"
+                        on:click=move |_| set_trigger.update(|t| *t = t.wrapping_add(1))
+                    >
+                        "Apply Filters"
+                    </button>
+                </div>
+            </div>
+
+            // Results
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading transactions..."</p> }>
+                {move || {
+                    let on_tx_select = on_tx_select.clone();
+                    transactions.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(data) => {
+                                let txs = data.transactions.clone();
+                                let total = data.total;
+
+                                if txs.is_empty() {
+                                    view! {
+                                        <p class="text-gray-500">"No transactions match filters"</p>
+                                    }.into_any()
+                                } else {
+                                    view! {
+                                        <div>
+                                            <p class="text-gray-400 text-sm mb-2">"Found "{total}" transactions"</p>
+                                            <table class="w-full text-left">
+                                                <thead class="text-gray-400 text-sm">
+                                                    <tr>
+                                                        <th class="px-4 py-2">"Block"</th>
+                                                        <th class="px-4 py-2">"Index"</th>
+                                                        <th class="px-4 py-2">"Hash"</th>
+                                                        <th class="px-4 py-2">"Type"</th>
+                                                        <th class="px-4 py-2">"Status"</th>
+                                                        <th class="px-4 py-2">"Sender"</th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {txs.into_iter().map(|tx| {
+                                                        let on_tx_select = on_tx_select.clone();
+                                                        view! { <IndexedTransactionRow tx=tx on_click=move |t| on_tx_select(t) /> }
+                                                    }).collect::<Vec<_>>()}
+                                                </tbody>
+                                            </table>
+                                        </div>
+                                    }.into_any()
+                                }
+                            },
+                            Err(e) => view! {
+                                <p class="text-red-400">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+// Trie Explorer Components
+
+#[component]
+fn TrieNodeCard(node: TrieNodeInfo, depth: usize) -> impl IntoView {
+    let is_binary = node.kind == "binary";
+    let taken = node.taken.clone().unwrap_or_default();
+    let left_class = if taken == "left" {
+        "text-green-400"
+    } else {
+        "text-gray-500"
+    };
+    let right_class = if taken == "right" {
+        "text-green-400"
+    } else {
+        "text-gray-500"
+    };
+
+    view! {
+        <div
+            class="border-l-2 border-slate-700 pl-3"
+            style=format!("margin-left: {}rem", depth.min(12))
+        >
+            <div class="bg-gray-900 rounded p-3 text-sm space-y-1">
+                <div class="flex items-center gap-2">
+                    <span class={if is_binary { "px-2 py-0.5 rounded text-xs bg-blue-900 text-blue-300" } else { "px-2 py-0.5 rounded text-xs bg-purple-900 text-purple-300" }}>
+                        {node.kind.clone()}
+                    </span>
+                    <span class="text-gray-400">"height "{node.height}</span>
+                    <span class="text-gray-500 font-mono text-xs" title=node.path.clone()>
+                        "depth "{node.path.len()}
+                    </span>
+                </div>
+                <div class="font-mono text-xs break-all">
+                    <span class="text-gray-400">"hash: "</span>
+                    {node.hash.clone().unwrap_or_else(|| "(not stored)".to_string())}
+                </div>
+                {if is_binary {
+                    view! {
+                        <div class="font-mono text-xs break-all">
+                            <div class=left_class>"left: "{node.left.clone().unwrap_or_default()}</div>
+                            <div class=right_class>"right: "{node.right.clone().unwrap_or_default()}</div>
+                        </div>
+                    }.into_any()
+                } else {
+                    let edge_path = node.edge_path.clone().unwrap_or_default();
+                    view! {
+                        <div class="font-mono text-xs break-all">
+                            <div class="text-gray-300" title=edge_path.clone()>
+                                "edge path ("{edge_path.len()}" bits): "{truncate_hex(&edge_path, 48)}
+                            </div>
+                            <div class="text-gray-300">"child: "{node.child.clone().unwrap_or_default()}</div>
+                        </div>
+                    }.into_any()
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn TrieExplorerView() -> impl IntoView {
+    let (trie, set_trie) = signal("contract".to_string());
+    let (key, set_key) = signal(String::new());
+    let (contract, set_contract) = signal(String::new());
+    let (request, set_request) = signal::<Option<(String, String, String)>>(None);
+
+    let path = LocalResource::new(move || {
+        let request = request.get();
+        async move {
+            match request {
+                Some((trie, key, contract)) => Some(fetch_trie_path(trie, key, contract).await),
+                None => None,
+            }
+        }
+    });
+
+    view! {
+        <div class="bg-gray-800 rounded-lg p-6">
+            <h2 class="text-2xl font-bold mb-4">"Trie Explorer"</h2>
+            <p class="text-gray-400 text-sm mb-4">
+                "Walk a bonsai trie from its root to a key and inspect every node hash on the way."
+            </p>
+
+            <div class="grid grid-cols-4 gap-4 mb-6">
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Trie"</label>
+                    <select
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        on:change=move |ev| set_trie.set(event_target_value(&ev))
+                    >
+                        <option value="contract">"Contract"</option>
+                        <option value="storage">"Contract storage"</option>
+                        <option value="class">"Class"</option>
+                    </select>
+                </div>
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Contract (storage only)"</label>
+                    <input
+                        type="text"
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm font-mono focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        placeholder="0x..."
+                        disabled=move || trie.get() != "storage"
+                        on:input=move |ev| set_contract.set(event_target_value(&ev))
+                    />
+                </div>
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Key"</label>
+                    <input
+                        type="text"
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm font-mono focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        placeholder="address, storage key or class hash"
+                        on:input=move |ev| set_key.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="flex items-end">
+                    <button
+                        class="px-4 py-2 bg-blue-600 hover:bg-blue-700 rounded text-sm"
+                        on:click=move |_| {
+                            let trie = trie.get();
+                            let contract = if trie == "storage" { contract.get() } else { String::new() };
+                            set_request.set(Some((trie, key.get().trim().to_string(), contract.trim().to_string())));
+                        }
+                    >
+                        "Walk Path"
+                    </button>
+                </div>
+            </div>
+
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Walking trie..."</p> }>
+                {move || {
+                    path.get().map(|result| {
+                        match result.as_ref() {
+                            None => view! {
+                                <p class="text-gray-500">"Pick a trie and a key to start."</p>
+                            }.into_any(),
+                            Some(Ok(data)) => {
+                                let (end_text, end_class) = match data.end.as_str() {
+                                    "leaf" => (format!("Reached leaf: {}", data.leaf.clone().unwrap_or_default()), "text-green-400"),
+                                    "diverged" => (format!("Key not in trie: path diverges at depth {}", data.diverged_at.unwrap_or_default()), "text-yellow-400"),
+                                    "missing_node" => (format!("Missing node at depth {}", data.missing_path.as_ref().map(|p| p.len()).unwrap_or_default()), "text-red-400"),
+                                    _ => ("Trie is empty".to_string(), "text-gray-400"),
+                                };
+                                let nodes = data.nodes.clone();
+                                view! {
+                                    <div class="space-y-4">
+                                        <div class="grid grid-cols-2 gap-4 text-sm">
+                                            <div>
+                                                <span class="text-gray-400">"Root hash: "</span>
+                                                <span class="font-mono break-all">{data.root_hash.clone().unwrap_or_else(|| "-".to_string())}</span>
+                                            </div>
+                                            <div>
+                                                <span class="text-gray-400">"Flat value: "</span>
+                                                <span class="font-mono break-all">{data.flat_value.clone().unwrap_or_else(|| "-".to_string())}</span>
+                                            </div>
+                                            <div>
+                                                <span class="text-gray-400">"Key: "</span>
+                                                <span class="font-mono break-all">{data.key.clone()}</span>
+                                            </div>
+                                            <div>
+                                                <span class="text-gray-400">"Identifier: "</span>
+                                                <span class="font-mono break-all">{format!("0x{}", data.identifier)}</span>
+                                            </div>
+                                        </div>
+                                        <div class="space-y-2">
+                                            {nodes.into_iter().enumerate().map(|(depth, node)| {
+                                                view! { <TrieNodeCard node=node depth=depth /> }
+                                            }).collect::<Vec<_>>()}
+                                        </div>
+                                        <p class=format!("text-sm font-mono break-all {end_class}")>{end_text}</p>
+                                    </div>
+                                }.into_any()
+                            }
+                            Some(Err(e)) => view! {
+                                <p class="text-red-400">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+// Mempool Components
+
+/// Format a millisecond Unix timestamp as UTC
+fn format_millis(millis: u64) -> String {
+    js_sys::Date::new(&JsValue::from_f64(millis as f64))
+        .to_iso_string()
+        .as_string()
+        .unwrap_or_else(|| millis.to_string())
+}
+
+fn mempool_fee_text(tx: &MempoolTransactionInfo) -> String {
+    if let Some(bounds) = &tx.resource_bounds {
+        format!(
+            "L2 gas {} @ {}, tip {}",
+            bounds.l2_gas.max_amount,
+            bounds.l2_gas.max_price_per_unit,
+            tx.tip.unwrap_or(0)
+        )
+    } else if let Some(max_fee) = &tx.max_fee {
+        format!("max fee {max_fee}")
+    } else if let Some(paid) = tx.paid_fee_on_l1 {
+        format!("paid on L1 {paid}")
+    } else {
+        "-".to_string()
+    }
+}
+
+#[component]
+fn MempoolRow(tx: MempoolTransactionInfo) -> impl IntoView {
+    let row_class = if tx.blocked_by_nonce_gap {
+        "border-b border-gray-700 bg-amber-900/20"
+    } else {
+        "border-b border-gray-700"
+    };
+    let fee = mempool_fee_text(&tx);
+    let nonce = tx
+        .nonce
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    view! {
+        <tr class=row_class>
+            <td class="px-4 py-3 font-mono text-sm text-blue-400" title=tx.tx_hash.clone()>{truncate_hash(&tx.tx_hash)}</td>
+            <td class="px-4 py-3">
+                <span class="px-2 py-1 text-xs rounded bg-gray-700">{tx.tx_type}</span>
+            </td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=tx.contract_address.clone()>{truncate_hash(&tx.contract_address)}</td>
+            <td class="px-4 py-3 text-center">
+                {nonce}
+                {tx.blocked_by_nonce_gap.then(|| view! {
+                    <span class="ml-2 px-2 py-0.5 text-xs rounded bg-amber-700 text-amber-100" title="An earlier nonce of this account is missing">"gap"</span>
+                })}
+            </td>
+            <td class="px-4 py-3 text-sm text-gray-400">{format_millis(tx.arrived_at)}</td>
+            <td class="px-4 py-3 text-sm font-mono text-gray-400">{fee}</td>
+        </tr>
+    }
+}
+
+#[component]
+fn MempoolGapList(accounts: Vec<MempoolAccountInfo>) -> impl IntoView {
+    let gapped: Vec<MempoolAccountInfo> = accounts
+        .into_iter()
+        .filter(|a| a.missing_count > 0)
+        .collect();
+    if gapped.is_empty() {
+        return view! {
+            <p class="text-green-400 text-sm mb-4">"No nonce gaps: every account's pending nonces are contiguous"</p>
+        }
+        .into_any();
+    }
+
+    view! {
+        <div class="mb-6 p-4 bg-amber-900/20 border border-amber-700 rounded">
+            <h3 class="text-lg font-semibold text-amber-300 mb-2">
+                {gapped.len()}" account(s) with nonce gaps"
+            </h3>
+            <div class="space-y-2 text-sm">
+                {gapped.into_iter().map(|a| {
+                    let mut missing = a
+                        .missing_nonces
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if a.missing_count > a.missing_nonces.len() as u64 {
+                        missing.push_str(&format!(" ... ({} missing)", a.missing_count));
+                    }
+                    let current = a.current_nonce.map(|n| n.to_string()).unwrap_or_else(|| "not deployed".to_string());
+                    view! {
+                        <div>
+                            <span class="font-mono text-gray-300" title=a.address.clone()>{truncate_hash(&a.address)}</span>
+                            <span class="text-gray-400">" current nonce "{current}", missing "</span>
+                            <span class="font-mono text-amber-300">{missing}</span>
+                        </div>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn MempoolView() -> impl IntoView {
+    let (sort, set_sort) = signal("sender".to_string());
+    let mempool = LocalResource::new(move || {
+        let sort = sort.get();
+        async move { fetch_mempool(sort, 200).await }
+    });
+
+    view! {
+        <div class="bg-gray-800 rounded-lg p-6">
+            <h2 class="text-2xl font-bold mb-4">"Mempool"</h2>
+            <p class="text-gray-400 text-sm mb-4">
+                "Validated transactions persisted in mempool_transactions. Transactions behind a missing nonce cannot be included until the gap is filled."
+            </p>
+
+            <div class="mb-4 w-48">
+                <label class="block text-gray-400 text-sm mb-1">"Sort by"</label>
+                <select
+                    class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                    on:change=move |ev| set_sort.set(event_target_value(&ev))
+                >
+                    <option value="sender">"Sender, nonce"</option>
+                    <option value="nonce">"Nonce"</option>
+                    <option value="arrival">"Arrival time"</option>
+                </select>
+            </div>
+
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading mempool..."</p> }>
+                {move || {
+                    mempool.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(data) if data.total == 0 => view! {
+                                <p class="text-gray-500">"The mempool is empty"</p>
+                            }.into_any(),
+                            Ok(data) => {
+                                let shown = data.transactions.len();
+                                let total = data.total;
+                                view! {
+                                    <div>
+                                        <MempoolGapList accounts=data.accounts.clone() />
+                                        <table class="w-full text-left">
+                                            <thead class="text-gray-400 text-sm">
+                                                <tr>
+                                                    <th class="px-4 py-2">"Tx Hash"</th>
+                                                    <th class="px-4 py-2">"Type"</th>
+                                                    <th class="px-4 py-2">"Sender"</th>
+                                                    <th class="px-4 py-2 text-center">"Nonce"</th>
+                                                    <th class="px-4 py-2">"Arrived"</th>
+                                                    <th class="px-4 py-2">"Fee"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {data.transactions.clone().into_iter().map(|tx| {
+                                                    view! { <MempoolRow tx=tx /> }
+                                                }).collect::<Vec<_>>()}
+                                            </tbody>
+                                        </table>
+                                        <p class="text-gray-400 text-sm mt-2 px-4">"Showing "{shown}" of "{total}" transactions"</p>
+                                    </div>
+                                }.into_any()
+                            }
+                            Err(e) => view! {
+                                <p class="text-red-400">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+// Messaging Components
+
+#[component]
+fn L1ToL2MessageRow(
+    message: L1ToL2MessageInfo,
+    on_tx_select: impl Fn((u64, u64)) + 'static,
+) -> impl IntoView {
+    let status_class = if message.status == "PENDING" {
+        "px-2 py-1 text-xs rounded bg-amber-700 text-amber-100"
+    } else {
+        "px-2 py-1 text-xs rounded bg-green-800 text-green-100"
+    };
+    let short = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(truncate_hash)
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let message_hash = short(&message.message_hash);
+    let from_address = short(&message.from_address);
+    let to_address = short(&message.to_address);
+    let selector = short(&message.entry_point_selector);
+    let tx_label = short(&message.tx_hash);
+    let fee = message
+        .paid_fee_on_l1
+        .map(|f| f.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let location = message.block_number.zip(message.tx_index);
+
+    view! {
+        <tr class="border-b border-gray-700">
+            <td class="px-4 py-3 font-mono">{message.nonce}</td>
+            <td class="px-4 py-3"><span class=status_class>{message.status.clone()}</span></td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=message.message_hash.clone().unwrap_or_default()>{message_hash}</td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=message.from_address.clone().unwrap_or_default()>{from_address}</td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=message.to_address.clone().unwrap_or_default()>{to_address}</td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-400" title=message.entry_point_selector.clone().unwrap_or_default()>{selector}</td>
+            <td class="px-4 py-3 text-sm text-gray-400 text-center" title=message.payload.join(", ")>{message.payload.len()}</td>
+            <td class="px-4 py-3 text-sm font-mono text-gray-400">{fee}</td>
+            <td class="px-4 py-3 font-mono text-sm">
+                {match location {
+                    Some(location) => view! {
+                        <button class="text-blue-400 hover:underline" on:click=move |_| on_tx_select(location)>{tx_label}</button>
+                    }.into_any(),
+                    None => view! { <span class="text-gray-500">{tx_label}</span> }.into_any(),
+                }}
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn L2ToL1MessageRow(
+    message: L2ToL1MessageInfo,
+    on_tx_select: impl Fn((u64, u64)) + 'static,
+) -> impl IntoView {
+    let location = (message.block_number, message.tx_index as u64);
+
+    view! {
+        <tr class="border-b border-gray-700">
+            <td class="px-4 py-3 text-blue-400 font-mono">{"#"}{message.block_number}</td>
+            <td class="px-4 py-3 font-mono text-sm">
+                <button class="text-blue-400 hover:underline" on:click=move |_| on_tx_select(location)>
+                    {truncate_hash(&message.tx_hash)}
+                </button>
+                <span class="text-gray-500">{format!(" #{}", message.message_index)}</span>
+            </td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=message.message_hash.clone()>{truncate_hash(&message.message_hash)}</td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=message.from_address.clone()>{truncate_hash(&message.from_address)}</td>
+            <td class="px-4 py-3 font-mono text-sm text-gray-300" title=message.to_address.clone()>{truncate_hash(&message.to_address)}</td>
+            <td class="px-4 py-3 text-sm text-gray-400 text-center" title=message.payload.join(", ")>{message.payload.len()}</td>
+        </tr>
+    }
+}
+
+#[component]
+fn MessagingView(on_tx_select: impl Fn((u64, u64)) + Clone + Send + 'static) -> impl IntoView {
+    let (from_nonce, set_from_nonce) = signal(0u64);
+    let (block_from, set_block_from) = signal::<Option<u64>>(None);
+    let (block_to, set_block_to) = signal::<Option<u64>>(None);
+    let (trigger, set_trigger) = signal(0u32);
+
+    let l1_to_l2 = LocalResource::new(move || {
+        let _ = trigger.get();
+        let from_nonce = from_nonce.get_untracked();
+        async move { fetch_l1_to_l2_messages(from_nonce, 100).await }
+    });
+    let l2_to_l1 = LocalResource::new(move || {
+        let _ = trigger.get();
+        let from = block_from.get_untracked();
+        let to = block_to.get_untracked();
+        async move { fetch_l2_to_l1_messages(from, to, 100).await }
+    });
+    let on_l2_tx_select = on_tx_select.clone();
+
+    view! {
+        <div class="bg-gray-800 rounded-lg p-6">
+            <h2 class="text-2xl font-bold mb-4">"Messaging"</h2>
+            <p class="text-gray-400 text-sm mb-4">
+                "L1 to L2 messages by core contract nonce, with the L1 handler transaction that consumed them, and L2 to L1 messages sent in a block range."
+            </p>
+
+            <div class="grid grid-cols-4 gap-4 mb-6">
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"From L1 Nonce"</label>
+                    <input
+                        type="number"
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        placeholder="0"
+                        on:input=move |ev| set_from_nonce.set(event_target_value(&ev).parse().unwrap_or(0))
+                    />
+                </div>
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Block From"</label>
+                    <input
+                        type="number"
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        placeholder="0"
+                        on:input=move |ev| set_block_from.set(event_target_value(&ev).parse().ok())
+                    />
+                </div>
+                <div>
+                    <label class="block text-gray-400 text-sm mb-1">"Block To"</label>
+                    <input
+                        type="number"
+                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        placeholder="latest"
+                        on:input=move |ev| set_block_to.set(event_target_value(&ev).parse().ok())
+                    />
+                </div>
+                <div class="flex items-end">
+                    <button
+                        class="px-4 py-2 bg-blue-600 hover:bg-blue-700 rounded text-sm"
+                        on:click=move |_| set_trigger.update(|t| *t = t.wrapping_add(1))
+                    >
+                        "Apply"
+                    </button>
+                </div>
+            </div>
+
+            <h3 class="text-lg font-semibold mb-2">"L1 → L2"</h3>
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading L1 to L2 messages..."</p> }>
+                {move || {
+                    let on_tx_select = on_tx_select.clone();
+                    l1_to_l2.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(data) if data.messages.is_empty() && data.undecodable_messages == 0 => view! {
+                                <p class="text-gray-500 mb-6">"No L1 to L2 messages from this nonce"</p>
+                            }.into_any(),
+                            Ok(data) => view! {
+                                <div class="mb-6">
+                                    <p class="text-gray-400 text-sm mb-2">
+                                        {data.pending}" pending, "{data.consumed}" consumed"
+                                        {(data.undecodable_messages > 0).then(|| format!(", {} undecodable messages skipped", data.undecodable_messages))}
+                                    </p>
+                                    <table class="w-full text-left">
+                                        <thead class="text-gray-400 text-sm">
+                                            <tr>
+                                                <th class="px-4 py-2">"Nonce"</th>
+                                                <th class="px-4 py-2">"Status"</th>
+                                                <th class="px-4 py-2">"Message Hash"</th>
+                                                <th class="px-4 py-2">"L1 Sender"</th>
+                                                <th class="px-4 py-2">"L2 Recipient"</th>
+                                                <th class="px-4 py-2">"Selector"</th>
+                                                <th class="px-4 py-2 text-center">"Payload"</th>
+                                                <th class="px-4 py-2">"Paid on L1"</th>
+                                                <th class="px-4 py-2">"L1 Handler Tx"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {data.messages.clone().into_iter().map(|message| {
+                                                let on_tx_select = on_tx_select.clone();
+                                                view! { <L1ToL2MessageRow message=message on_tx_select=move |t| on_tx_select(t) /> }
+                                            }).collect::<Vec<_>>()}
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }.into_any(),
+                            Err(e) => view! {
+                                <p class="text-red-400 mb-6">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+
+            <h3 class="text-lg font-semibold mb-2">"L2 → L1"</h3>
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading L2 to L1 messages..."</p> }>
+                {move || {
+                    let on_tx_select = on_l2_tx_select.clone();
+                    l2_to_l1.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(data) => {
+                                let last_block = data.last_block_scanned.unwrap_or(data.to_block);
+                                let skipped = data.undecodable_transactions;
+                                view! {
+                                    <div>
+                                        <p class="text-gray-400 text-sm mb-2">
+                                            {data.messages.len()}" messages in blocks "{data.from_block}" to "{last_block}
+                                            {(skipped > 0).then(|| format!(", {skipped} undecodable transactions skipped"))}
+                                        </p>
+                                        {(!data.messages.is_empty()).then(|| view! {
+                                            <table class="w-full text-left">
+                                                <thead class="text-gray-400 text-sm">
+                                                    <tr>
+                                                        <th class="px-4 py-2">"Block"</th>
+                                                        <th class="px-4 py-2">"Transaction"</th>
+                                                        <th class="px-4 py-2">"Message Hash"</th>
+                                                        <th class="px-4 py-2">"L2 Sender"</th>
+                                                        <th class="px-4 py-2">"L1 Recipient"</th>
+                                                        <th class="px-4 py-2 text-center">"Payload"</th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {data.messages.clone().into_iter().map(|message| {
+                                                        let on_tx_select = on_tx_select.clone();
+                                                        view! { <L2ToL1MessageRow message=message on_tx_select=move |t| on_tx_select(t) /> }
+                                                    }).collect::<Vec<_>>()}
+                                                </tbody>
+                                            </table>
+                                        })}
                                     </div>
                                 }.into_any()
-                            },
+                            }
                             Err(e) => view! {
                                 <p class="text-red-400">"Error: " {e.clone()}</p>
                             }.into_any(),
@@ -1443,87 +3509,130 @@ fn StateDiffView(block_number: u64, on_back: impl Fn() + 'static) -> impl IntoVi
     }
 }
 
-// Advanced Filters View
+// Block Utilization Components
+
+/// One chart series: a value per block, with the per-block limit when the chain config has one
+struct UtilizationSeries {
+    title: &'static str,
+    values: Vec<(u64, u64)>,
+    limit: Option<u64>,
+}
 
+impl UtilizationSeries {
+    /// Builds a series from the blocks that have the field; `None` when none do
+    fn new(
+        title: &'static str,
+        blocks: &[BouncerWeightsInfo],
+        value: impl Fn(&BouncerWeightsInfo) -> Option<u64>,
+        limit: Option<u64>,
+    ) -> Option<Self> {
+        let values: Vec<(u64, u64)> = blocks
+            .iter()
+            .filter_map(|b| value(b).map(|v| (b.block_number, v)))
+            .collect();
+        (!values.is_empty()).then_some(Self {
+            title,
+            values,
+            limit,
+        })
+    }
+}
+
+/// Share of `limit` used by `value`, in percent
+fn percent_of(value: u64, limit: u64) -> f64 {
+    if limit == 0 {
+        return 0.0;
+    }
+    value as f64 * 100.0 / limit as f64
+}
+
+/// Bar chart of one resource per block. Bars are scaled to the limit when it is known, so the
+/// top of the chart is a full block; bars turn amber above 75% and red above 90%.
 #[component]
-fn IndexedTransactionRow(
-    tx: IndexedTransactionInfo,
-    on_click: impl Fn((u64, u64)) + 'static,
+fn UtilizationChart(
+    series: UtilizationSeries,
+    on_block_select: impl Fn(u64) + Clone + Send + 'static,
 ) -> impl IntoView {
-    let block_number = tx.block_number;
-    let tx_index = tx.tx_index;
-    let status_class = if tx.status == "SUCCEEDED" {
-        "text-green-400"
-    } else {
-        "text-red-400"
+    let max = series.values.iter().map(|(_, v)| *v).max().unwrap_or(0);
+    let scale = series.limit.unwrap_or(0).max(max).max(1);
+    let peak = series
+        .values
+        .iter()
+        .max_by_key(|(_, v)| *v)
+        .map(|(block, _)| *block);
+    let summary = match series.limit {
+        Some(limit) => format!("max {max} of {limit} ({:.1}%)", percent_of(max, limit)),
+        None => format!("max {max}, no limit configured"),
     };
 
     view! {
-        <tr
-            class="border-b border-gray-700 hover:bg-gray-700 cursor-pointer"
-            on:click=move |_| on_click((block_number, tx_index))
-        >
-            <td class="px-4 py-3 text-blue-400 font-mono">{"#"}{tx.block_number}</td>
-            <td class="px-4 py-3 text-gray-400">{tx.tx_index}</td>
-            <td class="px-4 py-3 font-mono text-sm text-blue-400">{truncate_hash(&tx.tx_hash)}</td>
-            <td class="px-4 py-3">
-                <span class="px-2 py-1 text-xs rounded bg-gray-700">{tx.tx_type}</span>
-            </td>
-            <td class={format!("px-4 py-3 {}", status_class)}>{tx.status.clone()}</td>
-            <td class="px-4 py-3 font-mono text-sm text-gray-400">
-                {tx.sender_address.clone().map(|s| truncate_hash(&s)).unwrap_or_else(|| "-".to_string())}
-            </td>
-        </tr>
+        <div class="bg-gray-900 rounded p-4">
+            <div class="flex items-baseline justify-between mb-2">
+                <h3 class="font-semibold">{series.title}</h3>
+                <span class="text-gray-400 text-xs">
+                    {summary}
+                    {peak.map(|block| format!(" at block #{block}"))}
+                </span>
+            </div>
+            <div class="relative h-32 flex items-end gap-px border-b border-gray-700">
+                {series.limit.map(|_| view! {
+                    <div class="absolute left-0 right-0 top-0 border-t border-dashed border-red-500/60" title="Block limit"></div>
+                })}
+                {series.values.into_iter().map(|(block, value)| {
+                    let on_block_select = on_block_select.clone();
+                    let height = value as f64 * 100.0 / scale as f64;
+                    let used = series.limit.map(|limit| percent_of(value, limit));
+                    let color = match used {
+                        Some(p) if p > 90.0 => "bg-red-500 hover:bg-red-400",
+                        Some(p) if p > 75.0 => "bg-amber-500 hover:bg-amber-400",
+                        _ => "bg-sky-500 hover:bg-sky-400",
+                    };
+                    let title = match used {
+                        Some(p) => format!("Block #{block}: {value} ({p:.1}%)"),
+                        None => format!("Block #{block}: {value}"),
+                    };
+                    view! {
+                        <div
+                            class=format!("flex-1 min-w-px cursor-pointer {color}")
+                            style=format!("height: {height:.2}%")
+                            title=title
+                            on:click=move |_| on_block_select(block)
+                        ></div>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+        </div>
     }
 }
 
 #[component]
-fn AdvancedFiltersView(
-    on_tx_select: impl Fn((u64, u64)) + Clone + Send + 'static,
-) -> impl IntoView {
-    let (status_filter, set_status_filter) = signal::<Option<String>>(None);
+fn BlockUtilizationView(on_block_select: impl Fn(u64) + Clone + Send + 'static) -> impl IntoView {
     let (block_from, set_block_from) = signal::<Option<u64>>(None);
     let (block_to, set_block_to) = signal::<Option<u64>>(None);
     let (trigger, set_trigger) = signal(0u32);
 
-    let transactions = LocalResource::new(move || {
+    let weights = LocalResource::new(move || {
         let _ = trigger.get();
-        let status = status_filter.get();
-        let from = block_from.get();
-        let to = block_to.get();
-        async move { fetch_filtered_transactions(status, from, to, 100).await }
+        let from = block_from.get_untracked();
+        let to = block_to.get_untracked();
+        async move { fetch_bouncer_weights(from, to).await }
     });
 
     view! {
         <div class="bg-gray-800 rounded-lg p-6">
-            <h2 class="text-2xl font-bold mb-4">"Advanced Transaction Filters"</h2>
+            <h2 class="text-2xl font-bold mb-4">"Block Utilization"</h2>
+            <p class="text-gray-400 text-sm mb-4">
+                "Resources each block used according to the bouncer, against the per-block limits in the node's chain config. Click a bar to open the block."
+            </p>
 
-            // Filter controls
-            <div class="grid grid-cols-4 gap-4 mb-6">
-                <div>
-                    <label class="block text-gray-400 text-sm mb-1">"Status"</label>
-                    <select
-                        class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
-                        on:change=move |ev| {
-                            let val = event_target_value(&ev);
-                            set_status_filter.set(if val.is_empty() { None } else { Some(val) });
-                        }
-                    >
-                        <option value="">"All"</option>
-                        <option value="SUCCEEDED">"Succeeded"</option>
-                        <option value="REVERTED">"Reverted"</option>
-                    </select>
-                </div>
+            <div class="grid grid-cols-3 gap-4 mb-6">
                 <div>
                     <label class="block text-gray-400 text-sm mb-1">"Block From"</label>
                     <input
                         type="number"
                         class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
-                        placeholder="0"
-                        on:input=move |ev| {
-                            let val = event_target_value(&ev);
-                            set_block_from.set(val.parse().ok());
-                        }
+                        placeholder="latest - 99"
+                        on:input=move |ev| set_block_from.set(event_target_value(&ev).parse().ok())
                     />
                 </div>
                 <div>
@@ -1532,10 +3641,7 @@ fn AdvancedFiltersView(
                         type="number"
                         class="w-full px-3 py-2 bg-gray-700 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
                         placeholder="latest"
-                        on:input=move |ev| {
-                            let val = event_target_value(&ev);
-                            set_block_to.set(val.parse().ok());
-                        }
+                        on:input=move |ev| set_block_to.set(event_target_value(&ev).parse().ok())
                     />
                 </div>
                 <div class="flex items-end">
@@ -1543,51 +3649,242 @@ fn AdvancedFiltersView(
                         class="px-4 py-2 bg-blue-600 hover:bg-blue-700 rounded text-sm"
                         on:click=move |_| set_trigger.update(|t| *t = t.wrapping_add(1))
                     >
-                        "Apply Filters"
+                        "Apply"
                     </button>
                 </div>
             </div>
 
-            // Results
-            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading transactions..."</p> }>
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading bouncer weights..."</p> }>
                 {move || {
-                    let on_tx_select = on_tx_select.clone();
-                    transactions.get().map(|result| {
+                    let on_block_select = on_block_select.clone();
+                    weights.get().map(|result| {
                         match result.as_ref() {
+                            Ok(data) if data.blocks.is_empty() => view! {
+                                <p class="text-gray-500">
+                                    "No bouncer weights stored for blocks "{data.from_block}" to "{data.to_block}
+                                </p>
+                            }.into_any(),
                             Ok(data) => {
-                                let txs = data.transactions.clone();
-                                let total = data.total;
+                                let capacity = data.block_max_capacity.clone().unwrap_or_default();
+                                let blocks = &data.blocks;
+                                let series = [
+                                    UtilizationSeries::new("Sierra Gas", blocks, |b| b.sierra_gas, capacity.sierra_gas),
+                                    UtilizationSeries::new("Proving Gas", blocks, |b| b.proving_gas, capacity.proving_gas),
+                                    UtilizationSeries::new("Steps", blocks, |b| b.n_steps, None),
+                                    UtilizationSeries::new("Builtins", blocks, |b| b.builtin_count, None),
+                                    UtilizationSeries::new("L1 Gas", blocks, |b| Some(b.l1_gas), capacity.l1_gas),
+                                    UtilizationSeries::new("Transactions", blocks, |b| b.n_txs, capacity.n_txs),
+                                    UtilizationSeries::new("Events", blocks, |b| Some(b.n_events), capacity.n_events),
+                                    UtilizationSeries::new("State Diff Size", blocks, |b| Some(b.state_diff_size), capacity.state_diff_size),
+                                    UtilizationSeries::new("Message Segment Length", blocks, |b| Some(b.message_segment_length), capacity.message_segment_length),
+                                ];
+                                let skipped = data.undecodable_blocks;
+                                view! {
+                                    <div>
+                                        <p class="text-gray-400 text-sm mb-4">
+                                            {blocks.len()}" blocks from "{data.from_block}" to "{data.to_block}
+                                            {match &data.block_max_capacity_error {
+                                                Some(e) => format!(", limits are unknown because the chain config failed to decode: {e}"),
+                                                None if data.block_max_capacity.is_none() => ", no chain config stored so limits are unknown".to_string(),
+                                                None => String::new(),
+                                            }}
+                                            {(skipped > 0).then(|| format!(", {skipped} undecodable blocks skipped"))}
+                                        </p>
+                                        <div class="grid grid-cols-1 xl:grid-cols-2 gap-4">
+                                            {series.into_iter().flatten().map(|series| {
+                                                let on_block_select = on_block_select.clone();
+                                                view! { <UtilizationChart series=series on_block_select=on_block_select /> }
+                                            }).collect::<Vec<_>>()}
+                                        </div>
+                                    </div>
+                                }.into_any()
+                            }
+                            Err(e) => view! {
+                                <p class="text-red-400">"Error: " {e.clone()}</p>
+                            }.into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
 
-                                if txs.is_empty() {
-                                    view! {
-                                        <p class="text-gray-500">"No transactions match filters"</p>
-                                    }.into_any()
-                                } else {
-                                    view! {
-                                        <div>
-                                            <p class="text-gray-400 text-sm mb-2">"Found "{total}" transactions"</p>
-                                            <table class="w-full text-left">
-                                                <thead class="text-gray-400 text-sm">
-                                                    <tr>
-                                                        <th class="px-4 py-2">"Block"</th>
-                                                        <th class="px-4 py-2">"Index"</th>
-                                                        <th class="px-4 py-2">"Hash"</th>
-                                                        <th class="px-4 py-2">"Type"</th>
-                                                        <th class="px-4 py-2">"Status"</th>
-                                                        <th class="px-4 py-2">"Sender"</th>
-                                                    </tr>
-                                                </thead>
-                                                <tbody>
-                                                    {txs.into_iter().map(|tx| {
-                                                        let on_tx_select = on_tx_select.clone();
-                                                        view! { <IndexedTransactionRow tx=tx on_click=move |t| on_tx_select(t) /> }
-                                                    }).collect::<Vec<_>>()}
-                                                </tbody>
-                                            </table>
+// Node Status Components
+
+fn optional_block(block: Option<u64>) -> String {
+    block
+        .map(|n| format!("#{n}"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Badge for a block lag: green when caught up, amber for a few blocks, red beyond that
+#[component]
+fn LagBadge(lag: Option<u64>) -> impl IntoView {
+    let (class, text) = match lag {
+        None => (
+            "px-2 py-1 text-xs rounded bg-gray-700 text-gray-300",
+            "unknown".to_string(),
+        ),
+        Some(0) => (
+            "px-2 py-1 text-xs rounded bg-green-800 text-green-100",
+            "in sync".to_string(),
+        ),
+        Some(lag) if lag <= 10 => (
+            "px-2 py-1 text-xs rounded bg-amber-700 text-amber-100",
+            format!("{lag} behind"),
+        ),
+        Some(lag) => (
+            "px-2 py-1 text-xs rounded bg-red-800 text-red-100",
+            format!("{lag} behind"),
+        ),
+    };
+    view! { <span class=class>{text}</span> }
+}
+
+#[component]
+fn StatusField(label: &'static str, value: String) -> impl IntoView {
+    view! {
+        <div>
+            <p class="text-gray-400 text-sm">{label}</p>
+            <p class="font-mono break-all">{value}</p>
+        </div>
+    }
+}
+
+#[component]
+fn NodeStatusView() -> impl IntoView {
+    let meta = LocalResource::new(fetch_node_meta);
+
+    view! {
+        <div class="bg-gray-800 rounded-lg p-6">
+            <h2 class="text-2xl font-bold mb-4">"Node Status"</h2>
+            <p class="text-gray-400 text-sm mb-4">
+                "Decoded from the meta column family: how far the L1 confirmation and the tries are behind the L2 tip."
+            </p>
+
+            <Suspense fallback=move || view! { <p class="text-gray-400">"Loading node status..."</p> }>
+                {move || {
+                    meta.get().map(|result| {
+                        match result.as_ref() {
+                            Ok(data) => {
+                                let data = data.clone();
+                                let tip = match &data.chain_tip {
+                                    Some(tip) => format!("{} #{}", tip.status, tip.block_number),
+                                    None => "-".to_string(),
+                                };
+                                let preconfirmed = data.chain_tip.as_ref().and_then(|tip| tip.preconfirmed.clone());
+                                view! {
+                                    <div class="space-y-6">
+                                        <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                                            <StatusField label="Chain ID" value=data.chain_id.clone().unwrap_or_else(|| "-".to_string()) />
+                                            <StatusField label="Chain name" value=data.chain_name.clone().unwrap_or_else(|| "-".to_string()) />
+                                            <StatusField label="Genesis block hash" value=data.genesis_block_hash.clone().unwrap_or_else(|| "-".to_string()) />
+                                            <StatusField label="Chain tip" value=tip />
                                         </div>
-                                    }.into_any()
-                                }
-                            },
+
+                                        <table class="w-full text-left">
+                                            <thead class="text-gray-400 text-sm">
+                                                <tr>
+                                                    <th class="px-4 py-2">"Marker"</th>
+                                                    <th class="px-4 py-2">"Block"</th>
+                                                    <th class="px-4 py-2">"Lag behind L2 tip"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                <tr class="border-b border-gray-700">
+                                                    <td class="px-4 py-3">"L2 tip (latest closed block)"</td>
+                                                    <td class="px-4 py-3 font-mono">{optional_block(data.latest_block)}</td>
+                                                    <td class="px-4 py-3"></td>
+                                                </tr>
+                                                <tr class="border-b border-gray-700">
+                                                    <td class="px-4 py-3">"Confirmed on L1"</td>
+                                                    <td class="px-4 py-3 font-mono">{optional_block(data.confirmed_on_l1_tip)}</td>
+                                                    <td class="px-4 py-3"><LagBadge lag=data.l1_confirmation_lag /></td>
+                                                </tr>
+                                                <tr class="border-b border-gray-700">
+                                                    <td class="px-4 py-3">"Latest applied trie update"</td>
+                                                    <td class="px-4 py-3 font-mono">{optional_block(data.latest_applied_trie_update)}</td>
+                                                    <td class="px-4 py-3"><LagBadge lag=data.trie_lag /></td>
+                                                </tr>
+                                                <tr class="border-b border-gray-700">
+                                                    <td class="px-4 py-3">"Last synced L1 event block"</td>
+                                                    <td class="px-4 py-3 font-mono">{optional_block(data.last_synced_l1_event_block)}</td>
+                                                    <td class="px-4 py-3 text-gray-500 text-sm">"L1 block"</td>
+                                                </tr>
+                                                <tr class="border-b border-gray-700">
+                                                    <td class="px-4 py-3">"Snap sync block"</td>
+                                                    <td class="px-4 py-3 font-mono">{optional_block(data.snap_sync_latest_block)}</td>
+                                                    <td class="px-4 py-3"></td>
+                                                </tr>
+                                            </tbody>
+                                        </table>
+
+                                        {preconfirmed.map(|header| view! {
+                                            <div>
+                                                <h3 class="text-lg font-semibold mb-2">"Preconfirmed block #"{header.block_number}</h3>
+                                                <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                                                    <StatusField label="Sequencer" value=header.sequencer_address />
+                                                    <StatusField label="Timestamp" value=header.timestamp.to_string() />
+                                                    <StatusField label="Protocol version" value=header.protocol_version />
+                                                    <StatusField label="L1 DA mode" value=header.l1_da_mode />
+                                                </div>
+                                            </div>
+                                        })}
+
+                                        {data.runtime_exec_config.clone().map(|config| view! {
+                                            <div>
+                                                <h3 class="text-lg font-semibold mb-2">"Runtime execution config"</h3>
+                                                <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-2">
+                                                    <StatusField label="Protocol version" value=config.protocol_version />
+                                                    <StatusField label="Fees charged" value=(!config.no_charge_fee).to_string() />
+                                                </div>
+                                                {config.chain_config.map(|yaml| view! {
+                                                    <details>
+                                                        <summary class="cursor-pointer text-blue-400 text-sm">"Chain config"</summary>
+                                                        <pre class="mt-2 p-3 bg-gray-900 rounded text-xs overflow-x-auto">{yaml}</pre>
+                                                    </details>
+                                                })}
+                                            </div>
+                                        })}
+
+                                        {(!data.devnet_accounts.is_empty()).then(|| view! {
+                                            <div>
+                                                <h3 class="text-lg font-semibold mb-2">"Devnet accounts ("{data.devnet_accounts.len()}")"</h3>
+                                                <table class="w-full text-left">
+                                                    <thead class="text-gray-400 text-sm">
+                                                        <tr>
+                                                            <th class="px-4 py-2">"Address"</th>
+                                                            <th class="px-4 py-2">"Public key"</th>
+                                                            <th class="px-4 py-2">"Class hash"</th>
+                                                        </tr>
+                                                    </thead>
+                                                    <tbody>
+                                                        {data.devnet_accounts.clone().into_iter().map(|a| view! {
+                                                            <tr class="border-b border-gray-700">
+                                                                <td class="px-4 py-3 font-mono text-sm text-gray-300" title=a.address.clone()>{truncate_hash(&a.address)}</td>
+                                                                <td class="px-4 py-3 font-mono text-sm text-gray-300" title=a.public_key.clone()>{truncate_hash(&a.public_key)}</td>
+                                                                <td class="px-4 py-3 font-mono text-sm text-gray-300" title=a.class_hash.clone()>{truncate_hash(&a.class_hash)}</td>
+                                                            </tr>
+                                                        }).collect::<Vec<_>>()}
+                                                    </tbody>
+                                                </table>
+                                            </div>
+                                        })}
+
+                                        {(!data.unknown_keys.is_empty()).then(|| view! {
+                                            <p class="text-gray-400 text-sm">"Unknown meta keys: "{data.unknown_keys.join(", ")}</p>
+                                        })}
+                                        {(!data.decode_errors.is_empty()).then(|| view! {
+                                            <div class="p-4 bg-red-900/20 border border-red-700 rounded text-sm">
+                                                {data.decode_errors.clone().into_iter().map(|e| view! {
+                                                    <p class="text-red-300"><span class="font-mono">{e.key}</span>": "{e.message}</p>
+                                                }).collect::<Vec<_>>()}
+                                            </div>
+                                        })}
+                                    </div>
+                                }.into_any()
+                            }
                             Err(e) => view! {
                                 <p class="text-red-400">"Error: " {e.clone()}</p>
                             }.into_any(),
@@ -3163,7 +5460,7 @@ fn App() -> impl IntoView {
                         <div class="mt-2 space-y-1">
                             <NavItem
                                 label="Blocks"
-                                active=matches!(page.get(), Page::BlockList | Page::BlockDetail { .. } | Page::TransactionDetail { .. } | Page::StateDiff { .. })
+                                active=matches!(page.get(), Page::BlockList | Page::BlockDetail { .. } | Page::PendingBlock | Page::TransactionDetail { .. } | Page::StateDiff { .. })
                                 on_click=move || set_page.set(Page::BlockList)
                             />
                             <NavItem
@@ -3181,6 +5478,31 @@ fn App() -> impl IntoView {
                                 active=matches!(page.get(), Page::AdvancedFilters)
                                 on_click=move || set_page.set(Page::AdvancedFilters)
                             />
+                            <NavItem
+                                label="Tries"
+                                active=matches!(page.get(), Page::Tries)
+                                on_click=move || set_page.set(Page::Tries)
+                            />
+                            <NavItem
+                                label="Mempool"
+                                active=matches!(page.get(), Page::Mempool)
+                                on_click=move || set_page.set(Page::Mempool)
+                            />
+                            <NavItem
+                                label="Messaging"
+                                active=matches!(page.get(), Page::Messaging)
+                                on_click=move || set_page.set(Page::Messaging)
+                            />
+                            <NavItem
+                                label="Block Utilization"
+                                active=matches!(page.get(), Page::BlockUtilization)
+                                on_click=move || set_page.set(Page::BlockUtilization)
+                            />
+                            <NavItem
+                                label="Node Status"
+                                active=matches!(page.get(), Page::NodeStatus)
+                                on_click=move || set_page.set(Page::NodeStatus)
+                            />
                             <NavItem
                                 label="Raw Data"
                                 active=matches!(page.get(), Page::RawData | Page::RawKeyDetail { .. })
@@ -3205,7 +5527,13 @@ fn App() -> impl IntoView {
                     {move || {
                         match page.get() {
                             Page::BlockList => view! {
-                                <BlockList on_select=move |n| set_page.set(Page::BlockDetail { block_number: n }) />
+                                <BlockList
+                                    on_select=move |n| set_page.set(Page::BlockDetail { block_number: n })
+                                    on_pending_select=move || set_page.set(Page::PendingBlock)
+                                />
+                            }.into_any(),
+                            Page::PendingBlock => view! {
+                                <PendingBlockView on_back=move || set_page.set(Page::BlockList) />
                             }.into_any(),
                             Page::BlockDetail { block_number } => view! {
                                 <BlockDetailView
@@ -3277,6 +5605,25 @@ fn App() -> impl IntoView {
                             Page::SqlConsole => view! {
                                 <SqlConsoleView />
                             }.into_any(),
+                            Page::Tries => view! {
+                                <TrieExplorerView />
+                            }.into_any(),
+                            Page::Mempool => view! {
+                                <MempoolView />
+                            }.into_any(),
+                            Page::Messaging => view! {
+                                <MessagingView
+                                    on_tx_select=move |(bn, idx)| set_page.set(Page::TransactionDetail { block_number: bn, tx_index: idx as usize })
+                                />
+                            }.into_any(),
+                            Page::BlockUtilization => view! {
+                                <BlockUtilizationView
+                                    on_block_select=move |n| set_page.set(Page::BlockDetail { block_number: n })
+                                />
+                            }.into_any(),
+                            Page::NodeStatus => view! {
+                                <NodeStatusView />
+                            }.into_any(),
                         }
                     }}
                 </main>
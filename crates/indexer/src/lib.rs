@@ -1,13 +1,13 @@
 //! SQLite indexer for complex queries on Madara DB
 
-use db_reader::DbReader;
+use db_reader::{BuiltinCount, DbReader};
 use hex;
 use rusqlite::{params, Connection};
 use std::path::Path;
 use thiserror::Error;
 
 /// Current schema version - increment when schema changes
-const SCHEMA_VERSION: u32 = 2;
+const SCHEMA_VERSION: u32 = 4;
 
 #[derive(Error, Debug)]
 pub enum IndexerError {
@@ -114,6 +114,11 @@ pub struct IndexedClass {
     pub declared_at_block: Option<i64>,
 }
 
+/// SQLite integers are signed 64-bit; saturate larger counters instead of wrapping
+fn clamp_i64(value: u128) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
 /// SQLite-based indexer for complex queries
 pub struct Indexer {
     conn: Connection,
@@ -163,6 +168,7 @@ impl Indexer {
             DROP TABLE IF EXISTS blocks;
             DROP TABLE IF EXISTS classes;
             DROP TABLE IF EXISTS contracts;
+            DROP TABLE IF EXISTS block_bouncer_weights;
             DROP TABLE IF EXISTS index_status;
             "#,
         )?;
@@ -211,6 +217,21 @@ impl Indexer {
                 max_fee TEXT,
                 calldata_length INTEGER,
                 signature_length INTEGER,
+                steps INTEGER,
+                memory_holes INTEGER,
+                range_check_builtin INTEGER,
+                pedersen_builtin INTEGER,
+                poseidon_builtin INTEGER,
+                ec_op_builtin INTEGER,
+                ecdsa_builtin INTEGER,
+                bitwise_builtin INTEGER,
+                keccak_builtin INTEGER,
+                segment_arena_builtin INTEGER,
+                l1_gas INTEGER,
+                l1_data_gas INTEGER,
+                l2_gas INTEGER,
+                da_l1_gas INTEGER,
+                da_l1_data_gas INTEGER,
                 FOREIGN KEY (block_number) REFERENCES blocks(block_number)
             );
 
@@ -263,6 +284,30 @@ impl Indexer {
                 nonce INTEGER
             );
 
+            -- Block resource usage from the bouncer
+            CREATE TABLE IF NOT EXISTS block_bouncer_weights (
+                block_number INTEGER PRIMARY KEY,
+                l1_gas INTEGER NOT NULL,
+                message_segment_length INTEGER NOT NULL,
+                n_events INTEGER NOT NULL,
+                state_diff_size INTEGER NOT NULL,
+                sierra_gas INTEGER,
+                n_txs INTEGER,
+                proving_gas INTEGER,
+                n_steps INTEGER,
+                add_mod_builtin INTEGER,
+                bitwise_builtin INTEGER,
+                ecdsa_builtin INTEGER,
+                ec_op_builtin INTEGER,
+                keccak_builtin INTEGER,
+                mul_mod_builtin INTEGER,
+                pedersen_builtin INTEGER,
+                poseidon_builtin INTEGER,
+                range_check_builtin INTEGER,
+                range_check96_builtin INTEGER,
+                FOREIGN KEY (block_number) REFERENCES blocks(block_number)
+            );
+
             -- Index status table
             CREATE TABLE IF NOT EXISTS index_status (
                 id INTEGER PRIMARY KEY CHECK (id = 1),
@@ -276,6 +321,8 @@ impl Indexer {
             CREATE INDEX IF NOT EXISTS idx_tx_sender ON transactions(sender_address);
             CREATE INDEX IF NOT EXISTS idx_tx_type ON transactions(tx_type);
             CREATE INDEX IF NOT EXISTS idx_tx_block ON transactions(block_number);
+            CREATE INDEX IF NOT EXISTS idx_tx_steps ON transactions(steps);
+            CREATE INDEX IF NOT EXISTS idx_tx_l2_gas ON transactions(l2_gas);
             CREATE INDEX IF NOT EXISTS idx_events_address ON events(from_address);
             CREATE INDEX IF NOT EXISTS idx_events_key0 ON events(key_0);
             CREATE INDEX IF NOT EXISTS idx_events_block ON events(block_number);
@@ -369,7 +416,7 @@ impl Indexer {
 
         for block_n in start_block..=latest_block {
             // Index block info
-            if let Some(block_detail) = db.get_block_detail(block_n) {
+            if let Some(block_detail) = db.get_block_detail(block_n).ok().flatten() {
                 tx.execute(
                     "INSERT OR REPLACE INTO blocks (block_number, block_hash, parent_hash, state_root, sequencer_address, timestamp, transaction_count, event_count) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                     params![
@@ -386,11 +433,14 @@ impl Indexer {
             }
 
             // Index transactions from this block
-            let block = db.get_block_detail(block_n);
+            let block = db.get_block_detail(block_n).ok().flatten();
             if let Some(block_info) = &block {
                 for (tx_idx, tx_hash) in block_info.tx_hashes.iter().enumerate() {
                     // Try to get full transaction details
-                    let tx_detail = db.get_transaction_detail(block_n, tx_idx as u64);
+                    let tx_detail = db
+                        .get_transaction_detail(block_n, tx_idx as u64)
+                        .ok()
+                        .flatten();
 
                     let (
                         tx_type,
@@ -439,8 +489,18 @@ impl Indexer {
                         )
                     };
 
+                    let resources = tx_detail
+                        .as_ref()
+                        .and_then(|d| d.execution_resources.as_ref());
+                    let resource = |f: fn(&db_reader::ExecutionResources) -> u64| {
+                        resources.map(|r| clamp_i64(u128::from(f(r))))
+                    };
+                    let gas = |f: fn(&db_reader::ExecutionResources) -> u128| {
+                        resources.map(|r| clamp_i64(f(r)))
+                    };
+
                     tx.execute(
-                        "INSERT OR REPLACE INTO transactions (tx_hash, block_number, tx_index, tx_type, version, status, revert_reason, sender_address, nonce, actual_fee, fee_unit, calldata_length, signature_length) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                        "INSERT OR REPLACE INTO transactions (tx_hash, block_number, tx_index, tx_type, version, status, revert_reason, sender_address, nonce, actual_fee, fee_unit, calldata_length, signature_length, steps, memory_holes, range_check_builtin, pedersen_builtin, poseidon_builtin, ec_op_builtin, ecdsa_builtin, bitwise_builtin, keccak_builtin, segment_arena_builtin, l1_gas, l1_data_gas, l2_gas, da_l1_gas, da_l1_data_gas) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)",
                         params![
                             tx_hash,
                             block_n,
@@ -455,6 +515,21 @@ impl Indexer {
                             fee_unit,
                             calldata_len,
                             sig_len,
+                            resource(|r| r.steps),
+                            resource(|r| r.memory_holes),
+                            resource(|r| r.range_check_builtin_applications),
+                            resource(|r| r.pedersen_builtin_applications),
+                            resource(|r| r.poseidon_builtin_applications),
+                            resource(|r| r.ec_op_builtin_applications),
+                            resource(|r| r.ecdsa_builtin_applications),
+                            resource(|r| r.bitwise_builtin_applications),
+                            resource(|r| r.keccak_builtin_applications),
+                            resource(|r| r.segment_arena_builtin),
+                            gas(|r| r.total_gas_consumed.l1_gas),
+                            gas(|r| r.total_gas_consumed.l1_data_gas),
+                            gas(|r| r.total_gas_consumed.l2_gas),
+                            gas(|r| r.data_availability.l1_gas),
+                            gas(|r| r.data_availability.l1_data_gas),
                         ],
                     )?;
 
@@ -483,7 +558,7 @@ impl Indexer {
             }
 
             // Index state diff (storage updates, deployed contracts, classes)
-            if let Some(state_diff) = db.get_state_diff(block_n) {
+            if let Some(state_diff) = db.get_state_diff(block_n).ok().flatten() {
                 // Index storage updates
                 for storage_diff in &state_diff.storage_diffs {
                     for entry in &storage_diff.storage_entries {
@@ -525,6 +600,37 @@ impl Indexer {
                 }
             }
 
+            // Index bouncer weights
+            if let Some(weights) = db.get_bouncer_weights(block_n).ok().flatten() {
+                let int = |n: u64| clamp_i64(u128::from(n));
+                let builtin =
+                    |f: fn(&BuiltinCount) -> u64| weights.builtins.as_ref().map(|b| int(f(b)));
+                tx.execute(
+                    "INSERT OR REPLACE INTO block_bouncer_weights (block_number, l1_gas, message_segment_length, n_events, state_diff_size, sierra_gas, n_txs, proving_gas, n_steps, add_mod_builtin, bitwise_builtin, ecdsa_builtin, ec_op_builtin, keccak_builtin, mul_mod_builtin, pedersen_builtin, poseidon_builtin, range_check_builtin, range_check96_builtin) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+                    params![
+                        block_n,
+                        int(weights.l1_gas),
+                        int(weights.message_segment_length),
+                        int(weights.n_events),
+                        int(weights.state_diff_size),
+                        weights.sierra_gas.map(int),
+                        weights.n_txs.map(int),
+                        weights.proving_gas.map(int),
+                        weights.n_steps.map(int),
+                        builtin(|b| b.add_mod),
+                        builtin(|b| b.bitwise),
+                        builtin(|b| b.ecdsa),
+                        builtin(|b| b.ec_op),
+                        builtin(|b| b.keccak),
+                        builtin(|b| b.mul_mod),
+                        builtin(|b| b.pedersen),
+                        builtin(|b| b.poseidon),
+                        builtin(|b| b.range_check),
+                        builtin(|b| b.range_check96),
+                    ],
+                )?;
+            }
+
             indexed_count += 1;
 
             // Update progress every 10 blocks
@@ -1143,6 +1249,7 @@ impl Indexer {
         "deployed_contracts",
         "classes",
         "contracts",
+        "block_bouncer_weights",
         "index_status",
     ];
 
@@ -8,9 +8,9 @@ use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 use db_reader::version::detect_madara_db_version_for_db_path;
+use db_reader::{AuditProgress, DbReader, SUPPORTED_MADARA_DB_VERSIONS};
 
 const DEFAULT_REPO: &str = "Mohiiit/makimono";
-const SUPPORTED_MADARA_DB_VERSIONS: &[u32] = &[8, 9];
 
 #[derive(Parser, Debug)]
 #[command(name = "makimono")]
@@ -57,6 +57,10 @@ enum Commands {
         /// Explicit release tag to install/run (defaults to alias tag == db version)
         #[arg(long)]
         tag: Option<String>,
+
+        /// Follow a running node instead of reading a frozen snapshot
+        #[arg(long)]
+        follow: bool,
     },
 
     /// Manage installed toolchains
@@ -89,6 +93,18 @@ enum Commands {
         offline: bool,
     },
 
+    /// Check a DB's commitments against the state it stores
+    Audit {
+        #[command(subcommand)]
+        cmd: AuditCmd,
+    },
+
+    /// Recompute stored hashes and compare them with the DB
+    Verify {
+        #[command(subcommand)]
+        cmd: VerifyCmd,
+    },
+
     /// Update the Makimono bootstrapper itself (best-effort)
     SelfUpdate {
         /// GitHub repo to download bootstrapper from
@@ -137,6 +153,42 @@ enum ToolchainCmd {
     List,
 }
 
+#[derive(Subcommand, Debug)]
+enum AuditCmd {
+    /// Recompute each block's global state root from the state diffs and compare it with
+    /// the one recorded in `block_info`.
+    StateRoot {
+        /// Madara base-path or RocksDB directory
+        path: PathBuf,
+
+        /// First block to check; earlier state diffs are still replayed
+        #[arg(long, default_value_t = 0)]
+        from: u64,
+
+        /// Last block to check (defaults to the latest block)
+        #[arg(long)]
+        to: Option<u64>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum VerifyCmd {
+    /// Recompute each block's commitments and block hash from its transactions and state
+    /// diff and compare them with the ones recorded in `block_info`.
+    Blocks {
+        /// Madara base-path or RocksDB directory
+        path: PathBuf,
+
+        /// First block to check
+        #[arg(long, default_value_t = 0)]
+        from: u64,
+
+        /// Last block to check (defaults to, and is capped at, the latest block)
+        #[arg(long)]
+        to: Option<u64>,
+    },
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -165,6 +217,7 @@ fn main() {
             offline,
             repo,
             tag,
+            follow,
         } => cmd_run(
             &ctx,
             &path,
@@ -175,6 +228,7 @@ fn main() {
             offline,
             &repo,
             tag.as_deref(),
+            follow,
         ),
         Commands::Toolchain { cmd } => match cmd {
             ToolchainCmd::Install {
@@ -207,6 +261,12 @@ fn main() {
             tag.as_deref(),
             offline,
         ),
+        Commands::Audit { cmd } => match cmd {
+            AuditCmd::StateRoot { path, from, to } => cmd_audit_state_root(&path, from, to),
+        },
+        Commands::Verify { cmd } => match cmd {
+            VerifyCmd::Blocks { path, from, to } => cmd_verify_blocks(&path, from, to),
+        },
         Commands::SelfUpdate { .. } => {
             // Keeping this as a stub for now; installing/updating a running binary is platform
             // specific and is better done via install scripts.
@@ -468,6 +528,7 @@ fn cmd_run(
     offline: bool,
     repo: &str,
     tag: Option<&str>,
+    follow: bool,
 ) -> Result<(), String> {
     let db_dir = resolve_rocksdb_dir(input)?;
 
@@ -516,7 +577,8 @@ fn cmd_run(
         eprintln!(".db-version note: {err}");
     }
 
-    let mut child = Command::new(&toolchain_bin)
+    let mut command = Command::new(&toolchain_bin);
+    command
         .arg("--db-path")
         .arg(&db_dir)
         .arg("--index-path")
@@ -524,7 +586,12 @@ fn cmd_run(
         .arg("--host")
         .arg(host)
         .arg("--port")
-        .arg(port.to_string())
+        .arg(port.to_string());
+    if follow {
+        command.arg("--follow");
+    }
+
+    let mut child = command
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -545,6 +612,159 @@ fn cmd_run(
     Ok(())
 }
 
+fn cmd_audit_state_root(input: &Path, from: u64, to: Option<u64>) -> Result<(), String> {
+    let db_dir = resolve_rocksdb_dir(input)?;
+    let db =
+        DbReader::open(&db_dir).map_err(|e| format!("failed to open {}: {e}", db_dir.display()))?;
+    let to = match to {
+        Some(to) => to,
+        None => db
+            .get_latest_block_number()
+            .ok_or_else(|| anyhow("database has no blocks"))?,
+    };
+
+    let report = db
+        .audit_state_root(from, to, |progress| match progress {
+            AuditProgress::Replaying { block_number } if block_number % 1000 == 0 => {
+                eprintln!("replaying state diffs: block {block_number}/{from}");
+            }
+            AuditProgress::Checked(check) if !check.matches() => {
+                eprintln!(
+                    "block {}: expected {}, computed {}",
+                    check.block_number, check.expected_state_root, check.computed_state_root
+                );
+            }
+            AuditProgress::Checked(check) if check.block_number % 100 == 0 => {
+                eprintln!("checked block {}/{to}", check.block_number);
+            }
+            _ => {}
+        })
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "Checked {} blocks ({from}..={to}): {} mismatches",
+        report.blocks_checked, report.mismatches
+    );
+    let Some(mismatch) = report.first_mismatch else {
+        print_check(CheckStatus::Ok, "state root", "every block matches");
+        return Ok(());
+    };
+
+    let check = &mismatch.check;
+    let names = |tries: &[db_reader::Trie]| match tries {
+        [] => "none".to_string(),
+        tries => tries
+            .iter()
+            .map(|trie| trie.name())
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    print_check(
+        CheckStatus::Fail,
+        "state root",
+        &format!("first divergent block is {}", check.block_number),
+    );
+    println!("  expected state root:  {}", check.expected_state_root);
+    println!("  computed state root:  {}", check.computed_state_root);
+    println!("  contracts tree root:  {}", check.contracts_tree_root);
+    println!("  classes tree root:    {}", check.classes_tree_root);
+    match &mismatch.divergent_tries {
+        Some(tries) => println!("  disagreeing sub-trie: {}", names(tries)),
+        None => {
+            println!("  disagreeing sub-trie: unknown, the bonsai tries only hold the latest block")
+        }
+    }
+    println!("  changed since match:  {}", names(&mismatch.changed_tries));
+
+    Err(format!(
+        "state root mismatch at block {}",
+        check.block_number
+    ))
+}
+
+fn cmd_verify_blocks(input: &Path, from: u64, to: Option<u64>) -> Result<(), String> {
+    let db_dir = resolve_rocksdb_dir(input)?;
+    let db =
+        DbReader::open(&db_dir).map_err(|e| format!("failed to open {}: {e}", db_dir.display()))?;
+    let latest = db
+        .get_latest_block_number()
+        .ok_or_else(|| anyhow("database has no blocks"))?;
+    let to = match to {
+        Some(to) if to > latest => {
+            print_check(
+                CheckStatus::Warn,
+                "blocks",
+                &format!("--to {to} is past the latest block, checking {from}..={latest}"),
+            );
+            latest
+        }
+        Some(to) => to,
+        None => latest,
+    };
+
+    let mut checked = 0u64;
+    let mut mismatched = Vec::new();
+    let mut missing = 0u64;
+    let mut unreadable = 0u64;
+    for block_number in from..=to {
+        let verification = match db.verify_block(block_number) {
+            Ok(Some(verification)) => verification,
+            Ok(None) => {
+                missing += 1;
+                continue;
+            }
+            Err(e) => {
+                eprintln!("block {block_number}: {e}");
+                unreadable += 1;
+                continue;
+            }
+        };
+        checked += 1;
+        if !verification.is_valid() {
+            for (name, check) in verification.checks() {
+                if !check.matches() {
+                    eprintln!(
+                        "block {block_number}: {name}: expected {}, computed {}",
+                        check.expected, check.computed
+                    );
+                }
+            }
+            mismatched.push(block_number);
+        } else if block_number % 100 == 0 {
+            eprintln!("checked block {block_number}/{to}");
+        }
+    }
+
+    println!(
+        "Checked {checked} blocks ({from}..={to}): {} mismatches",
+        mismatched.len()
+    );
+    if missing > 0 {
+        print_check(
+            CheckStatus::Warn,
+            "blocks",
+            &format!("{missing} blocks not found"),
+        );
+    }
+    if unreadable > 0 {
+        print_check(
+            CheckStatus::Warn,
+            "blocks",
+            &format!("{unreadable} blocks could not be decoded"),
+        );
+    }
+    let Some(first) = mismatched.first() else {
+        print_check(CheckStatus::Ok, "block hashes", "every block matches");
+        return Ok(());
+    };
+    print_check(
+        CheckStatus::Fail,
+        "block hashes",
+        &format!("first mismatching block is {first}"),
+    );
+    Err(format!("{} blocks failed verification", mismatched.len()))
+}
+
 fn cmd_toolchain_list(ctx: &Ctx) -> Result<(), String> {
     let root = toolchains_dir(ctx);
     if !root.exists() {
@@ -14,6 +14,74 @@ pub struct StatsResponse {
     pub madara_db_version: MadaraDbVersionInfo,
 }
 
+/// Node status decoded from the `meta` column family
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeMetaResponse {
+    pub chain_id: Option<String>,
+    pub chain_name: Option<String>,
+    pub genesis_block_hash: Option<String>,
+    pub chain_tip: Option<ChainTipInfo>,
+    /// Latest closed block
+    pub latest_block: Option<u64>,
+    /// Latest block whose state update was seen on L1
+    pub confirmed_on_l1_tip: Option<u64>,
+    /// `latest_block - confirmed_on_l1_tip`
+    pub l1_confirmation_lag: Option<u64>,
+    pub last_synced_l1_event_block: Option<u64>,
+    /// Latest block applied to the bonsai tries
+    pub latest_applied_trie_update: Option<u64>,
+    /// `latest_block - latest_applied_trie_update`
+    pub trie_lag: Option<u64>,
+    pub snap_sync_latest_block: Option<u64>,
+    /// Public fields only; private keys are never served
+    pub devnet_accounts: Vec<DevnetAccountInfo>,
+    pub runtime_exec_config: Option<RuntimeExecConfigInfo>,
+    /// `meta` keys the visualizer does not know
+    pub unknown_keys: Vec<String>,
+    pub decode_errors: Vec<MetaDecodeErrorInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainTipInfo {
+    /// "CONFIRMED" or "PRECONFIRMED"
+    pub status: String,
+    /// Last closed block, or the block under construction
+    pub block_number: u64,
+    pub preconfirmed: Option<PreconfirmedHeaderInfo>,
+}
+
+/// Header of the block under construction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreconfirmedHeaderInfo {
+    pub block_number: u64,
+    pub sequencer_address: String,
+    pub timestamp: u64,
+    pub protocol_version: String,
+    pub gas_prices: GasPrices,
+    pub l1_da_mode: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevnetAccountInfo {
+    pub address: String,
+    pub public_key: String,
+    pub class_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeExecConfigInfo {
+    pub protocol_version: String,
+    pub no_charge_fee: bool,
+    /// Chain config as YAML
+    pub chain_config: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaDecodeErrorInfo {
+    pub key: String,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MadaraDbVersionInfo {
     /// The detected Madara DB schema version (from `.db-version`), if present.
@@ -52,6 +120,39 @@ pub struct BlockDetail {
     pub event_count: u64,
     pub l2_gas_used: u128,
     pub tx_hashes: Vec<String>,
+    pub transaction_commitment: String,
+    pub event_commitment: String,
+    pub state_diff_length: Option<u64>,
+    pub state_diff_commitment: Option<String>,
+    pub receipt_commitment: Option<String>,
+    pub protocol_version: String,
+    pub gas_prices: GasPrices,
+    pub l1_da_mode: String,
+}
+
+/// Block gas prices, in wei (ETH) and fri (STRK)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasPrices {
+    pub eth_l1_gas_price: u128,
+    pub strk_l1_gas_price: u128,
+    pub eth_l1_data_gas_price: u128,
+    pub strk_l1_data_gas_price: u128,
+    pub eth_l2_gas_price: u128,
+    pub strk_l2_gas_price: u128,
+}
+
+/// Error body returned with HTTP 422 when a value exists but fails to decode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodeErrorResponse {
+    /// Always "decode_error"
+    pub error: String,
+    pub message: String,
+    pub cf: String,
+    /// Hex-encoded key
+    pub key: String,
+    /// Byte offset where decoding stopped
+    pub offset: usize,
+    pub expected_type: String,
 }
 
 /// Paginated list of blocks
@@ -92,6 +193,134 @@ pub struct TransactionDetail {
     pub signature: Vec<String>,
     pub nonce: Option<String>,
     pub version: Option<String>,
+    /// Variant-specific fields, tagged by `type` (absent when the transaction could not be decoded)
+    #[serde(default)]
+    pub body: Option<TransactionBody>,
+    /// Receipt execution resources (absent when the transaction could not be decoded)
+    #[serde(default)]
+    pub execution_resources: Option<ExecutionResources>,
+    /// Calls and events decoded with the ABIs stored in `class_info`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedTransactionInfo>,
+}
+
+/// One decoded argument or event field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedArgInfo {
+    pub name: String,
+    pub type_name: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedCallInfo {
+    pub to: String,
+    pub selector: String,
+    /// Function name, when the selector resolves in the target's ABI
+    pub function: Option<String>,
+    /// Named arguments, when the calldata matches the function's inputs
+    pub args: Option<Vec<DecodedArgInfo>>,
+    pub calldata: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedEventInfo {
+    /// Index into `events`
+    pub index: usize,
+    pub from_address: String,
+    pub name: String,
+    pub fields: Vec<DecodedArgInfo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecodedTransactionInfo {
+    pub calls: Vec<DecodedCallInfo>,
+    pub events: Vec<DecodedEventInfo>,
+}
+
+/// Cairo VM resources and gas consumed by a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionResources {
+    pub steps: u64,
+    pub memory_holes: u64,
+    pub range_check_builtin_applications: u64,
+    pub pedersen_builtin_applications: u64,
+    pub poseidon_builtin_applications: u64,
+    pub ec_op_builtin_applications: u64,
+    pub ecdsa_builtin_applications: u64,
+    pub bitwise_builtin_applications: u64,
+    pub keccak_builtin_applications: u64,
+    pub segment_arena_builtin: u64,
+    pub data_availability: GasVector,
+    pub total_gas_consumed: GasVector,
+}
+
+/// Gas amounts per resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasVector {
+    pub l1_gas: u128,
+    pub l1_data_gas: u128,
+    pub l2_gas: u128,
+}
+
+/// Variant-specific transaction fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionBody {
+    Invoke {
+        max_fee: Option<String>,
+        entry_point_selector: Option<String>,
+        v3: Option<V3TransactionFields>,
+        account_deployment_data: Vec<String>,
+    },
+    L1Handler {
+        contract_address: String,
+        entry_point_selector: String,
+    },
+    Declare {
+        class_hash: String,
+        compiled_class_hash: Option<String>,
+        max_fee: Option<String>,
+        v3: Option<V3TransactionFields>,
+        account_deployment_data: Vec<String>,
+    },
+    Deploy {
+        class_hash: String,
+        contract_address_salt: String,
+        constructor_calldata: Vec<String>,
+    },
+    DeployAccount {
+        class_hash: String,
+        contract_address_salt: String,
+        constructor_calldata: Vec<String>,
+        max_fee: Option<String>,
+        v3: Option<V3TransactionFields>,
+    },
+}
+
+/// Fee market fields shared by all V3 transactions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct V3TransactionFields {
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: u64,
+    pub paymaster_data: Vec<String>,
+    pub nonce_data_availability_mode: String,
+    pub fee_data_availability_mode: String,
+}
+
+/// Per-resource bounds of a V3 transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceBoundsMapping {
+    pub l1_gas: ResourceBounds,
+    pub l2_gas: ResourceBounds,
+    pub l1_data_gas: Option<ResourceBounds>,
+}
+
+/// Maximum amount and price per unit for one resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceBounds {
+    pub max_amount: u64,
+    pub max_price_per_unit: u128,
 }
 
 /// Event information
@@ -118,6 +347,27 @@ pub struct TransactionListResponse {
     pub total: usize,
 }
 
+/// Block under construction, decoded from `preconfirmed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingBlockResponse {
+    pub header: PreconfirmedHeaderInfo,
+    /// In execution order
+    pub transactions: Vec<PendingTransactionInfo>,
+    pub undecodable_transactions: u64,
+}
+
+/// Transaction executed into the block under construction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTransactionInfo {
+    pub transaction: TransactionDetail,
+    /// State changes made by this transaction alone
+    pub state_diff: StateDiffResponse,
+    pub declared_class_hash: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub arrived_at: u64,
+    pub paid_fee_on_l1: Option<u128>,
+}
+
 /// Contract information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractResponse {
@@ -126,27 +376,164 @@ pub struct ContractResponse {
     pub nonce: Option<u64>,
 }
 
+/// Class hash of a contract starting at a given block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassHistoryEntry {
+    pub block_number: u64,
+    pub class_hash: String,
+}
+
+/// Contract class upgrade history response (newest first)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractClassHistoryResponse {
+    pub address: String,
+    pub entries: Vec<ClassHistoryEntry>,
+    pub total: usize,
+}
+
 /// Storage entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageEntryResponse {
     pub key: String,
     pub value: String,
+    /// Block at which this value was written
+    #[serde(default)]
+    pub block_number: Option<u64>,
 }
 
 /// Contract storage response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractStorageResponse {
     pub address: String,
+    /// Block the storage was read at (`None` means latest)
+    #[serde(default)]
+    pub block_number: Option<u64>,
     pub entries: Vec<StorageEntryResponse>,
     pub total: usize,
 }
 
+/// One historical version of a storage slot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageHistoryEntry {
+    pub block_number: u64,
+    pub value: String,
+}
+
+/// Storage slot history response (newest first)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageHistoryResponse {
+    pub address: String,
+    pub key: String,
+    pub entries: Vec<StorageHistoryEntry>,
+    pub total: usize,
+}
+
 /// Class information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClassResponse {
     pub class_hash: String,
     pub class_type: String,
     pub compiled_class_hash: Option<String>,
+    /// Decoded class, only returned by the class detail endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<ClassDefinitionInfo>,
+}
+
+/// Contract entry point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryPointInfo {
+    pub selector: String,
+    /// Sierra function index
+    pub function_idx: Option<u64>,
+    /// Legacy bytecode offset
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntryPointsByTypeInfo {
+    pub constructor: Vec<EntryPointInfo>,
+    pub external: Vec<EntryPointInfo>,
+    pub l1_handler: Vec<EntryPointInfo>,
+}
+
+/// Fully decoded class definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassDefinitionInfo {
+    pub declared_at: u64,
+    pub contract_class_version: Option<String>,
+    pub sierra_version: Option<String>,
+    pub compiler_version: Option<String>,
+    /// Sierra program length in felts, or the compressed legacy program size in bytes
+    pub program_length: usize,
+    pub entry_points: EntryPointsByTypeInfo,
+    pub abi: Option<serde_json::Value>,
+    pub compiled_class_hash: Option<String>,
+    pub compiled_class_hash_v2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legacy_program: Option<LegacyProgramInfo>,
+    /// Why the legacy program could not be decompressed or parsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legacy_program_error: Option<String>,
+    /// Stored compiled class hash checked against the declaring state diff, for Sierra classes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiled_class_hash_check: Option<CompiledClassHashCheckInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledClassHashCheckInfo {
+    pub declared_at: u64,
+    /// Compiled class hash recorded in the declaring block's state diff
+    pub state_diff_compiled_class_hash: Option<String>,
+    pub matches: bool,
+}
+
+/// CASM entry point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasmEntryPointInfo {
+    pub selector: String,
+    pub offset: u64,
+    pub builtins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CasmEntryPointsByTypeInfo {
+    pub constructor: Vec<CasmEntryPointInfo>,
+    pub external: Vec<CasmEntryPointInfo>,
+    pub l1_handler: Vec<CasmEntryPointInfo>,
+}
+
+/// Hints attached to one program counter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasmHintInfo {
+    pub pc: u64,
+    pub hints: Vec<serde_json::Value>,
+}
+
+/// Compiled class (CASM) read from `class_compiled`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledClassResponse {
+    pub class_hash: String,
+    pub compiled_class_hash: String,
+    pub block_number: u64,
+    pub compiler_version: String,
+    pub prime: String,
+    pub bytecode_length: usize,
+    pub entry_points: CasmEntryPointsByTypeInfo,
+    pub hints_count: usize,
+    pub hints: Vec<CasmHintInfo>,
+}
+
+/// Summary of a decompressed Cairo 0 program
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegacyProgramInfo {
+    pub builtins: Vec<String>,
+    pub prime: String,
+    pub compiler_version: Option<String>,
+    pub main_scope: Option<String>,
+    pub data_length: usize,
+    pub hints_count: usize,
+    pub identifiers_count: usize,
+    pub decompressed_size: usize,
 }
 
 /// List of contracts
@@ -218,6 +605,465 @@ pub struct ReplacedClassInfo {
     pub class_hash: String,
 }
 
+// Trie types
+
+/// A node visited on the way from a trie root to a key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrieNodeInfo {
+    /// Path of the node from the root, as a bit string
+    pub path: String,
+    /// "binary" or "edge"
+    pub kind: String,
+    pub hash: Option<String>,
+    pub height: u64,
+    /// Binary nodes: child hashes and the branch taken ("left" or "right")
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub taken: Option<String>,
+    /// Edge nodes: compressed path segment (bit string) and child hash
+    pub edge_path: Option<String>,
+    pub child: Option<String>,
+}
+
+/// Root-to-key walk through a bonsai trie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriePathResponse {
+    pub trie: String,
+    /// Hex-encoded trie identifier (contract address for storage tries)
+    pub identifier: String,
+    pub key: String,
+    pub root_hash: Option<String>,
+    pub nodes: Vec<TrieNodeInfo>,
+    /// "leaf", "diverged", "missing_node" or "empty_trie"
+    pub end: String,
+    /// Leaf value committed in the trie when `end` is "leaf"
+    pub leaf: Option<String>,
+    /// Depth where the key leaves the trie when `end` is "diverged"
+    pub diverged_at: Option<u64>,
+    /// Path of the missing node when `end` is "missing_node"
+    pub missing_path: Option<String>,
+    /// Value stored for the key in the flat column family
+    pub flat_value: Option<String>,
+}
+
+/// A trie leaf rewritten by a block, decoded from the bonsai trie logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrieLeafChangeInfo {
+    /// "contract", "storage" or "class"
+    pub trie: String,
+    /// Contract owning the storage trie, for storage leaves
+    pub contract_address: Option<String>,
+    pub key: String,
+    /// `None` when the block inserted the leaf
+    pub old_value: Option<String>,
+    /// `None` when the block removed the leaf
+    pub new_value: Option<String>,
+}
+
+/// A leaf where the trie log and the block's state diff disagree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrieLogMismatchInfo {
+    pub trie: String,
+    pub contract_address: Option<String>,
+    pub key: String,
+    pub reason: String,
+}
+
+/// Trie changes recorded for a block in the bonsai trie logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrieChangesResponse {
+    pub block_number: u64,
+    /// `false` when the block has no trie log: it changed nothing, or the log was pruned
+    pub has_log: bool,
+    /// Internal trie nodes rewritten across all three tries
+    pub nodes_changed: usize,
+    pub leaves: Vec<TrieLeafChangeInfo>,
+    pub mismatches: Vec<TrieLogMismatchInfo>,
+}
+
+// Event search types
+
+/// Request body for searching events, following `starknet_getEvents`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventSearchRequest {
+    #[serde(default)]
+    pub from_block: Option<u64>,
+    /// Defaults to the latest block
+    #[serde(default)]
+    pub to_block: Option<u64>,
+    /// Emitting contract
+    #[serde(default)]
+    pub address: Option<String>,
+    /// Accepted values per key position; an empty position matches any key
+    #[serde(default)]
+    pub keys: Vec<Vec<String>>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventMatchInfo {
+    pub block_number: u64,
+    pub tx_index: usize,
+    pub event_index: usize,
+    pub tx_hash: String,
+    pub from_address: String,
+    pub keys: Vec<String>,
+    pub data: Vec<String>,
+}
+
+/// How well the `events_bloom` filters pruned a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSearchStats {
+    pub blocks_in_range: u64,
+    pub blocks_with_bloom: u64,
+    pub blocks_scanned: u64,
+    /// Blocks whose filter passed the query
+    pub candidate_blocks: u64,
+    /// Candidate blocks without a matching event
+    pub false_positive_blocks: u64,
+    pub false_positive_rate: f64,
+    /// Mean single-item false-positive rate implied by how full the filters are
+    pub expected_item_false_positive_rate: f64,
+    /// `false` when every block in range was scanned instead
+    pub bloom_pruning: bool,
+    /// `false` when a scanned block had events missing from its filter
+    pub bloom_consistent: bool,
+    pub undecodable_transactions: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSearchResponse {
+    pub events: Vec<EventMatchInfo>,
+    pub stats: EventSearchStats,
+    /// Lowered to the latest block
+    pub to_block: u64,
+    /// Set when the search stopped at `limit` or at the per-call block cap; continue from the
+    /// next block
+    pub last_block_scanned: Option<u64>,
+}
+
+// Mempool types
+
+/// Transaction waiting in the mempool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolTransactionInfo {
+    pub tx_hash: String,
+    pub tx_type: String,
+    /// Sender, or the address being deployed for DEPLOY_ACCOUNT
+    pub contract_address: String,
+    pub nonce: Option<u64>,
+    pub version: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub arrived_at: u64,
+    pub max_fee: Option<String>,
+    pub tip: Option<u64>,
+    pub resource_bounds: Option<ResourceBoundsMapping>,
+    pub paid_fee_on_l1: Option<u128>,
+    pub charge_fee: bool,
+    pub declared_class_hash: Option<String>,
+    /// An earlier nonce of the same account is missing from the mempool
+    pub blocked_by_nonce_gap: bool,
+}
+
+/// Mempool nonces of one account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolAccountInfo {
+    pub address: String,
+    pub current_nonce: Option<u64>,
+    pub nonces: Vec<u64>,
+    /// Capped list; `missing_count` has the full size of the gap
+    pub missing_nonces: Vec<u64>,
+    pub missing_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolResponse {
+    pub transactions: Vec<MempoolTransactionInfo>,
+    /// Every account with a nonce in the mempool, sorted by address
+    pub accounts: Vec<MempoolAccountInfo>,
+    /// Transactions in the mempool, before `limit`
+    pub total: usize,
+    pub sort: String,
+}
+
+// Messaging types
+
+/// L1→L2 message, pending or consumed by an L1 handler transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1ToL2MessageInfo {
+    pub nonce: u64,
+    /// "PENDING" or "CONSUMED"
+    pub status: String,
+    pub message_hash: Option<String>,
+    /// L1 sender
+    pub from_address: Option<String>,
+    /// L2 recipient
+    pub to_address: Option<String>,
+    pub entry_point_selector: Option<String>,
+    pub payload: Vec<String>,
+    pub paid_fee_on_l1: Option<u128>,
+    /// L1 handler transaction that consumed the message
+    pub tx_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub tx_index: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1ToL2MessageListResponse {
+    pub messages: Vec<L1ToL2MessageInfo>,
+    pub pending: usize,
+    pub consumed: usize,
+    /// Nonces whose pending message or L1 handler transaction failed to decode
+    pub undecodable_messages: u64,
+}
+
+/// L2→L1 message sent by a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L2ToL1MessageInfo {
+    pub block_number: u64,
+    pub tx_index: usize,
+    pub message_index: usize,
+    pub tx_hash: String,
+    pub from_address: String,
+    pub to_address: String,
+    pub payload: Vec<String>,
+    pub message_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L2ToL1MessageListResponse {
+    pub messages: Vec<L2ToL1MessageInfo>,
+    pub from_block: u64,
+    /// Lowered to the latest block
+    pub to_block: u64,
+    /// Set when the listing stopped at `limit` or at the per-call block cap; continue from
+    /// the next block
+    pub last_block_scanned: Option<u64>,
+    pub undecodable_transactions: u64,
+}
+
+// Block utilization types
+
+/// Resources one block used, from `block_bouncer_weight`. Fields marked 0.14 or legacy
+/// are `None` for blocks written with the other bouncer layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BouncerWeightsInfo {
+    pub block_number: u64,
+    pub l1_gas: u64,
+    pub message_segment_length: u64,
+    pub n_events: u64,
+    pub state_diff_size: u64,
+    /// 0.14
+    pub sierra_gas: Option<u64>,
+    /// 0.14
+    pub n_txs: Option<u64>,
+    /// 0.14
+    pub proving_gas: Option<u64>,
+    /// Legacy
+    pub n_steps: Option<u64>,
+    /// Legacy: sum over all builtins
+    pub builtin_count: Option<u64>,
+    /// Legacy: builtin name -> applications
+    #[serde(default)]
+    pub builtins: Vec<(String, u64)>,
+}
+
+/// Per-block limits from the node's chain config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BouncerCapacityInfo {
+    pub l1_gas: Option<u64>,
+    pub message_segment_length: Option<u64>,
+    pub n_events: Option<u64>,
+    pub state_diff_size: Option<u64>,
+    pub sierra_gas: Option<u64>,
+    pub n_txs: Option<u64>,
+    pub proving_gas: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BouncerWeightsResponse {
+    /// Ascending by block number
+    pub blocks: Vec<BouncerWeightsInfo>,
+    pub from_block: u64,
+    pub to_block: u64,
+    pub undecodable_blocks: u64,
+    /// `None` when the chain config is not stored in the database or fails to decode
+    pub block_max_capacity: Option<BouncerCapacityInfo>,
+    /// Why the stored chain config could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_max_capacity_error: Option<String>,
+}
+
+// Storage proof types (`starknet_getStorageProof` shape)
+
+/// Merkle node in a proof: `{left, right}` for binary nodes, `{path, length, child}` for edges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MerkleNode {
+    Binary {
+        left: String,
+        right: String,
+    },
+    Edge {
+        path: String,
+        length: u8,
+        child: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeHashToNode {
+    pub node_hash: String,
+    pub node: MerkleNode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractLeafData {
+    pub nonce: String,
+    pub class_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_root: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractsProof {
+    pub nodes: Vec<NodeHashToNode>,
+    pub contract_leaves_data: Vec<ContractLeafData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalRoots {
+    pub contracts_tree_root: String,
+    pub classes_tree_root: String,
+    pub block_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageProofResponse {
+    pub classes_proof: Vec<NodeHashToNode>,
+    pub contracts_proof: ContractsProof,
+    pub contracts_storage_proofs: Vec<Vec<NodeHashToNode>>,
+    pub global_roots: GlobalRoots,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractStorageKeys {
+    pub contract_address: String,
+    pub storage_keys: Vec<String>,
+}
+
+/// Request body for generating a storage proof
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageProofRequest {
+    #[serde(default)]
+    pub class_hashes: Vec<String>,
+    #[serde(default)]
+    pub contract_addresses: Vec<String>,
+    #[serde(default)]
+    pub contracts_storage_keys: Vec<ContractStorageKeys>,
+}
+
+/// Request body for verifying a storage proof against a block's state root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyStorageProofRequest {
+    #[serde(flatten)]
+    pub request: StorageProofRequest,
+    pub proof: StorageProofResponse,
+    /// Block whose state root to check against; defaults to the latest block
+    #[serde(default)]
+    pub block_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofCheckResponse {
+    pub trie: String,
+    pub contract_address: Option<String>,
+    pub key: String,
+    /// Leaf value proven for the key; `None` when the proof shows it is absent
+    pub value: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofVerificationResponse {
+    pub block_number: Option<u64>,
+    pub expected_state_root: Option<String>,
+    pub computed_state_root: Option<String>,
+    pub state_root_matches: bool,
+    pub checks: Vec<ProofCheckResponse>,
+    pub valid: bool,
+}
+
+// State-root audit types
+
+/// Request body for starting a state-root audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateRootAuditRequest {
+    #[serde(default)]
+    pub from: u64,
+    /// Last block to check; defaults to the latest block
+    #[serde(default)]
+    pub to: Option<u64>,
+}
+
+/// First block whose recomputed state root differs from `block_info`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateRootMismatchInfo {
+    pub block_number: u64,
+    pub expected_state_root: String,
+    pub computed_state_root: String,
+    pub contracts_tree_root: String,
+    pub classes_tree_root: String,
+    /// Sub-tries ("contract", "class") whose recomputed root differs from the on-disk one;
+    /// only known when the mismatch is at the latest block
+    pub divergent_tries: Option<Vec<String>>,
+    /// Sub-tries that changed since the last matching block
+    pub changed_tries: Vec<String>,
+}
+
+/// Progress and result of a state-root audit job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateRootAuditStatus {
+    pub id: u64,
+    pub from: u64,
+    pub to: u64,
+    /// "running", "done" or "failed"
+    pub status: String,
+    /// Block being replayed or checked
+    pub current_block: Option<u64>,
+    pub blocks_checked: u64,
+    pub mismatches: u64,
+    pub first_mismatch: Option<StateRootMismatchInfo>,
+    pub error: Option<String>,
+}
+
+// Block verification types
+
+/// A commitment or block hash recomputed from the stored block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashCheckInfo {
+    /// "transaction_commitment", "event_commitment", "receipt_commitment",
+    /// "state_diff_commitment" or "block_hash"
+    pub name: String,
+    /// Stored in `block_info`
+    pub expected: String,
+    pub computed: String,
+    pub matches: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockVerificationResponse {
+    pub block_number: u64,
+    pub protocol_version: String,
+    /// Hashing rules picked from the protocol version
+    pub hash_rules: String,
+    /// Every check matched
+    pub valid: bool,
+    /// Checks the header supports; pre-0.13.2 headers have no receipt or state diff commitment
+    pub checks: Vec<HashCheckInfo>,
+}
+
 // Search types
 
 /// Search result response